@@ -0,0 +1,757 @@
+use super::AdminState;
+use crate::{
+    api::chat::{embed_content, EmbeddingOutcome},
+    auth::UserInfo,
+    error::{ApiError, ApiResult},
+    models::{
+        bundle::ContentBundle,
+        certificate::CertificateUpdate,
+        project::ProjectUpdate,
+        revision::{diff, FieldChange},
+        Certificate, ContentKind, Project, Revision,
+    },
+    repositories::{FieldChanges, VersionCheck},
+    services::slug::slugify,
+};
+use axum::{
+    body::Bytes,
+    extract::{Query, State},
+    http::{header, HeaderMap, HeaderValue},
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{collections::HashSet, sync::Arc};
+use validator::{Validate, ValidationErrors};
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+/// Serialization of an export or import bundle
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum BundleFormat {
+    #[default]
+    Json,
+    Yaml,
+}
+
+impl BundleFormat {
+    /// Format of an import body - JSON unless a YAML media type is given
+    fn from_content_type(headers: &HeaderMap) -> ApiResult<Self> {
+        let content_type = headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or("application/json");
+        let media_type = content_type.split(';').next().unwrap_or_default().trim();
+
+        match media_type.to_ascii_lowercase().as_str() {
+            "application/json" => Ok(Self::Json),
+            "application/yaml" | "application/x-yaml" | "text/yaml" => Ok(Self::Yaml),
+            other => Err(ApiError::UnsupportedMediaType(format!(
+                "Bundles must be JSON or YAML, got '{}'",
+                other
+            ))),
+        }
+    }
+
+    fn content_type(self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::Yaml => "application/yaml",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Yaml => "yaml",
+        }
+    }
+
+    fn encode(self, bundle: &ContentBundle) -> ApiResult<Vec<u8>> {
+        let encoded = match self {
+            Self::Json => serde_json::to_vec_pretty(bundle).map_err(|e| e.to_string()),
+            Self::Yaml => serde_yaml::to_string(bundle)
+                .map(String::into_bytes)
+                .map_err(|e| e.to_string()),
+        };
+        encoded.map_err(|e| ApiError::InternalError(format!("Failed to encode bundle: {}", e)))
+    }
+
+    fn decode(self, body: &[u8]) -> ApiResult<ContentBundle> {
+        let decoded = match self {
+            Self::Json => serde_json::from_slice(body).map_err(|e| e.to_string()),
+            Self::Yaml => serde_yaml::from_slice(body).map_err(|e| e.to_string()),
+        };
+        decoded.map_err(|e| ApiError::BadRequest(format!("Invalid bundle: {}", e)))
+    }
+}
+
+/// Query parameters for exporting content
+#[derive(Debug, Default, Deserialize)]
+pub struct ExportParams {
+    /// json (default) or yaml
+    #[serde(default)]
+    pub format: BundleFormat,
+}
+
+/// What to do with bundle items whose slug already exists
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum OnConflict {
+    /// Overwrite the existing item with the bundle's content
+    #[default]
+    Update,
+    /// Keep the existing item
+    Skip,
+}
+
+/// Query parameters for importing content
+#[derive(Debug, Default, Deserialize)]
+pub struct ImportParams {
+    /// Report what would change without writing anything
+    #[serde(default)]
+    pub dry_run: bool,
+    #[serde(default)]
+    pub on_conflict: OnConflict,
+}
+
+/// Outcome of one bundle item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum ImportAction {
+    Create,
+    Update,
+    Unchanged,
+    Skip,
+    /// Rejected before any write - see `errors`
+    Invalid,
+    /// Valid, but the write failed
+    Failed,
+}
+
+/// Per-item line of the import report
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ImportItem {
+    pub kind: ContentKind,
+    /// Index of the item within its list in the bundle
+    pub index: usize,
+    /// File the item was read from, for uploads of several files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    pub slug: String,
+    pub action: ImportAction,
+    /// Field-level differences to the stored item, for updates
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<FieldChange>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+    /// Content of the source that was left out, the item itself is fine
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "swagger", schema(value_type = Option<Object>))]
+    pub embedding: Option<EmbeddingOutcome>,
+}
+
+impl ImportItem {
+    pub(super) fn new(kind: ContentKind, index: usize, value: &Value) -> Self {
+        Self {
+            kind,
+            index,
+            source: None,
+            slug: value["slug"].as_str().unwrap_or_default().to_string(),
+            action: ImportAction::Invalid,
+            changes: Vec::new(),
+            errors: Vec::new(),
+            warnings: Vec::new(),
+            embedding: None,
+        }
+    }
+
+    pub(super) fn rejected(mut self, action: ImportAction, error: impl ToString) -> Self {
+        self.action = action;
+        self.errors.push(error.to_string());
+        self
+    }
+}
+
+/// Summary and per-item results of an import
+#[derive(Debug, Clone, Default, Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub created: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    pub skipped: usize,
+    pub invalid: usize,
+    pub failed: usize,
+    pub items: Vec<ImportItem>,
+}
+
+impl ImportReport {
+    pub(super) fn push(&mut self, item: ImportItem) {
+        let counter = match item.action {
+            ImportAction::Create => &mut self.created,
+            ImportAction::Update => &mut self.updated,
+            ImportAction::Unchanged => &mut self.unchanged,
+            ImportAction::Skip => &mut self.skipped,
+            ImportAction::Invalid => &mut self.invalid,
+            ImportAction::Failed => &mut self.failed,
+        };
+        *counter += 1;
+        self.items.push(item);
+    }
+
+    /// Log the summary
+    pub(super) fn finish(&self, user: &UserInfo, source: &str) {
+        tracing::info!(
+            "Admin {} imported {} (dry run: {}): {} created, {} updated, {} unchanged, {} skipped, {} invalid, {} failed",
+            user.email,
+            source,
+            self.dry_run,
+            self.created,
+            self.updated,
+            self.unchanged,
+            self.skipped,
+            self.invalid,
+            self.failed
+        );
+    }
+}
+
+/// `field: code` messages of failed validation rules
+fn validation_messages(errors: &ValidationErrors) -> Vec<String> {
+    let mut messages: Vec<String> = errors
+        .field_errors()
+        .iter()
+        .flat_map(|(field, errors)| {
+            errors.iter().map(move |e| match &e.message {
+                Some(message) => format!("{}: {}", field, message),
+                None => format!("{}: failed {} validation", field, e.code),
+            })
+        })
+        .collect();
+    messages.sort();
+    messages
+}
+
+/// Normalized slug of an item, from its own slug or else its title
+/// Slugs repeated within the bundle are rejected, the first occurrence wins
+fn bundle_slug(slug: &str, title: &str, seen: &mut HashSet<String>) -> Result<String, String> {
+    let slug = match slugify(slug) {
+        slug if slug.is_empty() => slugify(title),
+        slug => slug,
+    };
+    if slug.is_empty() {
+        return Err(format!("Cannot derive a slug from '{}'", title));
+    }
+    if !seen.insert(slug.clone()) {
+        return Err(format!(
+            "Slug '{}' appears more than once in the bundle",
+            slug
+        ));
+    }
+    Ok(slug)
+}
+
+/// Export every live project and certificate as a JSON or YAML bundle (Admin only)
+#[cfg_attr(feature = "swagger", utoipa::path(
+    get,
+    path = "/api/v1/admin/export",
+    params(
+        ("format" = Option<BundleFormat>, Query, description = "json (default) or yaml")
+    ),
+    responses(
+        (status = 200, description = "Content bundle, as an attachment", body = ContentBundle),
+        (status = 401, description = "Unauthorized - Missing or invalid token"),
+        (status = 403, description = "Forbidden - Not an admin user")
+    ),
+    security(
+        ("google_oauth" = ["openid", "email", "profile"])
+    ),
+    tag = "admin"
+))]
+pub async fn export_content(
+    State(state): State<Arc<AdminState>>,
+    Extension(user): Extension<UserInfo>,
+    Query(params): Query<ExportParams>,
+) -> ApiResult<Response> {
+    let projects = state.repositories.projects.list().await?;
+    let certificates = state.repositories.certificates.list().await?;
+    let bundle = ContentBundle::new(&projects, &certificates)?;
+    let body = params.format.encode(&bundle)?;
+
+    tracing::info!(
+        "Admin {} exported {} projects and {} certificates",
+        user.email,
+        projects.len(),
+        certificates.len()
+    );
+    let disposition = format!(
+        "attachment; filename=\"portfolio-{}.{}\"",
+        Utc::now().format("%Y-%m-%d"),
+        params.format.extension()
+    );
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                HeaderValue::from_static(params.format.content_type()),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                HeaderValue::from_str(&disposition)
+                    .map_err(|e| ApiError::InternalError(e.to_string()))?,
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+/// Import a JSON or YAML bundle, upserting items by slug (Admin only)
+/// Every item is validated on its own - invalid items are reported, the rest are still imported
+#[cfg_attr(feature = "swagger", utoipa::path(
+    post,
+    path = "/api/v1/admin/import",
+    request_body(
+        content = ContentBundle,
+        description = "Bundle as produced by the export endpoint",
+        content_type = "application/json"
+    ),
+    params(
+        ("dry_run" = Option<bool>, Query, description = "Only report what would change"),
+        ("on_conflict" = Option<OnConflict>, Query, description = "update (default) or skip items whose slug exists")
+    ),
+    responses(
+        (status = 200, description = "Per-item import report", body = ImportReport),
+        (status = 400, description = "Malformed bundle or unsupported bundle version"),
+        (status = 415, description = "Body is neither JSON nor YAML"),
+        (status = 401, description = "Unauthorized - Missing or invalid token"),
+        (status = 403, description = "Forbidden - Not an admin user")
+    ),
+    security(
+        ("google_oauth" = ["openid", "email", "profile"])
+    ),
+    tag = "admin"
+))]
+pub async fn import_content(
+    State(state): State<Arc<AdminState>>,
+    Extension(user): Extension<UserInfo>,
+    Query(params): Query<ImportParams>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ApiResult<Json<ImportReport>> {
+    let bundle = BundleFormat::from_content_type(&headers)?.decode(&body)?;
+    bundle.check_version()?;
+
+    let mut report = ImportReport {
+        dry_run: params.dry_run,
+        ..ImportReport::default()
+    };
+    let mut seen = HashSet::new();
+    for (index, value) in bundle.projects.into_iter().enumerate() {
+        let item = ImportItem::new(ContentKind::Project, index, &value);
+        let item = import_project(&state, &params, &user, &mut seen, item, value).await;
+        report.push(item);
+    }
+    let mut seen = HashSet::new();
+    for (index, value) in bundle.certificates.into_iter().enumerate() {
+        let item = ImportItem::new(ContentKind::Certificate, index, &value);
+        let item = import_certificate(&state, &params, &user, &mut seen, item, value).await;
+        report.push(item);
+    }
+
+    report.finish(&user, "a bundle");
+    Ok(Json(report))
+}
+
+/// Validate and upsert one project of an import, never failing the import as a whole
+pub(super) async fn import_project(
+    state: &AdminState,
+    params: &ImportParams,
+    user: &UserInfo,
+    seen: &mut HashSet<String>,
+    mut item: ImportItem,
+    value: Value,
+) -> ImportItem {
+    let repo = &state.repositories.projects;
+    let mut project: Project = match serde_json::from_value(value) {
+        Ok(project) => project,
+        Err(e) => return item.rejected(ImportAction::Invalid, e),
+    };
+    project.slug = match bundle_slug(&project.slug, &project.title, seen) {
+        Ok(slug) => slug,
+        Err(e) => return item.rejected(ImportAction::Invalid, e),
+    };
+    item.slug = project.slug.clone();
+    if let Err(e) = project.validate() {
+        item.errors = validation_messages(&e);
+        return item;
+    }
+    // Server-managed state is never taken from a bundle
+    project.id = None;
+    project.clear_embedding();
+    project.deleted_at = None;
+    project.deleted_by = None;
+    project.version = 0;
+
+    let result = async {
+        match repo.get_by_slug(&project.slug).await? {
+            Some(_) if params.on_conflict == OnConflict::Skip => {
+                item.action = ImportAction::Skip;
+            }
+            Some(existing) => {
+                let before = ProjectUpdate::from(existing);
+                let mut after = ProjectUpdate::from(project.clone());
+                after.retire_draft_visibility();
+                let changes = FieldChanges::between(&before, &after)?;
+                item.changes = diff(
+                    &mongodb::bson::to_document(&before)?,
+                    &mongodb::bson::to_document(&after)?,
+                );
+                item.action = if changes.is_empty() {
+                    ImportAction::Unchanged
+                } else {
+                    ImportAction::Update
+                };
+                if params.dry_run || changes.is_empty() {
+                    return Ok(());
+                }
+
+                let previous = repo
+                    .patch(&project.slug, &changes, &VersionCheck::Any)
+                    .await?
+                    .ok_or_else(|| {
+                        ApiError::NotFound(format!("Project '{}' not found", project.slug))
+                    })?;
+                state
+                    .repositories
+                    .revisions
+                    .record(Revision::capture(
+                        ContentKind::Project,
+                        &previous,
+                        &user.email,
+                    )?)
+                    .await?;
+                if after.embedding_text() != before.embedding_text() {
+                    let (embedding, outcome) =
+                        embed_content(state.embedder.as_ref(), &after.embedding_text()).await;
+                    match embedding {
+                        Some(embedding) => {
+                            repo.set_embedding(&project.slug, embedding.values, &embedding.model)
+                                .await?;
+                        }
+                        None => {
+                            repo.clear_embedding(&project.slug).await?;
+                        }
+                    }
+                    item.embedding = Some(outcome);
+                }
+            }
+            None if repo.slug_in_use(&project.slug).await? => {
+                item.action = ImportAction::Invalid;
+                item.errors.push(format!(
+                    "Slug '{}' belongs to a trashed project, restore or purge it first",
+                    project.slug
+                ));
+            }
+            None => {
+                item.action = ImportAction::Create;
+                if params.dry_run {
+                    return Ok(());
+                }
+                project.retire_draft_visibility();
+                let (embedding, outcome) =
+                    embed_content(state.embedder.as_ref(), &project.embedding_text()).await;
+                if let Some(embedding) = embedding {
+                    project.set_embedding(embedding.values, embedding.model);
+                }
+                item.embedding = Some(outcome);
+                repo.create(project.clone()).await?;
+            }
+        }
+        Ok::<_, ApiError>(())
+    }
+    .await;
+
+    match result {
+        Ok(()) => item,
+        Err(e) => item.rejected(ImportAction::Failed, e),
+    }
+}
+
+/// Validate and upsert one certificate of an import, never failing the import as a whole
+async fn import_certificate(
+    state: &AdminState,
+    params: &ImportParams,
+    user: &UserInfo,
+    seen: &mut HashSet<String>,
+    mut item: ImportItem,
+    mut value: Value,
+) -> ImportItem {
+    let repo = &state.repositories.certificates;
+    // Slugs are optional in bundles, they are derived from the name when missing
+    if let Some(fields) = value.as_object_mut() {
+        fields.entry("slug").or_insert_with(|| json!(""));
+    }
+    let mut certificate: Certificate = match serde_json::from_value(value) {
+        Ok(certificate) => certificate,
+        Err(e) => return item.rejected(ImportAction::Invalid, e),
+    };
+    certificate.slug = match bundle_slug(&certificate.slug, &certificate.name, seen) {
+        Ok(slug) => slug,
+        Err(e) => return item.rejected(ImportAction::Invalid, e),
+    };
+    item.slug = certificate.slug.clone();
+    if let Err(e) = certificate.validate() {
+        item.errors = validation_messages(&e);
+        return item;
+    }
+    // Server-managed state is never taken from a bundle
+    certificate.id = None;
+    certificate.clear_embedding();
+    certificate.deleted_at = None;
+    certificate.deleted_by = None;
+    certificate.set_verification(None);
+    certificate.version = 0;
+
+    let result = async {
+        match repo.get_by_slug(&certificate.slug).await? {
+            Some(_) if params.on_conflict == OnConflict::Skip => {
+                item.action = ImportAction::Skip;
+            }
+            Some(existing) => {
+                let before = CertificateUpdate::from(existing);
+                let after = CertificateUpdate::from(certificate.clone());
+                let changes = FieldChanges::between(&before, &after)?;
+                item.changes = diff(
+                    &mongodb::bson::to_document(&before)?,
+                    &mongodb::bson::to_document(&after)?,
+                );
+                item.action = if changes.is_empty() {
+                    ImportAction::Unchanged
+                } else {
+                    ImportAction::Update
+                };
+                if params.dry_run || changes.is_empty() {
+                    return Ok(());
+                }
+
+                let previous = repo
+                    .patch(&certificate.slug, &changes, &VersionCheck::Any)
+                    .await?
+                    .ok_or_else(|| {
+                        ApiError::NotFound(format!("Certificate '{}' not found", certificate.slug))
+                    })?;
+                state
+                    .repositories
+                    .revisions
+                    .record(Revision::capture(
+                        ContentKind::Certificate,
+                        &previous,
+                        &user.email,
+                    )?)
+                    .await?;
+                if after.embedding_text() != before.embedding_text() {
+                    let (embedding, outcome) =
+                        embed_content(state.embedder.as_ref(), &after.embedding_text()).await;
+                    match embedding {
+                        Some(embedding) => {
+                            repo.set_embedding(
+                                &certificate.slug,
+                                embedding.values,
+                                &embedding.model,
+                            )
+                            .await?;
+                        }
+                        None => {
+                            repo.clear_embedding(&certificate.slug).await?;
+                        }
+                    }
+                    item.embedding = Some(outcome);
+                }
+            }
+            None if repo.slug_in_use(&certificate.slug).await? => {
+                item.action = ImportAction::Invalid;
+                item.errors.push(format!(
+                    "Slug '{}' belongs to a trashed certificate, restore or purge it first",
+                    certificate.slug
+                ));
+            }
+            None => {
+                item.action = ImportAction::Create;
+                if params.dry_run {
+                    return Ok(());
+                }
+                let (embedding, outcome) =
+                    embed_content(state.embedder.as_ref(), &certificate.embedding_text()).await;
+                if let Some(embedding) = embedding {
+                    certificate.set_embedding(embedding.values, embedding.model);
+                }
+                item.embedding = Some(outcome);
+                repo.create(certificate.clone()).await?;
+            }
+        }
+        Ok::<_, ApiError>(())
+    }
+    .await;
+
+    match result {
+        Ok(()) => item,
+        Err(e) => item.rejected(ImportAction::Failed, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        api::test_support::{admin_app, body_json, test_auth},
+        repositories::Repositories,
+    };
+    use axum::{
+        body::{to_bytes, Body},
+        http::{Request, StatusCode},
+        Router,
+    };
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    fn app(repositories: &Repositories) -> (Router, String) {
+        let (_, token) = test_auth();
+        let app = admin_app(repositories);
+        (app, token)
+    }
+
+    #[tokio::test]
+    async fn test_export_then_import_with_dry_run_and_item_errors() {
+        let source = Repositories::in_memory();
+        let project = serde_json::from_value(json!({
+            "slug": "rust-api",
+            "date": "2024",
+            "title": "Rust API",
+            "description": { "overview": "REST API" },
+            "technologies": ["Rust"],
+            "features": [],
+            "githubUrl": "https://github.com/example/rust-api",
+            "embedding": [0.1, 0.2],
+            "images": null
+        }))
+        .unwrap();
+        source.projects.create(project).await.unwrap();
+
+        let (app_source, token) = app(&source);
+        let response = app_source
+            .oneshot(
+                Request::get("/export?format=yaml")
+                    .header("Authorization", format!("Bearer {}", token))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], "application/yaml");
+        let yaml = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let text = std::str::from_utf8(&yaml).unwrap();
+        assert!(text.contains("slug: rust-api"));
+        assert!(!text.contains("embedding"));
+
+        let target = Repositories::in_memory();
+        let (app_target, token) = app(&target);
+        let import = |uri: &str, content_type: &str, body: Vec<u8>| {
+            app_target.clone().oneshot(
+                Request::post(uri)
+                    .header("Authorization", format!("Bearer {}", token))
+                    .header("Content-Type", content_type)
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+        };
+
+        let response = import("/import?dry_run=true", "application/yaml", yaml.to_vec())
+            .await
+            .unwrap();
+        let report = body_json(response).await;
+        assert_eq!(report["created"], 1);
+        assert!(target.projects.list().await.unwrap().is_empty());
+
+        let response = import("/import", "application/yaml", yaml.to_vec())
+            .await
+            .unwrap();
+        assert_eq!(body_json(response).await["created"], 1);
+        let stored = target.projects.get_by_slug("rust-api").await.unwrap();
+        assert_eq!(stored.unwrap().embedding, Some(vec![0.5; 4]));
+
+        // One changed item, one invalid one and one certificate without a slug
+        let bundle = json!({
+            "version": 1,
+            "projects": [
+                {
+                    "slug": "rust-api",
+                    "date": "2024",
+                    "title": "Rust API v2",
+                    "description": { "overview": "REST API" },
+                    "technologies": ["Rust"],
+                    "features": [],
+                    "githubUrl": "https://github.com/example/rust-api"
+                },
+                {
+                    "slug": "broken",
+                    "date": "2024",
+                    "title": "",
+                    "technologies": [],
+                    "features": [],
+                    "githubUrl": "not a url"
+                }
+            ],
+            "certificates": [
+                {
+                    "name": "Rust",
+                    "issuer": "Coursera",
+                    "link": "https://coursera.org/verify/abc",
+                    "verified": true,
+                    "verified_at": { "$date": { "$numberLong": "1700000000000" } }
+                }
+            ]
+        });
+        let body = serde_json::to_vec(&bundle).unwrap();
+        let response = import("/import?dry_run=true", "application/json", body.clone())
+            .await
+            .unwrap();
+        let report = body_json(response).await;
+        assert_eq!(report["items"][0]["action"], "update");
+        assert_eq!(report["items"][0]["changes"][0]["path"], "title");
+        assert_eq!(report["items"][1]["action"], "invalid");
+        assert_eq!(report["items"][1]["errors"].as_array().unwrap().len(), 3);
+        assert_eq!(report["items"][2]["slug"], "rust");
+
+        let response = import("/import", "application/json", body).await.unwrap();
+        let report = body_json(response).await;
+        assert_eq!(
+            (report["updated"].clone(), report["created"].clone()),
+            (json!(1), json!(1))
+        );
+        let stored = target.projects.get_by_slug("rust-api").await.unwrap();
+        assert_eq!(stored.unwrap().title, "Rust API v2");
+        // A verification is only ever earned against the issuer page, never imported
+        let certificate = target.certificates.get_by_slug("rust").await.unwrap();
+        assert!(!certificate.unwrap().verified);
+
+        let response = import("/import", "application/json", br#"{"version": 9}"#.to_vec())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,54 @@
+use super::AdminState;
+use crate::{auth::UserInfo, error::ApiResult, repositories::CacheStats};
+use axum::{extract::State, Extension, Json};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+/// Read cache size and hit/miss counters since startup (Admin only)
+#[cfg_attr(feature = "swagger", utoipa::path(
+    get,
+    path = "/api/v1/admin/cache",
+    responses(
+        (status = 200, description = "Read cache statistics", body = CacheStats),
+        (status = 401, description = "Unauthorized - Missing or invalid token"),
+        (status = 403, description = "Forbidden - Not an admin user")
+    ),
+    security(
+        ("google_oauth" = ["openid", "email", "profile"])
+    ),
+    tag = "admin"
+))]
+pub async fn cache_stats(State(state): State<Arc<AdminState>>) -> ApiResult<Json<CacheStats>> {
+    Ok(Json(state.repositories.read_cache.stats()))
+}
+
+/// Drop every cached read, e.g. after editing the database by hand (Admin only)
+#[cfg_attr(feature = "swagger", utoipa::path(
+    delete,
+    path = "/api/v1/admin/cache",
+    responses(
+        (status = 200, description = "Number of flushed entries"),
+        (status = 401, description = "Unauthorized - Missing or invalid token"),
+        (status = 403, description = "Forbidden - Not an admin user")
+    ),
+    security(
+        ("google_oauth" = ["openid", "email", "profile"])
+    ),
+    tag = "admin"
+))]
+pub async fn flush_cache(
+    State(state): State<Arc<AdminState>>,
+    Extension(user): Extension<UserInfo>,
+) -> ApiResult<Json<Value>> {
+    let flushed = state.repositories.read_cache.flush();
+
+    tracing::info!(
+        "Admin {} flushed {} read cache entries",
+        user.email,
+        flushed
+    );
+    Ok(Json(json!({
+        "message": "Read cache flushed",
+        "flushed": flushed
+    })))
+}
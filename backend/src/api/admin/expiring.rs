@@ -0,0 +1,184 @@
+use super::AdminState;
+use crate::{
+    error::{ApiError, ApiResult},
+    models::{certificate::EXPIRING_SOON_DAYS, CertificateStatus},
+};
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Longest look-ahead accepted by the report
+const MAX_DAYS: i64 = 3650;
+
+/// Query parameters of the expiry report
+#[derive(Debug, Default, Deserialize)]
+pub struct ExpiringParams {
+    /// Look-ahead window in days (default 30)
+    pub days: Option<i64>,
+    /// Include certificates that have already expired
+    #[serde(default)]
+    pub include_expired: bool,
+}
+
+/// A certificate expiring within the requested window
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "swagger", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ExpiringCertificate {
+    pub slug: String,
+    pub name: String,
+    pub issuer: String,
+    pub credential_id: Option<String>,
+    pub expires_at: NaiveDate,
+    /// Negative once the certificate has expired
+    pub days_left: i64,
+    pub status: CertificateStatus,
+}
+
+/// Certificates expiring within the next `days` days, soonest first (Admin only)
+#[cfg_attr(feature = "swagger", utoipa::path(
+    get,
+    path = "/api/v1/admin/certificates/expiring",
+    params(
+        ("days" = Option<i64>, Query, description = "Look-ahead window in days (default 30)"),
+        ("include_expired" = Option<bool>, Query, description = "Include already expired certificates")
+    ),
+    responses(
+        (status = 200, description = "Expiring certificates", body = Vec<ExpiringCertificate>),
+        (status = 400, description = "Invalid window"),
+        (status = 401, description = "Unauthorized - Missing or invalid token"),
+        (status = 403, description = "Forbidden - Not an admin user")
+    ),
+    security(
+        ("google_oauth" = ["openid", "email", "profile"])
+    ),
+    tag = "admin"
+))]
+pub async fn expiring_certificates(
+    State(state): State<Arc<AdminState>>,
+    Query(params): Query<ExpiringParams>,
+) -> ApiResult<Json<Vec<ExpiringCertificate>>> {
+    let days = params.days.unwrap_or(EXPIRING_SOON_DAYS);
+    if !(0..=MAX_DAYS).contains(&days) {
+        return Err(ApiError::BadRequest(format!(
+            "days must be between 0 and {}",
+            MAX_DAYS
+        )));
+    }
+
+    let today = Utc::now().date_naive();
+    let mut expiring: Vec<ExpiringCertificate> = state
+        .repositories
+        .certificates
+        .list()
+        .await?
+        .into_iter()
+        .filter_map(|certificate| {
+            let expires_at = certificate.expires_at?;
+            let days_left = (expires_at - today).num_days();
+            if days_left > days || (days_left < 0 && !params.include_expired) {
+                return None;
+            }
+            Some(ExpiringCertificate {
+                status: CertificateStatus::on(Some(expires_at), today),
+                slug: certificate.slug,
+                name: certificate.name,
+                issuer: certificate.issuer,
+                credential_id: certificate.credential_id,
+                expires_at,
+                days_left,
+            })
+        })
+        .collect();
+    expiring.sort_by(|a, b| a.expires_at.cmp(&b.expires_at).then(a.slug.cmp(&b.slug)));
+
+    Ok(Json(expiring))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        api::test_support::{admin_app, body_json, test_auth},
+        repositories::Repositories,
+    };
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use chrono::{Days, Utc};
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_report_lists_certificates_expiring_within_window() {
+        let (_, token) = test_auth();
+        let repositories = Repositories::in_memory();
+        let today = Utc::now().date_naive();
+        for (slug, expires_at) in [
+            ("lapsed", Some(today - Days::new(3))),
+            ("soon", Some(today + Days::new(10))),
+            ("later", Some(today + Days::new(90))),
+            ("forever", None),
+        ] {
+            let certificate = serde_json::from_value(json!({
+                "name": slug,
+                "issuer": "Issuer",
+                "link": "https://example.com/verify",
+                "slug": slug,
+                "expires_at": expires_at,
+                "credential_id": format!("ID-{}", slug)
+            }))
+            .unwrap();
+            repositories.certificates.create(certificate).await.unwrap();
+        }
+        let app = admin_app(&repositories);
+        let request = |uri: &str| {
+            Request::builder()
+                .uri(uri)
+                .header("Authorization", format!("Bearer {}", token))
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(request("/certificates/expiring"))
+            .await
+            .unwrap();
+        let report = body_json(response).await;
+        assert_eq!(report.as_array().unwrap().len(), 1);
+        assert_eq!(report[0]["slug"], "soon");
+        assert_eq!(report[0]["credentialId"], "ID-soon");
+        assert_eq!(report[0]["daysLeft"], 10);
+        assert_eq!(report[0]["status"], "expiring-soon");
+
+        let response = app
+            .clone()
+            .oneshot(request(
+                "/certificates/expiring?days=120&include_expired=true",
+            ))
+            .await
+            .unwrap();
+        let report = body_json(response).await;
+        let slugs: Vec<&str> = report
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|item| item["slug"].as_str().unwrap())
+            .collect();
+        assert_eq!(slugs, ["lapsed", "soon", "later"]);
+        assert_eq!(report[0]["status"], "expired");
+        assert_eq!(report[0]["daysLeft"], -3);
+        assert_eq!(report[2]["status"], "active");
+
+        let response = app
+            .oneshot(request("/certificates/expiring?days=-1"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,180 @@
+use super::AdminState;
+use crate::{
+    auth::UserInfo,
+    error::{ApiError, ApiResult},
+    models::link::LinkCheckResponse,
+    services::link_health::{LinkCheckRun, LinkCheckStatus},
+};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Query parameters of the link report
+#[derive(Debug, Default, Deserialize)]
+pub struct LinkReportParams {
+    /// Include working links too, not only broken and unreachable ones
+    #[serde(default)]
+    pub all: bool,
+}
+
+/// Checked links with the state of the checker
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "swagger", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct LinkReport {
+    pub running: bool,
+    pub last_run: Option<LinkCheckRun>,
+    pub links: Vec<LinkCheckResponse>,
+}
+
+/// Broken and unreachable project and certificate links from the latest checks (Admin only)
+#[cfg_attr(feature = "swagger", utoipa::path(
+    get,
+    path = "/api/v1/admin/links",
+    params(
+        ("all" = Option<bool>, Query, description = "Include working links")
+    ),
+    responses(
+        (status = 200, description = "Link report", body = LinkReport),
+        (status = 401, description = "Unauthorized - Missing or invalid token"),
+        (status = 403, description = "Forbidden - Not an admin user")
+    ),
+    security(
+        ("google_oauth" = ["openid", "email", "profile"])
+    ),
+    tag = "admin"
+))]
+pub async fn link_report(
+    State(state): State<Arc<AdminState>>,
+    Query(params): Query<LinkReportParams>,
+) -> ApiResult<Json<LinkReport>> {
+    let checks = if params.all {
+        state.repositories.links.list().await?
+    } else {
+        state.repositories.links.list_failing().await?
+    };
+    let LinkCheckStatus { running, last_run } = state.links.status();
+
+    Ok(Json(LinkReport {
+        running,
+        last_run,
+        links: checks.into_iter().map(LinkCheckResponse::from).collect(),
+    }))
+}
+
+/// Check every link now in the background instead of waiting for the schedule (Admin only)
+#[cfg_attr(feature = "swagger", utoipa::path(
+    post,
+    path = "/api/v1/admin/links/check",
+    responses(
+        (status = 202, description = "Link check started", body = LinkCheckStatus),
+        (status = 401, description = "Unauthorized - Missing or invalid token"),
+        (status = 403, description = "Forbidden - Not an admin user"),
+        (status = 409, description = "A link check is already running")
+    ),
+    security(
+        ("google_oauth" = ["openid", "email", "profile"])
+    ),
+    tag = "admin"
+))]
+pub async fn start_link_check(
+    State(state): State<Arc<AdminState>>,
+    Extension(user): Extension<UserInfo>,
+) -> ApiResult<(StatusCode, Json<LinkCheckStatus>)> {
+    if !state.links.try_start() {
+        return Err(ApiError::Conflict(
+            "A link check is already running".to_string(),
+        ));
+    }
+
+    tracing::info!("Admin {} started a link check", user.email);
+    let links = state.links.clone();
+    tokio::spawn(async move { links.run_started().await });
+
+    Ok((StatusCode::ACCEPTED, Json(state.links.status())))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        api::test_support::{admin_app_with, body_json, test_auth, test_links, AdminServices},
+        repositories::Repositories,
+    };
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use serde_json::json;
+    use std::time::Duration;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_check_runs_in_background_and_reports_broken_links() {
+        let (_, token) = test_auth();
+        let repositories = Repositories::in_memory();
+        let certificate = serde_json::from_value(json!({
+            "name": "Rust",
+            "issuer": "Coursera",
+            "link": "coursera.org/verify/abc",
+            "slug": "rust"
+        }))
+        .unwrap();
+        repositories.certificates.create(certificate).await.unwrap();
+        let links = test_links(&repositories);
+        let app = admin_app_with(
+            &repositories,
+            AdminServices {
+                links: links.clone(),
+                ..AdminServices::new(&repositories)
+            },
+        );
+        let request = |method: &str, uri: &str| {
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("Authorization", format!("Bearer {}", token))
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(request("GET", "/links")).await.unwrap();
+        assert_eq!(
+            body_json(response).await,
+            json!({ "running": false, "lastRun": null, "links": [] })
+        );
+
+        // A run claimed elsewhere, e.g. by the schedule, blocks a second one
+        assert!(links.try_start());
+        let response = app
+            .clone()
+            .oneshot(request("POST", "/links/check"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        links.run_started().await;
+
+        let response = app
+            .clone()
+            .oneshot(request("POST", "/links/check"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        while links.status().running {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let response = app.clone().oneshot(request("GET", "/links")).await.unwrap();
+        let report = body_json(response).await;
+        assert_eq!(report["lastRun"]["checked"], 1);
+        assert_eq!(report["lastRun"]["failing"], 1);
+        assert_eq!(report["links"][0]["kind"], "certificate");
+        assert_eq!(report["links"][0]["field"], "link");
+        assert_eq!(report["links"][0]["state"], "broken");
+        assert_eq!(report["links"][0]["error"], "Not an http(s) URL");
+        assert!(report["links"][0]["failingSince"].is_string());
+    }
+}
@@ -0,0 +1,245 @@
+use super::{
+    bundle::{import_project, ImportAction, ImportItem, ImportParams, ImportReport},
+    AdminState,
+};
+use crate::{
+    auth::UserInfo,
+    error::{ApiError, ApiResult},
+    models::ContentKind,
+    services::markdown::parse_project,
+};
+use axum::{
+    body::Bytes,
+    extract::{Query, State},
+    http::{header, HeaderMap},
+    Extension, Json,
+};
+use serde_json::Value;
+use std::{
+    collections::HashSet,
+    io::{Cursor, Read},
+    sync::Arc,
+};
+
+/// Largest accepted upload, single file or zip
+pub const MAX_UPLOAD_BYTES: usize = 10 * 1024 * 1024;
+/// Most Markdown files read from one zip
+const MAX_ARCHIVE_FILES: usize = 200;
+/// Largest uncompressed Markdown file read from a zip
+const MAX_FILE_BYTES: u64 = 1024 * 1024;
+
+/// A Markdown file of the upload, or why it could not be read
+struct SourceFile {
+    name: Option<String>,
+    text: Result<String, String>,
+}
+
+/// Markdown files of a zip archive, sorted by path
+/// Folders, hidden files and macOS metadata are skipped
+fn read_archive(body: &[u8]) -> ApiResult<Vec<SourceFile>> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(body))
+        .map_err(|e| ApiError::BadRequest(format!("Invalid zip archive: {}", e)))?;
+
+    let mut files = Vec::new();
+    for index in 0..archive.len() {
+        let entry = archive
+            .by_index(index)
+            .map_err(|e| ApiError::BadRequest(format!("Invalid zip archive: {}", e)))?;
+        let name = entry.name().to_string();
+        let lowercase = name.to_lowercase();
+        let hidden = name
+            .split('/')
+            .any(|part| part.starts_with('.') || part == "__MACOSX");
+        if entry.is_dir()
+            || hidden
+            || !(lowercase.ends_with(".md") || lowercase.ends_with(".markdown"))
+        {
+            continue;
+        }
+        if files.len() == MAX_ARCHIVE_FILES {
+            return Err(ApiError::BadRequest(format!(
+                "Archive holds more than {} Markdown files",
+                MAX_ARCHIVE_FILES
+            )));
+        }
+
+        let text = if entry.size() > MAX_FILE_BYTES {
+            Err(format!("File is larger than {} bytes", MAX_FILE_BYTES))
+        } else {
+            let mut text = String::new();
+            entry
+                .take(MAX_FILE_BYTES)
+                .read_to_string(&mut text)
+                .map(|_| text)
+                .map_err(|e| format!("Cannot read file as UTF-8 text: {}", e))
+        };
+        files.push(SourceFile {
+            name: Some(name),
+            text,
+        });
+    }
+
+    files.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(files)
+}
+
+/// Import projects from Markdown case studies with YAML front matter (Admin only)
+/// Accepts one `text/markdown` file or an `application/zip` of many; every file is
+/// validated and upserted by slug on its own, like items of a bundle import
+#[cfg_attr(feature = "swagger", utoipa::path(
+    post,
+    path = "/api/v1/admin/import/markdown",
+    request_body(
+        description = "Markdown file, or a zip archive of Markdown files",
+        content(
+            (String = "text/markdown"),
+            (Vec<u8> = "application/zip")
+        )
+    ),
+    params(
+        ("dry_run" = Option<bool>, Query, description = "Only report what would change"),
+        ("on_conflict" = Option<String>, Query, description = "update (default) or skip projects whose slug exists")
+    ),
+    responses(
+        (status = 200, description = "Per-file import report", body = ImportReport),
+        (status = 400, description = "Unreadable zip archive or too many files"),
+        (status = 413, description = "Upload larger than 10 MiB"),
+        (status = 415, description = "Body is neither Markdown nor a zip archive"),
+        (status = 401, description = "Unauthorized - Missing or invalid token"),
+        (status = 403, description = "Forbidden - Not an admin user")
+    ),
+    security(
+        ("google_oauth" = ["openid", "email", "profile"])
+    ),
+    tag = "admin"
+))]
+pub async fn import_markdown(
+    State(state): State<Arc<AdminState>>,
+    Extension(user): Extension<UserInfo>,
+    Query(params): Query<ImportParams>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ApiResult<Json<ImportReport>> {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    let media_type = content_type.split(';').next().unwrap_or_default().trim();
+
+    let files = match media_type.to_ascii_lowercase().as_str() {
+        "text/markdown" | "text/x-markdown" | "text/plain" => vec![SourceFile {
+            name: None,
+            text: String::from_utf8(body.to_vec())
+                .map_err(|e| format!("Cannot read file as UTF-8 text: {}", e)),
+        }],
+        "application/zip" | "application/x-zip-compressed" => read_archive(&body)?,
+        other => {
+            return Err(ApiError::UnsupportedMediaType(format!(
+                "Expected text/markdown or application/zip, got '{}'",
+                other
+            )))
+        }
+    };
+
+    let mut report = ImportReport {
+        dry_run: params.dry_run,
+        ..ImportReport::default()
+    };
+    let mut seen = HashSet::new();
+    for (index, file) in files.into_iter().enumerate() {
+        let mut item = ImportItem::new(ContentKind::Project, index, &Value::Null);
+        item.source = file.name;
+
+        let parsed = file
+            .text
+            .and_then(|text| parse_project(&text).map_err(|e| e.to_string()));
+        let item = match parsed {
+            Ok(parsed) => {
+                item.slug = parsed.project.slug.clone();
+                item.warnings = parsed.warnings;
+                match serde_json::to_value(&parsed.project) {
+                    Ok(value) => {
+                        import_project(&state, &params, &user, &mut seen, item, value).await
+                    }
+                    Err(e) => item.rejected(ImportAction::Invalid, e),
+                }
+            }
+            Err(e) => item.rejected(ImportAction::Invalid, e),
+        };
+        report.push(item);
+    }
+
+    report.finish(&user, "Markdown projects");
+    Ok(Json(report))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        api::test_support::{admin_app, body_json, test_auth},
+        repositories::Repositories,
+    };
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use std::io::{Cursor, Write};
+    use tower::ServiceExt;
+    use zip::{write::SimpleFileOptions, ZipWriter};
+
+    fn archive(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, text) in files {
+            writer
+                .start_file(*name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(text.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[tokio::test]
+    async fn test_zip_of_case_studies_is_imported_per_file() {
+        let (_, token) = test_auth();
+        let repositories = Repositories::in_memory();
+        let app = admin_app(&repositories);
+
+        let body = archive(&[
+            (
+                "projects/rust-api.md",
+                "---\ntechnologies: [Rust]\ngithubUrl: https://github.com/example/rust-api\n---\n# Rust API\n\n## Problem\nSlow.\n\n## Impact\nFast.\n",
+            ),
+            ("projects/draft.md", "# Draft\n\nNo links yet.\n"),
+            ("__MACOSX/projects/._rust-api.md", "binary"),
+            ("README.txt", "not markdown"),
+        ]);
+        let response = app
+            .oneshot(
+                Request::post("/import/markdown")
+                    .header("Authorization", format!("Bearer {}", token))
+                    .header("Content-Type", "application/zip")
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let report = body_json(response).await;
+        assert_eq!(report["items"].as_array().unwrap().len(), 2);
+        assert_eq!(report["items"][0]["source"], "projects/draft.md");
+        assert_eq!(report["items"][0]["action"], "invalid");
+        assert_eq!(report["items"][1]["slug"], "rust-api");
+        assert_eq!(report["items"][1]["action"], "create");
+
+        let project = repositories
+            .projects
+            .get_by_slug("rust-api")
+            .await
+            .unwrap()
+            .unwrap();
+        let description = project.description.unwrap();
+        assert_eq!(description.problem.as_deref(), Some("Slow."));
+        assert_eq!(description.impact.as_deref(), Some("Fast."));
+    }
+}
@@ -0,0 +1,525 @@
+use super::AdminState;
+use crate::{
+    auth::UserInfo,
+    error::{ApiError, ApiResult},
+    models::media::MediaAssetResponse,
+    services::media::UploadedImage,
+};
+use axum::{
+    extract::{multipart::MultipartError, Multipart, Query, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Most images accepted in one upload request
+pub const MAX_FILES_PER_UPLOAD: usize = 10;
+/// Unreferenced uploads younger than this are not orphans yet, they may be about to be attached
+const DEFAULT_ORPHAN_MIN_AGE_HOURS: i64 = 24;
+
+/// Query parameters for listing and purging orphans
+#[derive(Debug, Default, Deserialize)]
+pub struct OrphanParams {
+    /// Only unreferenced assets uploaded at least this many hours ago (default 24)
+    pub min_age_hours: Option<i64>,
+    /// Only report what would be purged
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Asset that could not be purged
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "swagger", derive(utoipa::ToSchema))]
+pub struct PurgeFailure {
+    pub key: String,
+    pub error: String,
+}
+
+/// Outcome of purging orphaned media
+#[derive(Debug, Default, Serialize)]
+#[cfg_attr(feature = "swagger", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct MediaPurgeReport {
+    pub dry_run: bool,
+    /// Keys of purged assets, or of assets a dry run would purge
+    pub purged: Vec<String>,
+    pub freed_bytes: i64,
+    /// Assets that gained a reference meanwhile, live in another backend or failed to delete
+    pub failed: Vec<PurgeFailure>,
+}
+
+fn multipart_error(e: MultipartError) -> ApiError {
+    if e.status() == StatusCode::PAYLOAD_TOO_LARGE {
+        ApiError::PayloadTooLarge(e.body_text())
+    } else {
+        ApiError::BadRequest(format!("Invalid multipart body: {}", e.body_text()))
+    }
+}
+
+/// Name the offending file in client errors of multi-file uploads
+fn for_file(file_name: &str, e: ApiError) -> ApiError {
+    match e {
+        ApiError::BadRequest(msg) => ApiError::BadRequest(format!("{}: {}", file_name, msg)),
+        ApiError::PayloadTooLarge(msg) => {
+            ApiError::PayloadTooLarge(format!("{}: {}", file_name, msg))
+        }
+        ApiError::UnsupportedMediaType(msg) => {
+            ApiError::UnsupportedMediaType(format!("{}: {}", file_name, msg))
+        }
+        ApiError::ValidationError(msg) => {
+            ApiError::ValidationError(format!("{}: {}", file_name, msg))
+        }
+        other => other,
+    }
+}
+
+/// Upload images as `multipart/form-data` file fields (Admin only)
+/// Every file is checked and processed before any is stored, so one bad file
+/// rejects the whole request; the returned URLs can be added to a project's
+/// `images` or set as a certificate's `image_url`
+#[cfg_attr(feature = "swagger", utoipa::path(
+    post,
+    path = "/api/v1/admin/media",
+    request_body(
+        description = "One or more JPEG, PNG or WebP files in multipart file fields",
+        content_type = "multipart/form-data"
+    ),
+    responses(
+        (status = 201, description = "Stored images with their thumbnails", body = Vec<UploadedImage>),
+        (status = 400, description = "No file or malformed multipart body"),
+        (status = 413, description = "Image larger than the upload limit"),
+        (status = 415, description = "Not a JPEG, PNG or WebP image, or content not matching its declared type"),
+        (status = 422, description = "Image cannot be decoded or is too large in pixels"),
+        (status = 503, description = "Media storage backend failed"),
+        (status = 401, description = "Unauthorized - Missing or invalid token"),
+        (status = 403, description = "Forbidden - Not an admin user")
+    ),
+    security(
+        ("google_oauth" = ["openid", "email", "profile"])
+    ),
+    tag = "admin"
+))]
+pub async fn upload_media(
+    State(state): State<Arc<AdminState>>,
+    Extension(user): Extension<UserInfo>,
+    mut multipart: Multipart,
+) -> ApiResult<(StatusCode, Json<Vec<UploadedImage>>)> {
+    let mut prepared = Vec::new();
+    while let Some(field) = multipart.next_field().await.map_err(multipart_error)? {
+        // Text fields are ignored, files are recognised by their file name
+        let Some(file_name) = field.file_name().map(str::to_string) else {
+            continue;
+        };
+        if prepared.len() == MAX_FILES_PER_UPLOAD {
+            return Err(ApiError::BadRequest(format!(
+                "At most {} files can be uploaded at once",
+                MAX_FILES_PER_UPLOAD
+            )));
+        }
+
+        let content_type = field.content_type().map(str::to_string);
+        let bytes = field.bytes().await.map_err(multipart_error)?;
+        let image = state
+            .media
+            .prepare(Some(file_name.clone()), content_type, bytes)
+            .await
+            .map_err(|e| for_file(&file_name, e))?;
+        prepared.push(image);
+    }
+    if prepared.is_empty() {
+        return Err(ApiError::BadRequest(
+            "No file in the upload, send images as multipart file fields".to_string(),
+        ));
+    }
+
+    let mut uploaded = Vec::new();
+    for image in prepared {
+        let image = state.media.store(image).await?;
+        state
+            .repositories
+            .media
+            .create(image.to_asset(&user.email))
+            .await?;
+        uploaded.push(image);
+    }
+
+    tracing::info!(
+        "Admin {} uploaded {} image(s): {}",
+        user.email,
+        uploaded.len(),
+        uploaded
+            .iter()
+            .map(|image| image.key.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    Ok((StatusCode::CREATED, Json(uploaded)))
+}
+
+/// Media library with size, dimensions, uploader and referencing documents (Admin only)
+#[cfg_attr(feature = "swagger", utoipa::path(
+    get,
+    path = "/api/v1/admin/media",
+    responses(
+        (status = 200, description = "Assets, most recently uploaded first", body = Vec<MediaAssetResponse>),
+        (status = 401, description = "Unauthorized - Missing or invalid token"),
+        (status = 403, description = "Forbidden - Not an admin user")
+    ),
+    security(
+        ("google_oauth" = ["openid", "email", "profile"])
+    ),
+    tag = "admin"
+))]
+pub async fn list_media(
+    State(state): State<Arc<AdminState>>,
+) -> ApiResult<Json<Vec<MediaAssetResponse>>> {
+    let assets = state.repositories.media.list().await?;
+    Ok(Json(assets.into_iter().map(Into::into).collect()))
+}
+
+fn orphan_cutoff(params: &OrphanParams) -> ApiResult<chrono::DateTime<Utc>> {
+    let hours = params.min_age_hours.unwrap_or(DEFAULT_ORPHAN_MIN_AGE_HOURS);
+    if hours < 0 {
+        return Err(ApiError::BadRequest(
+            "min_age_hours must not be negative".to_string(),
+        ));
+    }
+    Ok(Utc::now() - Duration::hours(hours))
+}
+
+/// Assets no project or certificate references, oldest first (Admin only)
+/// Trashed content still counts as a reference until it is purged
+#[cfg_attr(feature = "swagger", utoipa::path(
+    get,
+    path = "/api/v1/admin/media/orphans",
+    params(
+        ("min_age_hours" = Option<i64>, Query, description = "Minimum age of the upload in hours (default 24)")
+    ),
+    responses(
+        (status = 200, description = "Unreferenced assets", body = Vec<MediaAssetResponse>),
+        (status = 400, description = "Negative min_age_hours"),
+        (status = 401, description = "Unauthorized - Missing or invalid token"),
+        (status = 403, description = "Forbidden - Not an admin user")
+    ),
+    security(
+        ("google_oauth" = ["openid", "email", "profile"])
+    ),
+    tag = "admin"
+))]
+pub async fn list_orphans(
+    State(state): State<Arc<AdminState>>,
+    Query(params): Query<OrphanParams>,
+) -> ApiResult<Json<Vec<MediaAssetResponse>>> {
+    let cutoff = orphan_cutoff(&params)?;
+    let orphans = state.repositories.media.list_orphans(cutoff).await?;
+    Ok(Json(orphans.into_iter().map(Into::into).collect()))
+}
+
+/// Delete orphaned assets with their files (Admin only)
+/// The record goes first and only while still unreferenced, so an asset attached
+/// during the purge keeps its files
+#[cfg_attr(feature = "swagger", utoipa::path(
+    delete,
+    path = "/api/v1/admin/media/orphans",
+    params(
+        ("min_age_hours" = Option<i64>, Query, description = "Minimum age of the upload in hours (default 24)"),
+        ("dry_run" = Option<bool>, Query, description = "Only report what would be purged")
+    ),
+    responses(
+        (status = 200, description = "Purged and failed assets", body = MediaPurgeReport),
+        (status = 400, description = "Negative min_age_hours"),
+        (status = 401, description = "Unauthorized - Missing or invalid token"),
+        (status = 403, description = "Forbidden - Not an admin user")
+    ),
+    security(
+        ("google_oauth" = ["openid", "email", "profile"])
+    ),
+    tag = "admin"
+))]
+pub async fn purge_orphans(
+    State(state): State<Arc<AdminState>>,
+    Extension(user): Extension<UserInfo>,
+    Query(params): Query<OrphanParams>,
+) -> ApiResult<Json<MediaPurgeReport>> {
+    let cutoff = orphan_cutoff(&params)?;
+    let orphans = state.repositories.media.list_orphans(cutoff).await?;
+
+    let mut report = MediaPurgeReport {
+        dry_run: params.dry_run,
+        ..MediaPurgeReport::default()
+    };
+    for asset in orphans {
+        let fail = |error: String| PurgeFailure {
+            key: asset.key.clone(),
+            error,
+        };
+        if asset.backend != state.media.backend() {
+            report.failed.push(fail(format!(
+                "Stored in the '{}' backend, the configured backend is '{}'",
+                asset.backend,
+                state.media.backend()
+            )));
+            continue;
+        }
+        if params.dry_run {
+            report.freed_bytes += asset.bytes;
+            report.purged.push(asset.key);
+            continue;
+        }
+
+        let Some(id) = asset.id else { continue };
+        if !state.repositories.media.delete_orphan(id).await? {
+            report
+                .failed
+                .push(fail("Referenced again since it was listed".to_string()));
+            continue;
+        }
+        match state.media.delete(&asset).await {
+            Ok(()) => {
+                report.freed_bytes += asset.bytes;
+                report.purged.push(asset.key);
+            }
+            Err(e) => report.failed.push(fail(e.to_string())),
+        }
+    }
+
+    if !params.dry_run {
+        tracing::info!(
+            "Admin {} purged {} orphaned media assets ({} bytes), {} failed",
+            user.email,
+            report.purged.len(),
+            report.freed_bytes,
+            report.failed.len()
+        );
+    }
+    Ok(Json(report))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        api::test_support::{admin_app_with, body_json, test_auth, test_media, AdminServices},
+        repositories::Repositories,
+    };
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
+    use std::io::Cursor;
+    use tower::ServiceExt;
+
+    const BOUNDARY: &str = "media-test-boundary";
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let image = RgbaImage::from_pixel(width, height, Rgba([20, 120, 200, 255]));
+        let mut bytes = Vec::new();
+        DynamicImage::ImageRgba8(image)
+            .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+            .unwrap();
+        bytes
+    }
+
+    /// `(field name, file name, content type, content)` parts of a multipart body
+    fn multipart(parts: &[(&str, Option<&str>, &str, &[u8])]) -> Vec<u8> {
+        let mut body = Vec::new();
+        for (name, file_name, content_type, content) in parts {
+            body.extend_from_slice(format!("--{}\r\n", BOUNDARY).as_bytes());
+            let disposition = match file_name {
+                Some(file_name) => format!(
+                    "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n",
+                    name, file_name
+                ),
+                None => format!("Content-Disposition: form-data; name=\"{}\"\r\n", name),
+            };
+            body.extend_from_slice(disposition.as_bytes());
+            body.extend_from_slice(format!("Content-Type: {}\r\n\r\n", content_type).as_bytes());
+            body.extend_from_slice(content);
+            body.extend_from_slice(b"\r\n");
+        }
+        body.extend_from_slice(format!("--{}--\r\n", BOUNDARY).as_bytes());
+        body
+    }
+
+    fn upload(token: &str, body: Vec<u8>) -> Request<Body> {
+        Request::post("/media")
+            .header("Authorization", format!("Bearer {}", token))
+            .header(
+                "Content-Type",
+                format!("multipart/form-data; boundary={}", BOUNDARY),
+            )
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_upload_validates_every_file_before_storing() {
+        let (_, token) = test_auth();
+        let (media, store) = test_media();
+        let repositories = Repositories::in_memory();
+        let app = admin_app_with(
+            &repositories,
+            AdminServices {
+                media,
+                ..AdminServices::new(&repositories)
+            },
+        );
+        let image = png(1000, 500);
+
+        let response = app
+            .clone()
+            .oneshot(upload(
+                &token,
+                multipart(&[
+                    ("file", Some("diagram.png"), "image/png", &image),
+                    ("file", Some("notes.png"), "image/png", b"not an image"),
+                ]),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        let error = body_json(response).await;
+        assert!(error["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("notes.png"));
+        assert!(store.files.lock().unwrap().is_empty());
+
+        let response = app
+            .oneshot(upload(
+                &token,
+                multipart(&[
+                    ("caption", None, "text/plain", b"ignored"),
+                    (
+                        "file",
+                        Some("Architecture Diagram.png"),
+                        "image/png",
+                        &image,
+                    ),
+                ]),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let uploaded = body_json(response).await;
+        let uploaded = &uploaded[0];
+        let key = uploaded["key"].as_str().unwrap();
+        assert!(key.contains("/architecture-diagram-") && key.ends_with(".png"));
+        assert_eq!(
+            uploaded["url"],
+            format!("https://media.example.com/{}", key)
+        );
+        assert_eq!(uploaded["backend"], "stub");
+        assert_eq!(uploaded["contentType"], "image/png");
+        assert_eq!(
+            (uploaded["width"].as_u64(), uploaded["height"].as_u64()),
+            (Some(1000), Some(500))
+        );
+        let widths: Vec<_> = uploaded["thumbnails"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|thumbnail| thumbnail["width"].as_u64().unwrap())
+            .collect();
+        assert_eq!(widths, vec![320, 960]);
+
+        let stored: Vec<_> = store
+            .files
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(key, _)| key.clone())
+            .collect();
+        assert_eq!(stored.len(), 3);
+        assert!(stored[1].ends_with("-w320.png"));
+    }
+
+    #[tokio::test]
+    async fn test_orphans_are_listed_and_purged_with_their_files() {
+        let (_, token) = test_auth();
+        let (media, store) = test_media();
+        let repositories = Repositories::in_memory();
+        let app = admin_app_with(
+            &repositories,
+            AdminServices {
+                media,
+                ..AdminServices::new(&repositories)
+            },
+        );
+        let admin = |request: Request<Body>| {
+            let app = app.clone();
+            async move { app.oneshot(request).await.unwrap() }
+        };
+        let get = |uri: &str| {
+            Request::get(uri)
+                .header("Authorization", format!("Bearer {}", token))
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let body = multipart(&[
+            ("file", Some("used.png"), "image/png", &png(400, 300)),
+            ("file", Some("unused.png"), "image/png", &png(200, 100)),
+        ]);
+        let uploaded = body_json(admin(upload(&token, body)).await).await;
+        let used_url = uploaded[0]["thumbnails"][0]["url"].as_str().unwrap();
+        let unused_key = uploaded[1]["key"].as_str().unwrap();
+        assert_eq!(store.files.lock().unwrap().len(), 3);
+
+        let certificate = serde_json::from_value(serde_json::json!({
+            "name": "Rust",
+            "issuer": "Coursera",
+            "link": "https://coursera.org/verify/abc",
+            "slug": "rust",
+            "image_url": used_url
+        }))
+        .unwrap();
+        repositories.certificates.create(certificate).await.unwrap();
+
+        let library = body_json(admin(get("/media")).await).await;
+        assert_eq!(library[0]["uploadedBy"], "admin@example.com");
+        assert_eq!(library[1]["referenceCount"], 1);
+        assert_eq!(library[1]["references"][0]["kind"], "certificate");
+
+        // Fresh uploads are not orphans until they are a day old
+        let orphans = body_json(admin(get("/media/orphans")).await).await;
+        assert_eq!(orphans, serde_json::json!([]));
+        let orphans = body_json(admin(get("/media/orphans?min_age_hours=0")).await).await;
+        assert_eq!(orphans.as_array().unwrap().len(), 1);
+        assert_eq!(orphans[0]["key"], unused_key);
+
+        let purge = |query: &str| {
+            Request::delete(format!("/media/orphans?min_age_hours=0{}", query))
+                .header("Authorization", format!("Bearer {}", token))
+                .body(Body::empty())
+                .unwrap()
+        };
+        let report = body_json(admin(purge("&dry_run=true")).await).await;
+        assert_eq!(report["purged"], serde_json::json!([unused_key]));
+        assert_eq!(store.files.lock().unwrap().len(), 3);
+
+        let report = body_json(admin(purge("")).await).await;
+        assert_eq!(report["purged"], serde_json::json!([unused_key]));
+        assert_eq!(report["failed"], serde_json::json!([]));
+        let remaining: Vec<String> = store
+            .files
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(key, _)| key.clone())
+            .collect();
+        assert_eq!(remaining.len(), 2);
+        assert!(remaining.iter().all(|key| key != unused_key));
+        assert_eq!(
+            body_json(admin(get("/media")).await)
+                .await
+                .as_array()
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,120 @@
+pub mod bundle;
+pub mod cache;
+pub mod expiring;
+pub mod links;
+pub mod markdown;
+pub mod media;
+pub mod ordering;
+pub mod publication;
+pub mod reindex;
+pub mod revisions;
+pub mod trash;
+pub mod verification;
+
+use crate::{
+    api::chat::Embedder,
+    auth::AuthConfig,
+    repositories::Repositories,
+    services::{link_health::LinkChecker, media::MediaService, verification::CertificateVerifier},
+};
+use axum::{
+    extract::DefaultBodyLimit,
+    middleware,
+    routing::{get, post, put},
+    Router,
+};
+use reindex::ReindexTracker;
+use std::sync::Arc;
+
+/// Shared state for admin maintenance endpoints
+pub struct AdminState {
+    pub repositories: Repositories,
+    pub embedder: Arc<dyn Embedder>,
+    pub reindex: Arc<ReindexTracker>,
+    pub media: Arc<MediaService>,
+    pub links: Arc<LinkChecker>,
+    pub verifier: Arc<CertificateVerifier>,
+}
+
+/// Build admin router - every route requires admin authentication
+pub fn router(
+    repositories: Repositories,
+    embedder: Arc<dyn Embedder>,
+    auth_config: Arc<AuthConfig>,
+    media: Arc<MediaService>,
+    links: Arc<LinkChecker>,
+    verifier: Arc<CertificateVerifier>,
+) -> Router {
+    // Room for the most files of one upload plus multipart framing
+    let media_body_limit = media
+        .max_bytes()
+        .saturating_mul(media::MAX_FILES_PER_UPLOAD)
+        .saturating_add(64 * 1024);
+    let state = Arc::new(AdminState {
+        repositories,
+        embedder,
+        reindex: Arc::new(ReindexTracker::default()),
+        media,
+        links,
+        verifier,
+    });
+
+    Router::new()
+        .route("/cache", get(cache::cache_stats).delete(cache::flush_cache))
+        .route(
+            "/certificates/expiring",
+            get(expiring::expiring_certificates),
+        )
+        .route(
+            "/certificates/{slug}/verify",
+            post(verification::verify_certificate),
+        )
+        .route("/export", get(bundle::export_content))
+        .route("/import", post(bundle::import_content))
+        .route(
+            "/import/markdown",
+            post(markdown::import_markdown)
+                .layer(DefaultBodyLimit::max(markdown::MAX_UPLOAD_BYTES)),
+        )
+        .route("/links", get(links::link_report))
+        .route("/links/check", post(links::start_link_check))
+        .route(
+            "/media",
+            post(media::upload_media)
+                .layer(DefaultBodyLimit::max(media_body_limit))
+                .get(media::list_media),
+        )
+        .route(
+            "/media/orphans",
+            get(media::list_orphans).delete(media::purge_orphans),
+        )
+        .route(
+            "/projects/order",
+            get(ordering::project_order).put(ordering::reorder_projects),
+        )
+        .route("/publication", get(publication::list_unpublished))
+        .route(
+            "/publication/{kind}/{slug}",
+            put(publication::set_publication),
+        )
+        .route(
+            "/reindex",
+            post(reindex::start_reindex).get(reindex::reindex_status),
+        )
+        .route("/revisions/{kind}/{slug}", get(revisions::list_revisions))
+        .route(
+            "/revisions/{kind}/{slug}/diff",
+            get(revisions::diff_revisions),
+        )
+        .route(
+            "/revisions/{kind}/{slug}/{id}/rollback",
+            post(revisions::rollback_revision),
+        )
+        .route("/trash", get(trash::list_trash).delete(trash::purge_trash))
+        .route("/trash/{kind}/{slug}/restore", post(trash::restore_item))
+        .route_layer(middleware::from_fn_with_state(
+            auth_config,
+            crate::auth::middleware::require_admin,
+        ))
+        .with_state(state)
+}
@@ -0,0 +1,312 @@
+use super::AdminState;
+use crate::{
+    auth::UserInfo,
+    error::{ApiError, ApiResult},
+    models::{ProjectVisibility, PublicationStatus},
+};
+use axum::{extract::State, Extension, Json};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, sync::Arc};
+
+/// Position of a project in the listings, whatever its visibility or publication
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "swagger", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectPosition {
+    pub slug: String,
+    pub title: String,
+    pub featured: bool,
+    pub order: i32,
+    pub visibility: ProjectVisibility,
+    pub publication: PublicationStatus,
+}
+
+/// New order of projects, first slug first
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "swagger", derive(utoipa::ToSchema))]
+pub struct ReorderRequest {
+    pub slugs: Vec<String>,
+}
+
+/// Every live project in listing order: featured first, then by `order` (Admin only)
+#[cfg_attr(feature = "swagger", utoipa::path(
+    get,
+    path = "/api/v1/admin/projects/order",
+    responses(
+        (status = 200, description = "Projects in listing order", body = Vec<ProjectPosition>),
+        (status = 401, description = "Unauthorized - Missing or invalid token"),
+        (status = 403, description = "Forbidden - Not an admin user")
+    ),
+    security(
+        ("google_oauth" = ["openid", "email", "profile"])
+    ),
+    tag = "admin"
+))]
+pub async fn project_order(
+    State(state): State<Arc<AdminState>>,
+) -> ApiResult<Json<Vec<ProjectPosition>>> {
+    let mut projects = state.repositories.projects.list().await?;
+    projects.sort_by_key(|project| (!project.featured, project.order));
+
+    Ok(Json(
+        projects
+            .into_iter()
+            .map(|project| ProjectPosition {
+                slug: project.slug,
+                title: project.title,
+                featured: project.featured,
+                order: project.order,
+                visibility: project.visibility,
+                publication: project.publication,
+            })
+            .collect(),
+    ))
+}
+
+/// Put the listed projects in the order of `slugs`, all at once (Admin only)
+/// They trade places among the positions they held and projects left out keep theirs,
+/// every live project is renumbered; nothing is written if any slug is unknown
+#[cfg_attr(feature = "swagger", utoipa::path(
+    put,
+    path = "/api/v1/admin/projects/order",
+    request_body = ReorderRequest,
+    responses(
+        (status = 200, description = "Projects reordered"),
+        (status = 400, description = "Empty or repeated slugs"),
+        (status = 401, description = "Unauthorized - Missing or invalid token"),
+        (status = 403, description = "Forbidden - Not an admin user"),
+        (status = 404, description = "Some slugs match no live project")
+    ),
+    security(
+        ("google_oauth" = ["openid", "email", "profile"])
+    ),
+    tag = "admin"
+))]
+pub async fn reorder_projects(
+    State(state): State<Arc<AdminState>>,
+    Extension(user): Extension<UserInfo>,
+    Json(request): Json<ReorderRequest>,
+) -> ApiResult<Json<serde_json::Value>> {
+    if request.slugs.is_empty() {
+        return Err(ApiError::BadRequest("slugs must not be empty".to_string()));
+    }
+    let mut seen = HashSet::new();
+    if let Some(repeated) = request.slugs.iter().find(|slug| !seen.insert(*slug)) {
+        return Err(ApiError::BadRequest(format!(
+            "'{}' is listed more than once",
+            repeated
+        )));
+    }
+
+    state.repositories.projects.reorder(&request.slugs).await?;
+
+    tracing::info!(
+        "Admin {} reordered {} projects",
+        user.email,
+        request.slugs.len()
+    );
+    Ok(Json(
+        serde_json::json!({ "reordered": request.slugs.len() }),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        api::{
+            conditional::HttpCache,
+            projects,
+            test_support::{admin_app, body_json, test_auth, StubEmbedder},
+        },
+        repositories::{Repositories, VersionCheck},
+    };
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_reorder_drives_featured_first_public_listing() {
+        let (auth, token) = test_auth();
+        let repositories = Repositories::in_memory();
+        for (slug, featured, visibility, publication) in [
+            ("alpha", false, "public", "published"),
+            ("beta", true, "public", "published"),
+            ("gamma", false, "public", "published"),
+            ("delta", true, "public", "published"),
+            ("hidden", false, "public", "draft"),
+            ("secret", false, "unlisted", "published"),
+        ] {
+            let project = serde_json::from_value(json!({
+                "slug": slug,
+                "title": slug,
+                "technologies": ["Rust"],
+                "features": [],
+                "githubUrl": format!("https://github.com/example/{}", slug),
+                "featured": featured,
+                "visibility": visibility,
+                "publication": publication
+            }))
+            .unwrap();
+            repositories.projects.create(project).await.unwrap();
+        }
+        let http_cache = Arc::new(HttpCache::default());
+        let admin = admin_app(&repositories);
+        let public = projects::router(repositories.clone(), StubEmbedder::ok(), auth, http_cache);
+        let reorder = |slugs: Value| {
+            Request::builder()
+                .method("PUT")
+                .uri("/projects/order")
+                .header("Authorization", format!("Bearer {}", token))
+                .header("Content-Type", "application/json")
+                .body(Body::from(json!({ "slugs": slugs }).to_string()))
+                .unwrap()
+        };
+        let listed = |public: axum::Router| async move {
+            let request = Request::builder().uri("/").body(Body::empty()).unwrap();
+            let body = body_json(public.oneshot(request).await.unwrap()).await;
+            body["items"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|item| item["slug"].as_str().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        // Featured first, insertion order within each group until reordered
+        assert_eq!(
+            listed(public.clone()).await,
+            ["beta", "delta", "alpha", "gamma"]
+        );
+
+        let response = admin
+            .clone()
+            .oneshot(reorder(json!(["delta", "beta", "gamma", "alpha"])))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            listed(public.clone()).await,
+            ["delta", "beta", "gamma", "alpha"]
+        );
+
+        // One unknown slug rejects the whole request
+        let response = admin
+            .clone()
+            .oneshot(reorder(json!(["alpha", "gamma", "missing"])))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            listed(public.clone()).await,
+            ["delta", "beta", "gamma", "alpha"]
+        );
+        let response = admin
+            .clone()
+            .oneshot(reorder(json!(["alpha", "alpha"])))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        // Drafts are hidden from public reads, unlisted projects only from listings
+        let get = |slug: &str| {
+            Request::builder()
+                .uri(format!("/{}", slug))
+                .body(Body::empty())
+                .unwrap()
+        };
+        let response = public.clone().oneshot(get("hidden")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let response = public.oneshot(get("secret")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let request = Request::builder()
+            .uri("/projects/order")
+            .header("Authorization", format!("Bearer {}", token))
+            .body(Body::empty())
+            .unwrap();
+        let order = body_json(admin.oneshot(request).await.unwrap()).await;
+        assert_eq!(order.as_array().unwrap().len(), 6);
+        assert_eq!(order[0]["slug"], "delta");
+        assert_eq!(order[0]["featured"], true);
+    }
+
+    #[tokio::test]
+    async fn test_partial_reorder_keeps_other_projects_in_place() {
+        let (_, token) = test_auth();
+        let repositories = Repositories::in_memory();
+        for slug in ["alpha", "beta", "gamma", "delta", "epsilon"] {
+            let project = serde_json::from_value(json!({
+                "slug": slug,
+                "title": slug,
+                "technologies": ["Rust"],
+                "features": [],
+                "githubUrl": format!("https://github.com/example/{}", slug)
+            }))
+            .unwrap();
+            repositories.projects.create(project).await.unwrap();
+        }
+        let admin = admin_app(&repositories);
+        let send = |method: &str, body: Body| {
+            let request = Request::builder()
+                .method(method)
+                .uri("/projects/order")
+                .header("Authorization", format!("Bearer {}", token))
+                .header("Content-Type", "application/json")
+                .body(body)
+                .unwrap();
+            admin.clone().oneshot(request)
+        };
+        let reorder = |slugs: Value| send("PUT", Body::from(json!({ "slugs": slugs }).to_string()));
+        let positions = || async {
+            let order = body_json(send("GET", Body::empty()).await.unwrap()).await;
+            order
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|item| {
+                    let slug = item["slug"].as_str().unwrap().to_string();
+                    (slug, item["order"].as_i64().unwrap())
+                })
+                .collect::<Vec<_>>()
+        };
+        let expected = |slugs: &[&str]| {
+            slugs
+                .iter()
+                .enumerate()
+                .map(|(order, slug)| (slug.to_string(), order as i64))
+                .collect::<Vec<_>>()
+        };
+
+        // Every project starts at order 0, a first partial reorder numbers them all
+        let response = reorder(json!(["delta", "beta"])).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            positions().await,
+            expected(&["alpha", "delta", "gamma", "beta", "epsilon"])
+        );
+
+        let response = reorder(json!(["epsilon", "alpha"])).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            positions().await,
+            expected(&["epsilon", "delta", "gamma", "beta", "alpha"])
+        );
+
+        // Reordering after a trash doesn't collide with the remaining projects
+        repositories
+            .projects
+            .trash("delta", "admin@example.com", &VersionCheck::Any)
+            .await
+            .unwrap();
+        let response = reorder(json!(["beta", "epsilon"])).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            positions().await,
+            expected(&["beta", "gamma", "epsilon", "alpha"])
+        );
+    }
+}
@@ -0,0 +1,384 @@
+use super::AdminState;
+use crate::{
+    auth::UserInfo,
+    error::{ApiError, ApiResult},
+    models::{ContentKind, PublicationStatus},
+};
+use axum::{
+    extract::{Path, Query, State},
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Query parameters of the unpublished content list
+#[derive(Debug, Default, Deserialize)]
+pub struct PublicationParams {
+    /// Only content in this status, every unpublished item when omitted
+    pub status: Option<PublicationStatus>,
+}
+
+/// Where a project or certificate is in the publishing workflow
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "swagger", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PublicationItem {
+    pub kind: ContentKind,
+    pub slug: String,
+    /// Project title or certificate name
+    pub title: String,
+    pub status: PublicationStatus,
+    pub publish_at: Option<DateTime<Utc>>,
+}
+
+/// Requested move in the publishing workflow
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "swagger", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PublicationRequest {
+    pub status: PublicationStatus,
+    /// Required when scheduling, and only then
+    #[serde(default)]
+    pub publish_at: Option<DateTime<Utc>>,
+}
+
+/// Every live item of both kinds in the publishing workflow
+async fn publication_items(state: &AdminState) -> ApiResult<Vec<PublicationItem>> {
+    let projects = state.repositories.projects.list().await?;
+    let certificates = state.repositories.certificates.list().await?;
+
+    let projects = projects.into_iter().map(|project| PublicationItem {
+        kind: ContentKind::Project,
+        slug: project.slug,
+        title: project.title,
+        status: project.publication,
+        publish_at: project.publish_at,
+    });
+    let certificates = certificates.into_iter().map(|certificate| PublicationItem {
+        kind: ContentKind::Certificate,
+        slug: certificate.slug,
+        title: certificate.name,
+        status: certificate.publication,
+        publish_at: certificate.publish_at,
+    });
+    Ok(projects.chain(certificates).collect())
+}
+
+/// Drafts, scheduled and archived content, next to go live first (Admin only)
+#[cfg_attr(feature = "swagger", utoipa::path(
+    get,
+    path = "/api/v1/admin/publication",
+    params(
+        ("status" = Option<PublicationStatus>, Query, description = "Only content in this status")
+    ),
+    responses(
+        (status = 200, description = "Content in the publishing workflow", body = Vec<PublicationItem>),
+        (status = 401, description = "Unauthorized - Missing or invalid token"),
+        (status = 403, description = "Forbidden - Not an admin user")
+    ),
+    security(
+        ("google_oauth" = ["openid", "email", "profile"])
+    ),
+    tag = "admin"
+))]
+pub async fn list_unpublished(
+    State(state): State<Arc<AdminState>>,
+    Query(params): Query<PublicationParams>,
+) -> ApiResult<Json<Vec<PublicationItem>>> {
+    let mut items: Vec<PublicationItem> = publication_items(&state)
+        .await?
+        .into_iter()
+        .filter(|item| match params.status {
+            Some(status) => item.status == status,
+            None => item.status != PublicationStatus::Published,
+        })
+        .collect();
+    // Scheduled content first, soonest first, then the rest by slug
+    items.sort_by(|a, b| {
+        (a.publish_at.is_none(), a.publish_at, &a.slug).cmp(&(
+            b.publish_at.is_none(),
+            b.publish_at,
+            &b.slug,
+        ))
+    });
+
+    Ok(Json(items))
+}
+
+/// Move a project or certificate through the publishing workflow (Admin only)
+/// Drafts can be scheduled, published or archived; published content goes back to draft
+/// or is archived; archived content is republished or reopened as a draft
+#[cfg_attr(feature = "swagger", utoipa::path(
+    put,
+    path = "/api/v1/admin/publication/{kind}/{slug}",
+    params(
+        ("kind" = ContentKind, Path, description = "project or certificate"),
+        ("slug" = String, Path, description = "Current slug of the document")
+    ),
+    request_body = PublicationRequest,
+    responses(
+        (status = 200, description = "Status changed", body = PublicationItem),
+        (status = 400, description = "Missing, past or unexpected publishAt"),
+        (status = 401, description = "Unauthorized - Missing or invalid token"),
+        (status = 403, description = "Forbidden - Not an admin user"),
+        (status = 404, description = "Document not found"),
+        (status = 409, description = "The workflow does not allow this move")
+    ),
+    security(
+        ("google_oauth" = ["openid", "email", "profile"])
+    ),
+    tag = "admin"
+))]
+pub async fn set_publication(
+    State(state): State<Arc<AdminState>>,
+    Extension(user): Extension<UserInfo>,
+    Path((kind, slug)): Path<(ContentKind, String)>,
+    Json(request): Json<PublicationRequest>,
+) -> ApiResult<Json<PublicationItem>> {
+    let not_found = || ApiError::NotFound(format!("No {} '{}'", kind.as_str(), slug));
+    let (title, current) = match kind {
+        ContentKind::Project => {
+            let project = state
+                .repositories
+                .projects
+                .get_by_slug(&slug)
+                .await?
+                .ok_or_else(not_found)?;
+            (project.title, project.publication)
+        }
+        ContentKind::Certificate => {
+            let certificate = state
+                .repositories
+                .certificates
+                .get_by_slug(&slug)
+                .await?
+                .ok_or_else(not_found)?;
+            (certificate.name, certificate.publication)
+        }
+    };
+
+    if !current.can_become(request.status) {
+        return Err(ApiError::Conflict(format!(
+            "A {} {} cannot become {}",
+            current.as_str(),
+            kind.as_str(),
+            request.status.as_str()
+        )));
+    }
+    match (request.status, request.publish_at) {
+        (PublicationStatus::Scheduled, None) => {
+            return Err(ApiError::BadRequest(
+                "publishAt is required when scheduling".to_string(),
+            ));
+        }
+        (PublicationStatus::Scheduled, Some(at)) if at <= Utc::now() => {
+            return Err(ApiError::BadRequest(
+                "publishAt must be in the future, publish directly instead".to_string(),
+            ));
+        }
+        (PublicationStatus::Scheduled, Some(_)) | (_, None) => {}
+        (_, Some(_)) => {
+            return Err(ApiError::BadRequest(
+                "publishAt is only accepted when scheduling".to_string(),
+            ));
+        }
+    }
+
+    let stored = match kind {
+        ContentKind::Project => {
+            state
+                .repositories
+                .projects
+                .set_publication(&slug, request.status, request.publish_at)
+                .await?
+        }
+        ContentKind::Certificate => {
+            state
+                .repositories
+                .certificates
+                .set_publication(&slug, request.status, request.publish_at)
+                .await?
+        }
+    };
+    if !stored {
+        return Err(not_found());
+    }
+
+    tracing::info!(
+        "Admin {} moved {} {} from {} to {}",
+        user.email,
+        kind.as_str(),
+        slug,
+        current.as_str(),
+        request.status.as_str()
+    );
+    Ok(Json(PublicationItem {
+        kind,
+        slug,
+        title,
+        status: request.status,
+        publish_at: request.publish_at,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        api::{
+            certificates,
+            conditional::HttpCache,
+            projects, search,
+            test_support::{admin_app, body_json, test_auth, StubEmbedder},
+        },
+        repositories::Repositories,
+    };
+    use axum::{
+        body::Body,
+        http::{header, Request, StatusCode},
+        Router,
+    };
+    use chrono::{Duration, Utc};
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_drafts_stay_private_until_published() {
+        let (auth, token) = test_auth();
+        let repositories = Repositories::in_memory();
+        let http_cache = Arc::new(HttpCache::default());
+        let admin = admin_app(&repositories);
+        let projects = projects::router(
+            repositories.clone(),
+            StubEmbedder::ok(),
+            auth.clone(),
+            http_cache.clone(),
+        );
+        let certificates =
+            certificates::router(repositories.clone(), StubEmbedder::ok(), auth, http_cache);
+        let search = search::router(repositories.clone());
+        let send = |app: &Router, method: &str, uri: &str, body: Option<Value>, admin: bool| {
+            let mut request = Request::builder().method(method).uri(uri);
+            if admin {
+                request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+            }
+            let request = match body {
+                Some(body) => request
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(body.to_string())),
+                None => request.body(Body::empty()),
+            };
+            app.clone().oneshot(request.unwrap())
+        };
+
+        let response = send(
+            &projects,
+            "POST",
+            "/",
+            Some(json!({
+                "title": "Rust Engine",
+                "technologies": ["Rust"],
+                "features": [],
+                "githubUrl": "https://github.com/example/engine",
+                "publication": "draft"
+            })),
+            true,
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let response = send(
+            &certificates,
+            "POST",
+            "/",
+            Some(json!({
+                "name": "Rust Fundamentals",
+                "issuer": "Academy",
+                "link": "https://example.com/verify",
+                "slug": "",
+                "publication": "scheduled"
+            })),
+            true,
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        // Hidden from public reads, listings and search, previewed by admins
+        let response = send(&projects, "GET", "/rust-engine", None, false)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let response = send(&projects, "GET", "/", None, false).await.unwrap();
+        assert_eq!(body_json(response).await["total"], 0);
+        let response = send(&search, "GET", "/?q=rust", None, false).await.unwrap();
+        assert_eq!(body_json(response).await["total"], 0);
+        let response = send(&projects, "GET", "/rust-engine", None, true)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CACHE_CONTROL],
+            "private, no-store"
+        );
+        assert_eq!(body_json(response).await["publication"], "draft");
+
+        let response = send(&admin, "GET", "/publication", None, true)
+            .await
+            .unwrap();
+        let items = body_json(response).await;
+        assert_eq!(items.as_array().unwrap().len(), 1);
+        assert_eq!(items[0]["kind"], "project");
+        assert_eq!(items[0]["status"], "draft");
+
+        // Scheduling needs a future date, published content is not rescheduled
+        let transition = |status: &str, publish_at: Option<String>| {
+            send(
+                &admin,
+                "PUT",
+                "/publication/project/rust-engine",
+                Some(json!({ "status": status, "publishAt": publish_at })),
+                true,
+            )
+        };
+        let past = (Utc::now() - Duration::hours(1)).to_rfc3339();
+        let future = (Utc::now() + Duration::hours(1)).to_rfc3339();
+        let response = transition("scheduled", Some(past)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let response = transition("scheduled", Some(future.clone())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_json(response).await["status"], "scheduled");
+        let response = send(&projects, "GET", "/rust-engine", None, false)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = transition("published", None).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = send(&projects, "GET", "/rust-engine", None, false)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = send(&search, "GET", "/?q=rust", None, false).await.unwrap();
+        assert_eq!(body_json(response).await["total"], 1);
+        let response = transition("scheduled", Some(future)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let response = transition("archived", None).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = send(&projects, "GET", "/", None, false).await.unwrap();
+        assert_eq!(body_json(response).await["total"], 0);
+
+        let response = send(
+            &admin,
+            "PUT",
+            "/publication/certificate/missing",
+            Some(json!({ "status": "published" })),
+            true,
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
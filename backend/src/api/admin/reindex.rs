@@ -0,0 +1,378 @@
+use super::AdminState;
+use crate::{
+    api::chat::{embed_content, Embedder, EmbeddingOutcome},
+    auth::UserInfo,
+    error::{ApiError, ApiResult},
+    models::ContentKind,
+    repositories::Repositories,
+};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+const DEFAULT_CONCURRENCY: usize = 4;
+const MAX_CONCURRENCY: usize = 16;
+/// Failures kept in the status report - the counters stay exact beyond this
+const MAX_REPORTED_FAILURES: usize = 50;
+
+/// Lifecycle of the reindex job
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReindexState {
+    #[default]
+    Idle,
+    Running,
+    Completed,
+    Failed,
+}
+
+/// A document whose embedding could not be regenerated
+#[derive(Debug, Clone, Serialize)]
+pub struct ReindexFailure {
+    pub kind: ContentKind,
+    pub slug: String,
+    pub error: String,
+}
+
+/// Progress report of the current (or last) reindex job
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReindexStatus {
+    pub state: ReindexState,
+    pub model: Option<String>,
+    pub concurrency: usize,
+    pub total: usize,
+    pub done: usize,
+    pub failed: usize,
+    pub started_by: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub error: Option<String>,
+    pub failures: Vec<ReindexFailure>,
+}
+
+/// Shared progress of the background reindex job
+#[derive(Default)]
+pub struct ReindexTracker {
+    status: Mutex<ReindexStatus>,
+}
+
+impl ReindexTracker {
+    /// Current progress snapshot
+    pub fn snapshot(&self) -> ReindexStatus {
+        self.status.lock().unwrap().clone()
+    }
+
+    /// Reset progress for a new job, returns false if a job is already running
+    fn try_start(&self, model: &str, concurrency: usize, started_by: &str) -> bool {
+        let mut status = self.status.lock().unwrap();
+        if status.state == ReindexState::Running {
+            return false;
+        }
+
+        *status = ReindexStatus {
+            state: ReindexState::Running,
+            model: Some(model.to_string()),
+            concurrency,
+            started_by: Some(started_by.to_string()),
+            started_at: Some(Utc::now()),
+            ..Default::default()
+        };
+        true
+    }
+
+    fn set_total(&self, total: usize) {
+        self.status.lock().unwrap().total = total;
+    }
+
+    fn record_success(&self) {
+        self.status.lock().unwrap().done += 1;
+    }
+
+    fn record_failure(&self, failure: ReindexFailure) {
+        let mut status = self.status.lock().unwrap();
+        status.failed += 1;
+        if status.failures.len() < MAX_REPORTED_FAILURES {
+            status.failures.push(failure);
+        }
+    }
+
+    fn finish(&self, error: Option<String>) {
+        let mut status = self.status.lock().unwrap();
+        status.state = if error.is_some() {
+            ReindexState::Failed
+        } else {
+            ReindexState::Completed
+        };
+        status.error = error;
+        status.finished_at = Some(Utc::now());
+    }
+}
+
+/// Query parameters for starting a reindex
+#[derive(Debug, Default, Deserialize)]
+pub struct ReindexParams {
+    /// Number of embedding requests in flight at once (1-16, default 4)
+    pub concurrency: Option<usize>,
+}
+
+/// A document queued for re-embedding
+struct ReindexItem {
+    kind: ContentKind,
+    slug: String,
+    text: String,
+}
+
+/// Start re-embedding every project and certificate in the background (Admin only)
+#[cfg_attr(feature = "swagger", utoipa::path(
+    post,
+    path = "/api/v1/admin/reindex",
+    params(
+        ("concurrency" = Option<usize>, Query, description = "Concurrent embedding requests (1-16)")
+    ),
+    responses(
+        (status = 202, description = "Reindex job started"),
+        (status = 400, description = "Invalid concurrency"),
+        (status = 401, description = "Unauthorized - Missing or invalid token"),
+        (status = 403, description = "Forbidden - Not an admin user"),
+        (status = 409, description = "A reindex job is already running")
+    ),
+    security(
+        ("google_oauth" = ["openid", "email", "profile"])
+    ),
+    tag = "admin"
+))]
+pub async fn start_reindex(
+    State(state): State<Arc<AdminState>>,
+    Extension(user): Extension<UserInfo>,
+    Query(params): Query<ReindexParams>,
+) -> ApiResult<(StatusCode, Json<ReindexStatus>)> {
+    let concurrency = params.concurrency.unwrap_or(DEFAULT_CONCURRENCY);
+    if !(1..=MAX_CONCURRENCY).contains(&concurrency) {
+        return Err(ApiError::BadRequest(format!(
+            "concurrency must be between 1 and {}",
+            MAX_CONCURRENCY
+        )));
+    }
+
+    if !state
+        .reindex
+        .try_start(state.embedder.model(), concurrency, &user.email)
+    {
+        return Err(ApiError::Conflict(
+            "A reindex job is already running".to_string(),
+        ));
+    }
+
+    tracing::info!(
+        "Admin {} started reindex with model {} (concurrency {})",
+        user.email,
+        state.embedder.model(),
+        concurrency
+    );
+
+    tokio::spawn(run_reindex(
+        state.repositories.clone(),
+        state.embedder.clone(),
+        state.reindex.clone(),
+        concurrency,
+    ));
+
+    Ok((StatusCode::ACCEPTED, Json(state.reindex.snapshot())))
+}
+
+/// Progress of the current or last reindex job (Admin only)
+#[cfg_attr(feature = "swagger", utoipa::path(
+    get,
+    path = "/api/v1/admin/reindex",
+    responses(
+        (status = 200, description = "Reindex job status"),
+        (status = 401, description = "Unauthorized - Missing or invalid token"),
+        (status = 403, description = "Forbidden - Not an admin user")
+    ),
+    security(
+        ("google_oauth" = ["openid", "email", "profile"])
+    ),
+    tag = "admin"
+))]
+pub async fn reindex_status(State(state): State<Arc<AdminState>>) -> Json<ReindexStatus> {
+    Json(state.reindex.snapshot())
+}
+
+/// Re-embed all content with bounded concurrency, reporting progress to the tracker
+async fn run_reindex(
+    repositories: Repositories,
+    embedder: Arc<dyn Embedder>,
+    tracker: Arc<ReindexTracker>,
+    concurrency: usize,
+) {
+    let items = match collect_items(&repositories).await {
+        Ok(items) => items,
+        Err(e) => {
+            tracing::error!("Reindex aborted, failed to load content: {}", e);
+            tracker.finish(Some(e.to_string()));
+            return;
+        }
+    };
+
+    tracker.set_total(items.len());
+
+    stream::iter(items)
+        .for_each_concurrent(concurrency, |item| {
+            let repositories = &repositories;
+            let embedder = embedder.as_ref();
+            let tracker = tracker.as_ref();
+            async move {
+                match reindex_item(repositories, embedder, &item).await {
+                    Ok(()) => tracker.record_success(),
+                    Err(error) => {
+                        tracing::warn!(
+                            "Reindex failed for {:?} '{}': {}",
+                            item.kind,
+                            item.slug,
+                            error
+                        );
+                        tracker.record_failure(ReindexFailure {
+                            kind: item.kind,
+                            slug: item.slug,
+                            error,
+                        });
+                    }
+                }
+            }
+        })
+        .await;
+
+    let status = tracker.snapshot();
+    tracing::info!(
+        "Reindex finished: {} done, {} failed, {} total",
+        status.done,
+        status.failed,
+        status.total
+    );
+    tracker.finish(None);
+}
+
+/// Canonical embedding text of every stored project and certificate
+async fn collect_items(repositories: &Repositories) -> ApiResult<Vec<ReindexItem>> {
+    let projects = repositories.projects.list().await?;
+    let certificates = repositories.certificates.list().await?;
+
+    let project_items = projects.into_iter().map(|project| ReindexItem {
+        kind: ContentKind::Project,
+        text: project.embedding_text(),
+        slug: project.slug,
+    });
+    let certificate_items = certificates.into_iter().map(|certificate| ReindexItem {
+        kind: ContentKind::Certificate,
+        text: certificate.embedding_text(),
+        slug: certificate.slug,
+    });
+
+    Ok(project_items.chain(certificate_items).collect())
+}
+
+/// Embed one document and store the vector with its model
+async fn reindex_item(
+    repositories: &Repositories,
+    embedder: &dyn Embedder,
+    item: &ReindexItem,
+) -> Result<(), String> {
+    let embedding = match embed_content(embedder, &item.text).await {
+        (Some(embedding), _) => embedding,
+        (None, EmbeddingOutcome::Failed { error }) => return Err(error),
+        (None, _) => return Err("No embedding generated".to_string()),
+    };
+
+    let stored = match item.kind {
+        ContentKind::Project => {
+            repositories
+                .projects
+                .set_embedding(&item.slug, embedding.values, &embedding.model)
+                .await
+        }
+        ContentKind::Certificate => {
+            repositories
+                .certificates
+                .set_embedding(&item.slug, embedding.values, &embedding.model)
+                .await
+        }
+    };
+
+    match stored {
+        Ok(true) => Ok(()),
+        Ok(false) => Err("Document no longer exists".to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::slug::slugify;
+    use crate::{api::test_support::StubEmbedder, models::Certificate};
+
+    fn certificate(name: &str) -> Certificate {
+        Certificate {
+            id: None,
+            name: name.to_string(),
+            issuer: "Coursera".to_string(),
+            link: "https://coursera.org/verify/abc".to_string(),
+            slug: slugify(name),
+            issue_date: None,
+            embedding: Some(vec![0.0; 2]),
+            embedding_model: Some("old-model".to_string()),
+            embedding_dimensions: Some(2),
+            expires_at: None,
+            credential_id: None,
+            image_url: None,
+            verified: false,
+            verified_at: None,
+            publication: Default::default(),
+            publish_at: None,
+            deleted_at: None,
+            deleted_by: None,
+            version: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reindex_reports_progress_and_records_model() {
+        let repositories = Repositories::in_memory();
+        for name in ["Rust Basics", "Cloud fail Practitioner", "Data Science"] {
+            repositories
+                .certificates
+                .create(certificate(name))
+                .await
+                .unwrap();
+        }
+
+        let tracker = Arc::new(ReindexTracker::default());
+        let embedder = Arc::new(StubEmbedder {
+            fail_on: Some("fail"),
+        });
+        assert!(tracker.try_start(embedder.model(), 2, "admin@example.com"));
+        assert!(!tracker.try_start(embedder.model(), 2, "admin@example.com"));
+
+        run_reindex(repositories.clone(), embedder, tracker.clone(), 2).await;
+
+        let status = tracker.snapshot();
+        assert_eq!(status.state, ReindexState::Completed);
+        assert_eq!((status.total, status.done, status.failed), (3, 2, 1));
+        assert_eq!(status.failures[0].slug, "cloud-fail-practitioner");
+
+        let reindexed = repositories
+            .certificates
+            .get_by_slug("rust-basics")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(reindexed.embedding_model.as_deref(), Some("stub-embedding"));
+        assert_eq!(reindexed.embedding_dimensions, Some(4));
+    }
+}
@@ -0,0 +1,399 @@
+use super::AdminState;
+use crate::{
+    api::chat::embed_content,
+    auth::UserInfo,
+    error::{ApiError, ApiResult},
+    models::{
+        certificate::CertificateUpdate,
+        project::ProjectUpdate,
+        revision::{diff, FieldChange, RevisionSummary},
+        Certificate, ContentKind, Project, Revision,
+    },
+    repositories::VersionCheck,
+};
+use axum::{
+    extract::{Path, Query, State},
+    Extension, Json,
+};
+use mongodb::bson::{oid::ObjectId, Document};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+/// Query parameters for comparing two versions of a document
+#[derive(Debug, Deserialize)]
+pub struct DiffParams {
+    /// Revision id of the older version
+    pub from: String,
+    /// Revision id of the newer version, the live document when omitted
+    pub to: Option<String>,
+}
+
+/// Field-level differences between two versions of a document
+#[derive(Debug, Serialize)]
+pub struct DiffResponse {
+    pub from: String,
+    pub to: String,
+    pub changes: Vec<FieldChange>,
+}
+
+/// Live document as stored, with its id
+async fn current_document(
+    state: &AdminState,
+    kind: ContentKind,
+    slug: &str,
+) -> ApiResult<(ObjectId, Document)> {
+    let document = match kind {
+        ContentKind::Project => match state.repositories.projects.get_by_slug(slug).await? {
+            Some(project) => Some(mongodb::bson::to_document(&project)?),
+            None => None,
+        },
+        ContentKind::Certificate => {
+            match state.repositories.certificates.get_by_slug(slug).await? {
+                Some(certificate) => Some(mongodb::bson::to_document(&certificate)?),
+                None => None,
+            }
+        }
+    };
+    let document =
+        document.ok_or_else(|| ApiError::NotFound(format!("No {} '{}'", kind.as_str(), slug)))?;
+    let id = document
+        .get_object_id("_id")
+        .map_err(|_| ApiError::InternalError("Stored document has no ObjectId".to_string()))?;
+
+    Ok((id, document))
+}
+
+/// A revision of the document with id `content_id`
+async fn find_revision(
+    state: &AdminState,
+    kind: ContentKind,
+    content_id: ObjectId,
+    id: &str,
+) -> ApiResult<Revision> {
+    let id = ObjectId::parse_str(id)?;
+    state
+        .repositories
+        .revisions
+        .get(kind, content_id, id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Revision {} not found", id)))
+}
+
+/// Model stored in a revision
+fn revision_model<T: DeserializeOwned>(revision: Revision) -> ApiResult<T> {
+    mongodb::bson::from_document(revision.document).map_err(|e| {
+        tracing::error!("Revision {:?} does not match model: {}", revision.id, e);
+        ApiError::InternalError("Stored revision is malformed".to_string())
+    })
+}
+
+/// List revisions of a project or certificate, newest first (Admin only)
+#[cfg_attr(feature = "swagger", utoipa::path(
+    get,
+    path = "/api/v1/admin/revisions/{kind}/{slug}",
+    params(
+        ("kind" = ContentKind, Path, description = "project or certificate"),
+        ("slug" = String, Path, description = "Current slug of the document")
+    ),
+    responses(
+        (status = 200, description = "Revisions, newest first", body = Vec<RevisionSummary>),
+        (status = 404, description = "Document not found"),
+        (status = 401, description = "Unauthorized - Missing or invalid token"),
+        (status = 403, description = "Forbidden - Not an admin user")
+    ),
+    security(
+        ("google_oauth" = ["openid", "email", "profile"])
+    ),
+    tag = "admin"
+))]
+pub async fn list_revisions(
+    State(state): State<Arc<AdminState>>,
+    Path((kind, slug)): Path<(ContentKind, String)>,
+) -> ApiResult<Json<Vec<RevisionSummary>>> {
+    let (content_id, _) = current_document(&state, kind, &slug).await?;
+    let revisions = state.repositories.revisions.list(kind, content_id).await?;
+    Ok(Json(revisions.iter().map(RevisionSummary::from).collect()))
+}
+
+/// Field-level diff between two revisions, or a revision and the live document (Admin only)
+#[cfg_attr(feature = "swagger", utoipa::path(
+    get,
+    path = "/api/v1/admin/revisions/{kind}/{slug}/diff",
+    params(
+        ("kind" = ContentKind, Path, description = "project or certificate"),
+        ("slug" = String, Path, description = "Current slug of the document"),
+        ("from" = String, Query, description = "Revision id of the older version"),
+        ("to" = Option<String>, Query, description = "Revision id of the newer version, defaults to the live document")
+    ),
+    responses(
+        (status = 200, description = "Changed fields"),
+        (status = 400, description = "Invalid revision id"),
+        (status = 404, description = "Document or revision not found"),
+        (status = 401, description = "Unauthorized - Missing or invalid token"),
+        (status = 403, description = "Forbidden - Not an admin user")
+    ),
+    security(
+        ("google_oauth" = ["openid", "email", "profile"])
+    ),
+    tag = "admin"
+))]
+pub async fn diff_revisions(
+    State(state): State<Arc<AdminState>>,
+    Path((kind, slug)): Path<(ContentKind, String)>,
+    Query(params): Query<DiffParams>,
+) -> ApiResult<Json<DiffResponse>> {
+    let (content_id, current) = current_document(&state, kind, &slug).await?;
+    let from = find_revision(&state, kind, content_id, &params.from).await?;
+    let (to_label, to) = match &params.to {
+        Some(id) => (
+            id.clone(),
+            find_revision(&state, kind, content_id, id).await?.document,
+        ),
+        None => ("current".to_string(), current),
+    };
+
+    Ok(Json(DiffResponse {
+        from: params.from,
+        to: to_label,
+        changes: diff(&from.document, &to),
+    }))
+}
+
+/// Restore the content of a revision, keeping the current slug (Admin only)
+/// The replaced version is itself kept as a new revision
+#[cfg_attr(feature = "swagger", utoipa::path(
+    post,
+    path = "/api/v1/admin/revisions/{kind}/{slug}/{id}/rollback",
+    params(
+        ("kind" = ContentKind, Path, description = "project or certificate"),
+        ("slug" = String, Path, description = "Current slug of the document"),
+        ("id" = String, Path, description = "Revision to restore")
+    ),
+    responses(
+        (status = 200, description = "Document rolled back"),
+        (status = 400, description = "Invalid revision id"),
+        (status = 404, description = "Document or revision not found"),
+        (status = 401, description = "Unauthorized - Missing or invalid token"),
+        (status = 403, description = "Forbidden - Not an admin user")
+    ),
+    security(
+        ("google_oauth" = ["openid", "email", "profile"])
+    ),
+    tag = "admin"
+))]
+pub async fn rollback_revision(
+    State(state): State<Arc<AdminState>>,
+    Extension(user): Extension<UserInfo>,
+    Path((kind, slug, id)): Path<(ContentKind, String, String)>,
+) -> ApiResult<Json<Value>> {
+    let (content_id, _) = current_document(&state, kind, &slug).await?;
+    let revision = find_revision(&state, kind, content_id, &id).await?;
+    let not_found = || ApiError::NotFound(format!("No {} '{}'", kind.as_str(), slug));
+
+    let (before, embedding_text) = match kind {
+        ContentKind::Project => {
+            let mut update = ProjectUpdate::from(revision_model::<Project>(revision)?);
+            update.slug = slug.clone();
+            update.retire_draft_visibility();
+            let text = update.embedding_text();
+            let before = state
+                .repositories
+                .projects
+                .update(&slug, update, &VersionCheck::Any)
+                .await?;
+            (
+                Revision::capture(kind, &before.ok_or_else(not_found)?, &user.email)?,
+                text,
+            )
+        }
+        ContentKind::Certificate => {
+            let update = CertificateUpdate::from(revision_model::<Certificate>(revision)?);
+            let text = update.embedding_text();
+            let before = state
+                .repositories
+                .certificates
+                .update(&slug, update, &VersionCheck::Any)
+                .await?;
+            (
+                Revision::capture(kind, &before.ok_or_else(not_found)?, &user.email)?,
+                text,
+            )
+        }
+    };
+    state.repositories.revisions.record(before).await?;
+
+    let (embedding, embedding_outcome) =
+        embed_content(state.embedder.as_ref(), &embedding_text).await;
+    // A failed embedding must not leave the replaced text's vector behind
+    let projects = &state.repositories.projects;
+    let certificates = &state.repositories.certificates;
+    match (kind, embedding) {
+        (ContentKind::Project, Some(embedding)) => {
+            projects
+                .set_embedding(&slug, embedding.values, &embedding.model)
+                .await?
+        }
+        (ContentKind::Certificate, Some(embedding)) => {
+            certificates
+                .set_embedding(&slug, embedding.values, &embedding.model)
+                .await?
+        }
+        (ContentKind::Project, None) => projects.clear_embedding(&slug).await?,
+        (ContentKind::Certificate, None) => certificates.clear_embedding(&slug).await?,
+    };
+
+    tracing::info!(
+        "Admin {} rolled back {} '{}' to revision {}",
+        user.email,
+        kind.as_str(),
+        slug,
+        id
+    );
+    Ok(Json(json!({
+        "message": "Rolled back to revision",
+        "kind": kind,
+        "slug": slug,
+        "revision": id,
+        "embedding": embedding_outcome
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        api::{
+            conditional::HttpCache,
+            projects,
+            test_support::{admin_app, body_json, test_auth, StubEmbedder},
+        },
+        repositories::Repositories,
+    };
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        Router,
+    };
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    fn project(title: &str, overview: &str) -> Value {
+        json!({
+            "date": "2024",
+            "title": title,
+            "description": { "overview": overview },
+            "technologies": ["Rust"],
+            "features": [],
+            "githubUrl": "https://github.com/example/rust-api",
+            "images": null
+        })
+    }
+
+    async fn send(
+        app: &Router,
+        method: &str,
+        uri: &str,
+        token: &str,
+        body: Option<Value>,
+    ) -> Value {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Authorization", format!("Bearer {}", token))
+            .header("Content-Type", "application/json")
+            .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert!(response.status().is_success(), "{} {}", method, uri);
+        body_json(response).await
+    }
+
+    #[tokio::test]
+    async fn test_updates_are_revisioned_and_can_be_rolled_back() {
+        let (auth, token) = test_auth();
+        let repositories = Repositories::in_memory();
+        let http_cache = Arc::new(HttpCache::default());
+        let projects = projects::router(repositories.clone(), StubEmbedder::ok(), auth, http_cache);
+        let admin = admin_app(&repositories);
+
+        let mut body = project("Rust API", "First draft");
+        body["slug"] = json!("rust-api");
+        send(&projects, "POST", "/", &token, Some(body)).await;
+        send(
+            &projects,
+            "PUT",
+            "/rust-api",
+            &token,
+            Some(project("Rust API", "Second draft")),
+        )
+        .await;
+        send(
+            &projects,
+            "PUT",
+            "/rust-api",
+            &token,
+            Some(project("Rust REST API", "Final")),
+        )
+        .await;
+
+        let revisions = send(&admin, "GET", "/revisions/project/rust-api", &token, None).await;
+        let revisions = revisions.as_array().unwrap();
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(revisions[0]["editedBy"], "admin@example.com");
+        let first = revisions[1]["id"].as_str().unwrap().to_string();
+
+        let diff = send(
+            &admin,
+            "GET",
+            &format!("/revisions/project/rust-api/diff?from={}", first),
+            &token,
+            None,
+        )
+        .await;
+        assert_eq!(diff["to"], "current");
+        let paths: Vec<&str> = diff["changes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["path"].as_str().unwrap())
+            .collect();
+        assert_eq!(paths, vec!["description.overview", "title"]);
+        assert_eq!(diff["changes"][0]["before"], "First draft");
+
+        send(
+            &admin,
+            "POST",
+            &format!("/revisions/project/rust-api/{}/rollback", first),
+            &token,
+            None,
+        )
+        .await;
+        let restored = repositories
+            .projects
+            .get_by_slug("rust-api")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(restored.title, "Rust API");
+        assert_eq!(
+            restored.description.unwrap().overview.as_deref(),
+            Some("First draft")
+        );
+
+        // The rolled-back version is kept too
+        let revisions = send(&admin, "GET", "/revisions/project/rust-api", &token, None).await;
+        assert_eq!(revisions.as_array().unwrap().len(), 3);
+
+        let response = admin
+            .oneshot(
+                Request::get("/revisions/project/rust-api/diff?from=not-an-id")
+                    .header("Authorization", format!("Bearer {}", token))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,260 @@
+use super::AdminState;
+use crate::{
+    auth::UserInfo,
+    error::{ApiError, ApiResult},
+    models::ContentKind,
+};
+use axum::{
+    extract::{Path, Query, State},
+    Extension, Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+/// Trashed items are kept this long when no `older_than_days` is given
+const DEFAULT_RETENTION_DAYS: i64 = 30;
+
+/// A trashed project or certificate
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashItem {
+    pub kind: ContentKind,
+    pub slug: String,
+    pub title: String,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub deleted_by: Option<String>,
+}
+
+/// Query parameters for purging the trash
+#[derive(Debug, Default, Deserialize)]
+pub struct PurgeParams {
+    /// Only purge items trashed at least this many days ago (default 30)
+    pub older_than_days: Option<i64>,
+}
+
+/// List trashed projects and certificates, most recently deleted first (Admin only)
+#[cfg_attr(feature = "swagger", utoipa::path(
+    get,
+    path = "/api/v1/admin/trash",
+    responses(
+        (status = 200, description = "Trashed items"),
+        (status = 401, description = "Unauthorized - Missing or invalid token"),
+        (status = 403, description = "Forbidden - Not an admin user")
+    ),
+    security(
+        ("google_oauth" = ["openid", "email", "profile"])
+    ),
+    tag = "admin"
+))]
+pub async fn list_trash(State(state): State<Arc<AdminState>>) -> ApiResult<Json<Vec<TrashItem>>> {
+    let projects = state.repositories.projects.list_trash().await?;
+    let certificates = state.repositories.certificates.list_trash().await?;
+
+    let mut items: Vec<TrashItem> = projects
+        .into_iter()
+        .map(|project| TrashItem {
+            kind: ContentKind::Project,
+            slug: project.slug,
+            title: project.title,
+            deleted_at: project.deleted_at,
+            deleted_by: project.deleted_by,
+        })
+        .chain(certificates.into_iter().map(|certificate| TrashItem {
+            kind: ContentKind::Certificate,
+            slug: certificate.slug,
+            title: certificate.name,
+            deleted_at: certificate.deleted_at,
+            deleted_by: certificate.deleted_by,
+        }))
+        .collect();
+    items.sort_by_key(|item| std::cmp::Reverse(item.deleted_at));
+
+    Ok(Json(items))
+}
+
+/// Restore a trashed project or certificate (Admin only)
+#[cfg_attr(feature = "swagger", utoipa::path(
+    post,
+    path = "/api/v1/admin/trash/{kind}/{slug}/restore",
+    params(
+        ("kind" = ContentKind, Path, description = "project or certificate"),
+        ("slug" = String, Path, description = "Slug of the trashed item")
+    ),
+    responses(
+        (status = 200, description = "Item restored"),
+        (status = 404, description = "No trashed item with this slug"),
+        (status = 401, description = "Unauthorized - Missing or invalid token"),
+        (status = 403, description = "Forbidden - Not an admin user")
+    ),
+    security(
+        ("google_oauth" = ["openid", "email", "profile"])
+    ),
+    tag = "admin"
+))]
+pub async fn restore_item(
+    State(state): State<Arc<AdminState>>,
+    Extension(user): Extension<UserInfo>,
+    Path((kind, slug)): Path<(ContentKind, String)>,
+) -> ApiResult<Json<Value>> {
+    let restored = match kind {
+        ContentKind::Project => state.repositories.projects.restore(&slug).await?,
+        ContentKind::Certificate => state.repositories.certificates.restore(&slug).await?,
+    };
+    if !restored {
+        return Err(ApiError::NotFound(format!(
+            "No trashed {} '{}'",
+            kind.as_str(),
+            slug
+        )));
+    }
+
+    tracing::info!("Admin {} restored {} '{}'", user.email, kind.as_str(), slug);
+    Ok(Json(json!({
+        "message": "Item restored",
+        "kind": kind,
+        "slug": slug
+    })))
+}
+
+/// Permanently delete items trashed more than N days ago (Admin only)
+#[cfg_attr(feature = "swagger", utoipa::path(
+    delete,
+    path = "/api/v1/admin/trash",
+    params(
+        ("older_than_days" = Option<i64>, Query, description = "Minimum age in the trash, in days (default 30)")
+    ),
+    responses(
+        (status = 200, description = "Number of purged items per kind"),
+        (status = 400, description = "Invalid age"),
+        (status = 401, description = "Unauthorized - Missing or invalid token"),
+        (status = 403, description = "Forbidden - Not an admin user")
+    ),
+    security(
+        ("google_oauth" = ["openid", "email", "profile"])
+    ),
+    tag = "admin"
+))]
+pub async fn purge_trash(
+    State(state): State<Arc<AdminState>>,
+    Extension(user): Extension<UserInfo>,
+    Query(params): Query<PurgeParams>,
+) -> ApiResult<Json<Value>> {
+    let days = params.older_than_days.unwrap_or(DEFAULT_RETENTION_DAYS);
+    if days < 0 {
+        return Err(ApiError::BadRequest(
+            "older_than_days must not be negative".to_string(),
+        ));
+    }
+
+    let cutoff = Utc::now() - Duration::days(days);
+    let projects = state.repositories.projects.purge_trash(cutoff).await?;
+    let certificates = state.repositories.certificates.purge_trash(cutoff).await?;
+
+    tracing::info!(
+        "Admin {} purged trash older than {} days: {} projects, {} certificates",
+        user.email,
+        days,
+        projects,
+        certificates
+    );
+    Ok(Json(json!({
+        "olderThanDays": days,
+        "projects": projects,
+        "certificates": certificates
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        api::test_support::{admin_app, body_json, test_auth},
+        repositories::{Repositories, VersionCheck},
+    };
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_trash_restore_and_purge() {
+        let (_, token) = test_auth();
+        let repositories = Repositories::in_memory();
+        let project = serde_json::from_value(json!({
+            "slug": "rust-api",
+            "date": "2024",
+            "title": "Rust API",
+            "description": null,
+            "technologies": ["Rust"],
+            "features": [],
+            "githubUrl": "https://github.com/example/rust-api",
+            "embedding": null,
+            "images": null
+        }))
+        .unwrap();
+        repositories.projects.create(project).await.unwrap();
+        repositories
+            .projects
+            .trash("rust-api", "admin@example.com", &VersionCheck::Any)
+            .await
+            .unwrap();
+        let app = admin_app(&repositories);
+
+        let request = |method: &str, uri: &str| {
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("Authorization", format!("Bearer {}", token))
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(request("GET", "/trash")).await.unwrap();
+        let trash = body_json(response).await;
+        assert_eq!(trash[0]["kind"], "project");
+        assert_eq!(trash[0]["deletedBy"], "admin@example.com");
+
+        // Recently trashed items survive the default retention
+        let response = app
+            .clone()
+            .oneshot(request("DELETE", "/trash"))
+            .await
+            .unwrap();
+        assert_eq!(body_json(response).await["projects"], 0);
+
+        let response = app
+            .clone()
+            .oneshot(request("POST", "/trash/project/rust-api/restore"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(repositories
+            .projects
+            .get_by_slug("rust-api")
+            .await
+            .unwrap()
+            .is_some());
+
+        let response = app
+            .clone()
+            .oneshot(request("POST", "/trash/project/rust-api/restore"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        repositories
+            .projects
+            .trash("rust-api", "admin@example.com", &VersionCheck::Any)
+            .await
+            .unwrap();
+        let response = app
+            .oneshot(request("DELETE", "/trash?older_than_days=0"))
+            .await
+            .unwrap();
+        assert_eq!(body_json(response).await["projects"], 1);
+        assert!(!repositories.projects.slug_in_use("rust-api").await.unwrap());
+    }
+}
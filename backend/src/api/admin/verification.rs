@@ -0,0 +1,315 @@
+use super::AdminState;
+use crate::{
+    auth::UserInfo,
+    error::{ApiError, ApiResult},
+};
+use axum::{
+    extract::{Path, State},
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::Arc;
+
+/// Outcome of checking a certificate against its issuer page
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "swagger", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationResult {
+    pub slug: String,
+    pub verified: bool,
+    /// Issuer matcher that read the page (`coursera`, `credly` or `generic`)
+    pub matcher: String,
+    pub checked_at: DateTime<Utc>,
+    pub verified_at: Option<DateTime<Utc>>,
+    /// What the page was missing when the certificate could not be verified
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Fetch the certificate's link and confirm it names the credential and its holder (Admin only)
+/// A page that cannot be loaded leaves the stored verification untouched
+#[cfg_attr(feature = "swagger", utoipa::path(
+    post,
+    path = "/api/v1/admin/certificates/{slug}/verify",
+    responses(
+        (status = 200, description = "Verification stored", body = VerificationResult),
+        (status = 401, description = "Unauthorized - Missing or invalid token"),
+        (status = 403, description = "Forbidden - Not an admin user"),
+        (status = 404, description = "Certificate not found"),
+        (status = 502, description = "Issuer page could not be loaded")
+    ),
+    security(
+        ("google_oauth" = ["openid", "email", "profile"])
+    ),
+    tag = "admin"
+))]
+pub async fn verify_certificate(
+    State(state): State<Arc<AdminState>>,
+    Extension(user): Extension<UserInfo>,
+    Path(slug): Path<String>,
+) -> ApiResult<Json<VerificationResult>> {
+    let certificate = state
+        .repositories
+        .certificates
+        .get_by_slug(&slug)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Certificate '{}' not found", slug)))?;
+
+    let verdict = state
+        .verifier
+        .verify(&certificate)
+        .await
+        .map_err(|e| ApiError::BadGateway(format!("Could not load the issuer page: {}", e)))?;
+
+    let checked_at = Utc::now();
+    let verified_at = match verdict.mismatch {
+        None => Some(checked_at),
+        Some(_) => None,
+    };
+    let stored = state
+        .repositories
+        .certificates
+        .set_verification(&slug, verified_at)
+        .await?;
+    if !stored {
+        return Err(ApiError::NotFound(format!(
+            "Certificate '{}' not found",
+            slug
+        )));
+    }
+
+    tracing::info!(
+        "Admin {} verified certificate {}: {}",
+        user.email,
+        slug,
+        verdict.mismatch.as_deref().unwrap_or("ok")
+    );
+    Ok(Json(VerificationResult {
+        slug,
+        verified: verified_at.is_some(),
+        matcher: verdict.matcher.to_string(),
+        checked_at,
+        verified_at,
+        reason: verdict.mismatch,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        api::{
+            certificates,
+            conditional::HttpCache,
+            test_support::{admin_app, body_json, test_auth, StubEmbedder},
+        },
+        repositories::Repositories,
+    };
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        response::Html,
+        routing::get,
+        Router,
+    };
+    use chrono::Utc;
+    use serde_json::json;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    /// Local server standing in for an issuer's verification pages
+    async fn issuer_site() -> String {
+        let app = Router::new()
+            .route(
+                "/verify/rust",
+                get(|| async {
+                    Html(include_str!(
+                        "../../services/verification/fixtures/generic.html"
+                    ))
+                }),
+            )
+            .route("/verify/gone", get(|| async { StatusCode::NOT_FOUND }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}", address)
+    }
+
+    #[tokio::test]
+    async fn test_verification_is_stored_on_the_certificate() {
+        let base = issuer_site().await;
+        let (auth, token) = test_auth();
+        let repositories = Repositories::in_memory();
+        for (name, slug, path) in [
+            ("Rust Fundamentals", "rust", "/verify/rust"),
+            ("Go Fundamentals", "go", "/verify/rust"),
+            ("Gone", "gone", "/verify/gone"),
+        ] {
+            let certificate = serde_json::from_value(json!({
+                "name": name,
+                "issuer": "Academy",
+                "link": format!("{}{}", base, path),
+                "slug": slug
+            }))
+            .unwrap();
+            repositories.certificates.create(certificate).await.unwrap();
+        }
+        let http_cache = Arc::new(HttpCache::default());
+        let app = admin_app(&repositories);
+        let public =
+            certificates::router(repositories.clone(), StubEmbedder::ok(), auth, http_cache);
+        let read = |if_none_match: Option<&str>| {
+            let mut request = Request::get("/rust");
+            if let Some(tag) = if_none_match {
+                request = request.header("If-None-Match", tag);
+            }
+            public.clone().oneshot(request.body(Body::empty()).unwrap())
+        };
+        let response = read(None).await.unwrap();
+        let read_tag = response.headers()["etag"].to_str().unwrap().to_string();
+        assert_eq!(body_json(response).await["verified"], false);
+
+        let verify = |slug: &str| {
+            Request::builder()
+                .method("POST")
+                .uri(format!("/certificates/{}/verify", slug))
+                .header("Authorization", format!("Bearer {}", token))
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(verify("rust")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let result = body_json(response).await;
+        assert_eq!(result["verified"], true);
+        assert_eq!(result["matcher"], "generic");
+        assert_eq!(result["verifiedAt"], result["checkedAt"]);
+        let stored = repositories
+            .certificates
+            .get_by_slug("rust")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(stored.verified);
+        assert!(stored.verified_at.is_some());
+
+        // Clients revalidating their unverified copy get the new one
+        let response = read(Some(&read_tag)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_ne!(response.headers()["etag"], read_tag.as_str());
+        assert_eq!(body_json(response).await["verified"], true);
+
+        let response = app.clone().oneshot(verify("go")).await.unwrap();
+        let result = body_json(response).await;
+        assert_eq!(result["verified"], false);
+        assert_eq!(
+            result["reason"],
+            "Certificate \"Go Fundamentals\" not found on the issuer page"
+        );
+
+        let response = app.clone().oneshot(verify("gone")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+
+        let response = app.oneshot(verify("missing")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_editing_what_was_verified_voids_the_verification() {
+        let (auth, token) = test_auth();
+        let repositories = Repositories::in_memory();
+        let certificate = serde_json::from_value(json!({
+            "name": "Rust Fundamentals",
+            "issuer": "Academy",
+            "link": "https://academy.example.com/verify/rust",
+            "slug": "rust"
+        }))
+        .unwrap();
+        repositories.certificates.create(certificate).await.unwrap();
+        let app = certificates::router(
+            repositories.clone(),
+            StubEmbedder::ok(),
+            auth,
+            Arc::new(HttpCache::default()),
+        );
+        let send = |method: &str, content_type: &str, body: serde_json::Value| {
+            let request = Request::builder()
+                .method(method)
+                .uri("/rust")
+                .header("Authorization", format!("Bearer {}", token))
+                .header("Content-Type", content_type)
+                .body(Body::from(body.to_string()))
+                .unwrap();
+            app.clone().oneshot(request)
+        };
+        let verified = || async {
+            repositories
+                .certificates
+                .get_by_slug("rust")
+                .await
+                .unwrap()
+                .unwrap()
+                .verified
+        };
+        let verify = || async {
+            repositories
+                .certificates
+                .set_verification("rust", Some(Utc::now()))
+                .await
+                .unwrap();
+        };
+        let replace = |name: &str, issuer: &str, link: &str| json!({ "name": name, "issuer": issuer, "link": link, "image_url": null });
+
+        verify().await;
+        let response = send(
+            "PATCH",
+            "application/merge-patch+json",
+            json!({ "issuer": "Rust Academy" }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(verified().await);
+        let response = send(
+            "PATCH",
+            "application/merge-patch+json",
+            json!({ "name": "Rust Advanced" }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!verified().await);
+
+        verify().await;
+        let link = "https://academy.example.com/verify/rust";
+        let response = send(
+            "PUT",
+            "application/json",
+            replace("Rust Advanced", "Academy", link),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(verified().await);
+        let response = send(
+            "PUT",
+            "application/json",
+            replace(
+                "Rust Advanced",
+                "Academy",
+                "https://academy.example.com/v/2",
+            ),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!verified().await);
+        let stored = repositories
+            .certificates
+            .get_by_slug("rust")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(stored.verified_at.is_none());
+    }
+}
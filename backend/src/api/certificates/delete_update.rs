@@ -1,20 +1,37 @@
-use crate::{auth::UserInfo, database::MongoClient, models::certificate::CertificateUpdate};
+use super::CertificatesState;
+use crate::{
+    api::{
+        chat::embed_content,
+        conditional::{etag, if_match},
+        patch::PatchFormat,
+    },
+    auth::UserInfo,
+    error::{ApiError, ApiResult},
+    models::{certificate::CertificateUpdate, ContentKind, Revision},
+    repositories::{FieldChanges, VersionCheck},
+};
 use axum::{
+    body::Bytes,
     extract::{Path, State},
-    http::StatusCode,
+    http::{header, HeaderMap},
+    response::{IntoResponse, Response},
     Extension, Json,
 };
 use serde_json::{json, Value};
 use std::sync::Arc;
 use validator::Validate;
 
-/// Delete certificate by slug (Admin only)
+/// Move certificate to the trash by slug (Admin only)
 #[cfg_attr(feature = "swagger", utoipa::path(
     delete,
     path = "/api/v1/certificates/{slug}",
+    params(
+        ("If-Match" = Option<String>, Header, description = "ETag from the last read, the delete fails with 412 if the certificate changed since")
+    ),
     responses(
-        (status = 200, description = "Certificate deleted successfully"),
+        (status = 200, description = "Certificate moved to the trash"),
         (status = 404, description = "Certificate not found"),
+        (status = 412, description = "Certificate was modified since the If-Match version"),
         (status = 401, description = "Unauthorized - Missing or invalid token"),
         (status = 403, description = "Forbidden - Not an admin user"),
         (status = 500, description = "Internal server error")
@@ -25,32 +42,41 @@ use validator::Validate;
     tag = "certificates"
 ))]
 pub async fn delete_certificate(
-    State(db): State<Arc<MongoClient>>,
+    State(state): State<Arc<CertificatesState>>,
     Extension(user): Extension<UserInfo>,
     Path(slug): Path<String>,
-) -> Result<Json<Value>, StatusCode> {
+    headers: HeaderMap,
+) -> ApiResult<Json<Value>> {
     tracing::info!("Admin {} deleting certificate: {}", user.email, slug);
-    match db.delete_by_slug("certificates", &slug).await {
-        Ok(true) => {
-            tracing::info!("Certificate '{}' deleted by {}", slug, user.email);
-            Ok(Json(json!({"message": "Certificate deleted successfully"})))
-        }
-        Ok(false) => Err(StatusCode::NOT_FOUND),
-        Err(e) => {
-            tracing::error!("Failed to delete certificate: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
+
+    if !state
+        .repo
+        .trash(&slug, &user.email, &if_match(&headers))
+        .await?
+    {
+        return Err(ApiError::NotFound(format!(
+            "Certificate '{}' not found",
+            slug
+        )));
     }
+
+    tracing::info!("Certificate '{}' moved to trash by {}", slug, user.email);
+    Ok(Json(json!({"message": "Certificate moved to trash"})))
 }
 
-/// Update certificate by slug (Admin only)
+/// Update certificate by slug, keeping the previous version as a revision (Admin only)
 #[cfg_attr(feature = "swagger", utoipa::path(
     put,
     path = "/api/v1/certificates/{slug}",
     request_body = CertificateUpdate,
+    params(
+        ("If-Match" = Option<String>, Header, description = "ETag from the last read, the update fails with 412 if the certificate changed since")
+    ),
     responses(
-        (status = 200, description = "Certificate updated successfully"),
+        (status = 200, description = "Certificate updated successfully",
+            headers(("ETag" = String, description = "New version of the certificate"))),
         (status = 404, description = "Certificate not found"),
+        (status = 412, description = "Certificate was modified since the If-Match version"),
         (status = 400, description = "Invalid request body"),
         (status = 401, description = "Unauthorized - Missing or invalid token"),
         (status = 403, description = "Forbidden - Not an admin user"),
@@ -62,34 +88,185 @@ pub async fn delete_certificate(
     tag = "certificates"
 ))]
 pub async fn update_certificate(
-    State(db): State<Arc<MongoClient>>,
+    State(state): State<Arc<CertificatesState>>,
     Extension(user): Extension<UserInfo>,
     Path(slug): Path<String>,
+    headers: HeaderMap,
     Json(certificate): Json<CertificateUpdate>,
-) -> Result<Json<Value>, StatusCode> {
+) -> ApiResult<Response> {
     tracing::info!("Admin {} updating certificate: {}", user.email, slug);
 
     // Validate input
     certificate.validate().map_err(|e| {
         tracing::warn!("Validation failed for certificate update: {}", e);
-        StatusCode::BAD_REQUEST
+        ApiError::BadRequest(e.to_string())
     })?;
 
-    let update_doc =
-        mongodb::bson::to_document(&certificate).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let embedding_text = certificate.embedding_text();
+
+    let before = state
+        .repo
+        .update(&slug, certificate, &if_match(&headers))
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Certificate '{}' not found", slug)))?;
+    state
+        .revisions
+        .record(Revision::capture(
+            ContentKind::Certificate,
+            &before,
+            &user.email,
+        )?)
+        .await?;
 
-    match db.update_by_slug("certificates", &slug, update_doc).await {
-        Ok(true) => {
-            tracing::info!("Certificate '{}' updated by {}", slug, user.email);
-            Ok(Json(json!({
-                "message": "Certificate updated successfully",
-                "slug": slug
-            })))
+    let (embedding, embedding_outcome) =
+        embed_content(state.embedder.as_ref(), &embedding_text).await;
+    match embedding {
+        Some(embedding) => {
+            state
+                .repo
+                .set_embedding(&slug, embedding.values, &embedding.model)
+                .await?;
         }
-        Ok(false) => Err(StatusCode::NOT_FOUND),
-        Err(e) => {
-            tracing::error!("Failed to update certificate: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        None => {
+            state.repo.clear_embedding(&slug).await?;
         }
     }
+
+    tracing::info!("Certificate '{}' updated by {}", slug, user.email);
+    Ok((
+        [(header::ETAG, etag(before.id, before.version + 1))],
+        Json(json!({
+            "message": "Certificate updated successfully",
+            "slug": slug,
+            "embedding": embedding_outcome
+        })),
+    )
+        .into_response())
+}
+
+/// Partially update certificate by slug with a merge patch or JSON Patch (Admin only)
+/// Only the changed fields are written; without If-Match the patch still only
+/// applies to the version it was validated against
+#[cfg_attr(feature = "swagger", utoipa::path(
+    patch,
+    path = "/api/v1/certificates/{slug}",
+    request_body(
+        description = "RFC 7386 merge patch or RFC 6902 JSON Patch of the CertificateUpdate fields",
+        content(
+            (Object = "application/merge-patch+json"),
+            (Object = "application/json-patch+json")
+        )
+    ),
+    params(
+        ("If-Match" = Option<String>, Header, description = "ETag from the last read, the patch fails with 412 if the certificate changed since")
+    ),
+    responses(
+        (status = 200, description = "Certificate patched",
+            headers(("ETag" = String, description = "New version of the certificate"))),
+        (status = 400, description = "Malformed patch document"),
+        (status = 404, description = "Certificate not found"),
+        (status = 409, description = "A JSON Patch test operation failed"),
+        (status = 412, description = "Certificate was modified since the If-Match version"),
+        (status = 415, description = "Content-Type is not a supported patch format"),
+        (status = 422, description = "Patched certificate fails validation"),
+        (status = 401, description = "Unauthorized - Missing or invalid token"),
+        (status = 403, description = "Forbidden - Not an admin user"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("google_oauth" = ["openid", "email", "profile"])
+    ),
+    tag = "certificates"
+))]
+pub async fn patch_certificate(
+    State(state): State<Arc<CertificatesState>>,
+    Extension(user): Extension<UserInfo>,
+    Path(slug): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ApiResult<Response> {
+    tracing::info!("Admin {} patching certificate: {}", user.email, slug);
+
+    let format = PatchFormat::from_headers(&headers)?;
+    let current = state
+        .repo
+        .get_by_slug(&slug)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Certificate '{}' not found", slug)))?;
+    let check = match if_match(&headers) {
+        VersionCheck::Any => VersionCheck::OneOf(
+            current
+                .id
+                .map(|id| (id, current.version))
+                .into_iter()
+                .collect(),
+        ),
+        check => check,
+    };
+
+    let original = CertificateUpdate::from(current.clone());
+    let update = format.apply(&original, &body).inspect_err(|e| {
+        tracing::warn!("Rejected patch of certificate '{}': {}", slug, e);
+    })?;
+    let changes = FieldChanges::between(&original, &update)?;
+    if changes.is_empty() {
+        return Ok((
+            [(header::ETAG, etag(current.id, current.version))],
+            Json(json!({
+                "message": "Certificate unchanged",
+                "slug": slug,
+                "embedding": null
+            })),
+        )
+            .into_response());
+    }
+
+    let before = state
+        .repo
+        .patch(&slug, &changes, &check)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Certificate '{}' not found", slug)))?;
+    state
+        .revisions
+        .record(Revision::capture(
+            ContentKind::Certificate,
+            &before,
+            &user.email,
+        )?)
+        .await?;
+
+    // Re-embed only when the embedded text changed
+    let mut embedding_outcome = None;
+    if update.embedding_text() != original.embedding_text() {
+        let (embedding, outcome) =
+            embed_content(state.embedder.as_ref(), &update.embedding_text()).await;
+        match embedding {
+            Some(embedding) => {
+                state
+                    .repo
+                    .set_embedding(&slug, embedding.values, &embedding.model)
+                    .await?;
+            }
+            None => {
+                state.repo.clear_embedding(&slug).await?;
+            }
+        }
+        embedding_outcome = Some(outcome);
+    }
+
+    tracing::info!(
+        "Certificate '{}' patched by {}: {:?}",
+        slug,
+        user.email,
+        changes.paths()
+    );
+    Ok((
+        [(header::ETAG, etag(before.id, before.version + 1))],
+        Json(json!({
+            "message": "Certificate patched successfully",
+            "slug": slug,
+            "embedding": embedding_outcome
+        })),
+    )
+        .into_response())
 }
@@ -1,94 +1,120 @@
-use crate::{database::MongoClient, models::Certificate};
+use super::CertificatesState;
+use crate::{
+    api::{
+        chat::embed_content,
+        listing::{ListParams, ListResponse},
+    },
+    auth::middleware::admin_user,
+    error::{ApiError, ApiResult},
+    models::{Certificate, CertificateResponse, ContentKind},
+};
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::Response,
     Json,
 };
-use mongodb::bson::{doc};
+use chrono::Utc;
 use serde_json::{json, Value};
 use std::sync::Arc;
 use validator::Validate;
 
-/// List all certificates
+/// List certificates with filtering, sorting and cursor pagination
 #[cfg_attr(feature = "swagger", utoipa::path(
     get,
     path = "/api/v1/certificates",
+    params(ListParams),
     responses(
-        (status = 200, description = "List of certificates retrieved successfully"),
+        (status = 200, description = "Page of certificates (`{ items, total, nextCursor }`, or a bare array with `format=array`)", body = Vec<CertificateResponse>,
+            headers(
+                ("ETag" = String, description = "Changes whenever a listed certificate or the pagination changes"),
+                ("Last-Modified" = String, description = "When the server first served this ETag"),
+                ("Cache-Control" = String, description = "Configured with HTTP_CACHE_CONTROL")
+            )),
+        (status = 304, description = "Not modified since the If-None-Match / If-Modified-Since validators"),
+        (status = 400, description = "Invalid query parameters"),
         (status = 500, description = "Internal server error")
     ),
     tag = "certificates"
 ))]
 pub async fn list_certificates(
-    State(db): State<Arc<MongoClient>>,
-) -> Result<Json<Value>, StatusCode> {
-    let collection = db.certificates();
-
-    let cursor_result = collection.find(doc! {}).await;
-
-    match cursor_result {
-        Ok(mut cursor) => {
-            let mut certificates = Vec::new();
-            let mut success_count = 0;
-            let mut error_count = 0;
-
-            use futures::stream::StreamExt;
-            while let Some(result) = cursor.next().await {
-                match result {
-                    Ok(doc) => {
-                        certificates.push(doc);
-                        success_count += 1;
-                    }
-                    Err(e) => {
-                        error_count += 1;
-                        tracing::warn!("Failed to deserialize certificate {}: {}", error_count, e);
-                    }
-                }
-            }
-
-            tracing::info!(
-                "Certificates retrieval: {} successful, {} failed",
-                success_count,
-                error_count
-            );
+    State(state): State<Arc<CertificatesState>>,
+    Query(params): Query<ListParams>,
+    headers: HeaderMap,
+) -> ApiResult<Response> {
+    let (query, format) = params.into_query(ContentKind::Certificate)?;
 
-            // Return direct array for consistency with frontend expectations
-            Ok(Json(json!(certificates)))
-        }
-        Err(error) => {
-            tracing::error!("Failed to fetch certificates: {}", error);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
+    // Statuses follow the date, so lists also change with the day
+    let page = state.repo.find(&query).await?;
+    let validators = state
+        .http_cache
+        .list_validators(
+            ContentKind::Certificate,
+            page.items
+                .iter()
+                .map(|certificate| (certificate.id, certificate.version)),
+            page.total,
+            page.next_offset,
+        )
+        .dated(Utc::now().date_naive());
+    if let Some(not_modified) = state.http_cache.not_modified(&headers, &validators) {
+        return Ok(not_modified);
     }
+
+    let list = ListResponse::new(page.map(CertificateResponse::from), format);
+    Ok(state.http_cache.fresh(&validators, Json(list)))
 }
 
 /// Get single certificate by slug
+/// Admins may preview unpublished certificates by sending their token
 #[cfg_attr(feature = "swagger", utoipa::path(
     get,
     path = "/api/v1/certificates/{slug}",
     responses(
-        (status = 200, description = "Certificate retrieved successfully"),
-        (status = 404, description = "Certificate not found")
+        (status = 200, description = "Certificate retrieved successfully", body = CertificateResponse,
+            headers(
+                ("ETag" = String, description = "Version of the certificate, send it back as If-Match"),
+                ("Last-Modified" = String, description = "When the server first served this ETag"),
+                ("Cache-Control" = String, description = "Configured with HTTP_CACHE_CONTROL")
+            )),
+        (status = 304, description = "Not modified since the If-None-Match / If-Modified-Since validators"),
+        (status = 404, description = "Certificate not found or not published")
     ),
     tag = "certificates"
 ))]
 pub async fn get_certificate(
-    State(db): State<Arc<MongoClient>>,
+    State(state): State<Arc<CertificatesState>>,
     Path(slug): Path<String>,
-) -> Result<Json<Value>, StatusCode> {
-    let collection = db.certificates();
+    headers: HeaderMap,
+) -> ApiResult<Response> {
+    let certificate = state
+        .repo
+        .get_by_slug(&slug)
+        .await?
+        .filter(|certificate| {
+            certificate.is_published() || admin_user(&state.auth_config, &headers).is_some()
+        })
+        .ok_or_else(|| ApiError::NotFound(format!("Certificate '{}' not found", slug)))?;
 
-    match collection.find_one(doc! { "slug": slug }).await {
-        Ok(Some(certificate)) => Ok(Json(json!(certificate))),
-        Ok(None) => Err(StatusCode::NOT_FOUND),
-        Err(error) => {
-            tracing::error!("Database error: {}", error);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
+    let validators = state
+        .http_cache
+        .document_validators(certificate.id, certificate.version)
+        .dated(Utc::now().date_naive());
+    if !certificate.is_published() {
+        return Ok(state
+            .http_cache
+            .private(&validators, Json(CertificateResponse::from(certificate))));
+    }
+    if let Some(not_modified) = state.http_cache.not_modified(&headers, &validators) {
+        return Ok(not_modified);
     }
+    Ok(state
+        .http_cache
+        .fresh(&validators, Json(CertificateResponse::from(certificate))))
 }
 
 /// Create new certificate (Admin only)
+/// Goes live at once unless `publication` is `draft`, or `scheduled` with a `publish_at`
 #[cfg_attr(feature = "swagger", utoipa::path(
     post,
     path = "/api/v1/certificates",
@@ -96,7 +122,8 @@ pub async fn get_certificate(
         (status = 201, description = "Certificate created successfully"),
         (status = 400, description = "Invalid request body"),
         (status = 401, description = "Unauthorized - Missing or invalid token"),
-        (status = 403, description = "Forbidden - Not an admin user")
+        (status = 403, description = "Forbidden - Not an admin user"),
+        (status = 409, description = "Slug already in use")
     ),
     security(
         ("google_oauth" = ["openid", "email", "profile"])
@@ -104,39 +131,42 @@ pub async fn get_certificate(
     tag = "certificates"
 ))]
 pub async fn create_certificate(
-    State(db): State<Arc<MongoClient>>,
+    State(state): State<Arc<CertificatesState>>,
     Json(mut certificate): Json<Certificate>,
-) -> Result<(StatusCode, Json<Value>), StatusCode> {
+) -> ApiResult<(StatusCode, Json<Value>)> {
     // Validate input
     certificate.validate().map_err(|e| {
         tracing::warn!("Validation failed for certificate creation: {}", e);
-        StatusCode::BAD_REQUEST
+        ApiError::BadRequest(e.to_string())
     })?;
 
-    let collection = db.certificates();
-
-    certificate.slug = Certificate::generate_slug(&certificate.name);
+    certificate.slug = state
+        .slugs
+        .generate(ContentKind::Certificate, &certificate.name)
+        .await?;
 
-    let doc = mongodb::bson::to_document(&certificate).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let (embedding, embedding_outcome) =
+        embed_content(state.embedder.as_ref(), &certificate.embedding_text()).await;
+    match embedding {
+        Some(embedding) => certificate.set_embedding(embedding.values, embedding.model),
+        None => certificate.clear_embedding(),
+    }
 
-    match collection.insert_one(doc).await {
-        Ok(result) => {
-            let inserted_id = result
-                .inserted_id
-                .as_object_id()
-                .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+    // Versions, trash state and verification are server-managed, the client cannot set them
+    certificate.version = 0;
+    certificate.deleted_at = None;
+    certificate.deleted_by = None;
+    certificate.set_verification(None);
+    let slug = certificate.slug.clone();
+    let inserted_id = state.repo.create(certificate).await?;
 
-            Ok((
-                StatusCode::CREATED,
-                Json(json!({
-                    "id": inserted_id.to_hex(),
-                    "message": "Certificate created successfully"
-                })),
-            ))
-        }
-        Err(error) => {
-            tracing::error!("Failed to create certificate: {}", error);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
-    }
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "id": inserted_id.to_hex(),
+            "slug": slug,
+            "message": "Certificate created successfully",
+            "embedding": embedding_outcome
+        })),
+    ))
 }
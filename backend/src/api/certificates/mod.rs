@@ -1,17 +1,48 @@
 pub mod delete_update;
 pub mod handlers;
 
-use crate::{auth::AuthConfig, database::MongoClient};
+use crate::{
+    api::{chat::Embedder, conditional::HttpCache},
+    auth::AuthConfig,
+    repositories::{CertificateRepository, Repositories, RevisionRepository},
+    services::SlugService,
+};
 use axum::{
     middleware,
-    routing::{delete, get, post, put},
+    routing::{delete, get, patch, post, put},
     Router,
 };
 use std::sync::Arc;
 
+/// Shared state for certificate handlers
+pub struct CertificatesState {
+    pub repo: Arc<dyn CertificateRepository>,
+    pub embedder: Arc<dyn Embedder>,
+    pub slugs: SlugService,
+    pub revisions: Arc<dyn RevisionRepository>,
+    pub http_cache: Arc<HttpCache>,
+    /// Recognizes admins previewing unpublished content on the public routes
+    pub auth_config: Arc<AuthConfig>,
+}
+
 /// Build certificates router with CRUD endpoints
 /// POST operations require admin authentication
-pub fn router(db_client: Arc<MongoClient>, auth_config: Arc<AuthConfig>) -> Router {
+/// Writes embed the certificate content for RAG retrieval
+pub fn router(
+    repositories: Repositories,
+    embedder: Arc<dyn Embedder>,
+    auth_config: Arc<AuthConfig>,
+    http_cache: Arc<HttpCache>,
+) -> Router {
+    let state = Arc::new(CertificatesState {
+        repo: repositories.certificates.clone(),
+        embedder,
+        revisions: repositories.revisions.clone(),
+        http_cache,
+        auth_config: auth_config.clone(),
+        slugs: SlugService::new(repositories),
+    });
+
     Router::new()
         .route("/", get(handlers::list_certificates))
         .route(
@@ -32,9 +63,251 @@ pub fn router(db_client: Arc<MongoClient>, auth_config: Arc<AuthConfig>) -> Rout
         .route(
             "/{slug}",
             put(delete_update::update_certificate).layer(middleware::from_fn_with_state(
+                auth_config.clone(),
+                crate::auth::middleware::require_admin,
+            )),
+        )
+        .route(
+            "/{slug}",
+            patch(delete_update::patch_certificate).layer(middleware::from_fn_with_state(
                 auth_config,
                 crate::auth::middleware::require_admin,
             )),
         )
-        .with_state(db_client)
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::test_support::{body_json, test_auth, StubEmbedder},
+        repositories::InMemoryCertificateRepository,
+    };
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use chrono::Utc;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    fn test_router(repo: Arc<InMemoryCertificateRepository>, fail_embedding: bool) -> Router {
+        let (auth, _) = test_auth();
+        let embedder = if fail_embedding {
+            StubEmbedder::failing()
+        } else {
+            StubEmbedder::ok()
+        };
+        let repositories = Repositories {
+            certificates: repo,
+            ..Repositories::in_memory()
+        };
+        router(repositories, embedder, auth, Arc::new(HttpCache::default()))
+    }
+
+    fn sample_certificate() -> Value {
+        json!({
+            "name": "Rust Fundamentals",
+            "issuer": "Coursera",
+            "link": "https://coursera.org/verify/rust",
+            "slug": "rust-fundamentals",
+            "issue_date": "May 2024",
+            "image_url": "https://example.com/rust.png",
+            "embedding": [0.1, 0.2]
+        })
+    }
+
+    #[tokio::test]
+    async fn test_create_and_read_certificate_without_mongodb() {
+        let (_, token) = test_auth();
+        let repo = Arc::new(InMemoryCertificateRepository::default());
+        let app = test_router(repo.clone(), false);
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::post("/")
+                    .header("Authorization", format!("Bearer {}", token))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(sample_certificate().to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(
+            body_json(response).await["embedding"]["status"],
+            "generated"
+        );
+        let stored = repo
+            .get_by_slug("rust-fundamentals")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.embedding, Some(vec![0.5; 4]));
+
+        let response = app
+            .clone()
+            .oneshot(Request::get("/").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let page = body_json(response).await;
+        assert_eq!(page["total"], 1);
+
+        // Public DTO: hex string id and the field names the sites read, no embedding
+        let certificate = &page["items"][0];
+        assert!(certificate["_id"].as_str().is_some_and(|id| id.len() == 24));
+        assert_eq!(certificate["issue_date"], "May 2024");
+        assert_eq!(certificate["image_url"], "https://example.com/rust.png");
+        assert_eq!(certificate["status"], "active");
+        assert!(certificate.get("embedding").is_none());
+
+        let response = app
+            .oneshot(Request::get("/missing").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_stale_if_match_is_rejected() {
+        let (_, token) = test_auth();
+        let repo = Arc::new(InMemoryCertificateRepository::default());
+        let app = test_router(repo.clone(), false);
+
+        let send = |method: &str, if_match: Option<&str>| {
+            let mut request = Request::builder()
+                .method(method)
+                .uri("/rust-fundamentals")
+                .header("Authorization", format!("Bearer {}", token))
+                .header("Content-Type", "application/json");
+            if let Some(tag) = if_match {
+                request = request.header("If-Match", tag);
+            }
+            let body = match method {
+                "PUT" => Body::from(sample_certificate().to_string()),
+                _ => Body::empty(),
+            };
+            app.clone().oneshot(request.body(body).unwrap())
+        };
+
+        let id = repo
+            .create(serde_json::from_value(sample_certificate()).unwrap())
+            .await
+            .unwrap();
+        let tag = |version: i64| format!("\"{}-{}\"", id.to_hex(), version);
+
+        // Reads carry the day in their tag, preconditions only compare id and version
+        let response = send("GET", None).await.unwrap();
+        let read_tag = response.headers()["etag"].to_str().unwrap().to_string();
+        let today = Utc::now().format("%Y%m%d").to_string();
+        assert_eq!(read_tag, format!("\"{}-0-{}\"", id.to_hex(), today));
+
+        let response = send("PUT", Some(&read_tag)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["etag"], tag(1).as_str());
+        let response = send("PUT", Some(&read_tag)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+        let response = send("DELETE", Some(&tag(0))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+
+        let response = send("DELETE", Some(&tag(1))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = send("PUT", Some(&tag(2))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_conditional_get_until_a_write() {
+        let (_, token) = test_auth();
+        let repo = Arc::new(InMemoryCertificateRepository::default());
+        let app = test_router(repo.clone(), false);
+        repo.create(serde_json::from_value(sample_certificate()).unwrap())
+            .await
+            .unwrap();
+
+        let get = |uri: &str, if_none_match: Option<&str>| {
+            let mut request = Request::get(uri);
+            if let Some(tag) = if_none_match {
+                request = request.header("If-None-Match", tag);
+            }
+            app.clone().oneshot(request.body(Body::empty()).unwrap())
+        };
+
+        let response = get("/", None).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().contains_key("last-modified"));
+        let list_tag = response.headers()["etag"].to_str().unwrap().to_string();
+        let response = get("/rust-fundamentals", None).await.unwrap();
+        let read_tag = response.headers()["etag"].to_str().unwrap().to_string();
+
+        let response = get("/", Some(&list_tag)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        let response = get("/rust-fundamentals", Some(&read_tag)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::patch("/rust-fundamentals")
+                    .header("Authorization", format!("Bearer {}", token))
+                    .header("Content-Type", "application/merge-patch+json")
+                    .body(Body::from(json!({ "issuer": "DeepLearning" }).to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = get("/", Some(&list_tag)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = get("/rust-fundamentals", Some(&read_tag)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_json(response).await["issuer"], "DeepLearning");
+    }
+
+    #[tokio::test]
+    async fn test_create_certificate_requires_admin_token() {
+        let app = test_router(Arc::new(InMemoryCertificateRepository::default()), false);
+
+        let response = app
+            .oneshot(
+                Request::post("/")
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(sample_certificate().to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_failed_embedding_is_reported_not_dropped() {
+        let (_, token) = test_auth();
+        let repo = Arc::new(InMemoryCertificateRepository::default());
+        let app = test_router(repo.clone(), true);
+
+        let response = app
+            .oneshot(
+                Request::post("/")
+                    .header("Authorization", format!("Bearer {}", token))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(sample_certificate().to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(body_json(response).await["embedding"]["status"], "failed");
+
+        let stored = repo
+            .get_by_slug("rust-fundamentals")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(stored.embedding.is_none());
+    }
 }
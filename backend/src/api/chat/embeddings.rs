@@ -1,6 +1,7 @@
+use anyhow::Result;
+use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use anyhow::Result;
 
 #[derive(Debug, Serialize)]
 struct EmbeddingRequest {
@@ -28,33 +29,113 @@ struct Embedding {
     values: Vec<f64>,
 }
 
+/// Embedding model used when EMBEDDING_MODEL is not configured
+pub const DEFAULT_EMBEDDING_MODEL: &str = "text-embedding-004";
+/// Vector size of `DEFAULT_EMBEDDING_MODEL`, used for the vector search index definitions
+pub const DEFAULT_EMBEDDING_DIMENSIONS: u32 = 768;
+
 /// Generate embedding vector for query
-pub async fn generate_embedding(
-    api_key: &str,
-    text: &str,
-) -> Result<Vec<f64>> {
+pub async fn generate_embedding(api_key: &str, model: &str, text: &str) -> Result<Vec<f64>> {
     let client = Client::new();
     let url = format!(
-        "https://generativelanguage.googleapis.com/v1beta/models/text-embedding-004:embedContent?key={}",
-        api_key
+        "https://generativelanguage.googleapis.com/v1beta/models/{}:embedContent?key={}",
+        model, api_key
     );
-    
+
     let request_body = EmbeddingRequest {
-        model: "models/text-embedding-004".to_string(),
+        model: format!("models/{}", model),
         content: Content {
             parts: vec![Part {
                 text: text.to_string(),
             }],
         },
     };
-    
-    let response = client
-        .post(&url)
-        .json(&request_body)
-        .send()
-        .await?;
-    
+
+    let response = client.post(&url).json(&request_body).send().await?;
+
     let embedding_response: EmbeddingResponse = response.json().await?;
-    
+
     Ok(embedding_response.embedding.values)
 }
+
+/// Source of embedding vectors for stored content and chat queries
+#[async_trait]
+pub trait Embedder: Send + Sync {
+    /// Name of the model producing the vectors, recorded next to each stored embedding
+    fn model(&self) -> &str;
+
+    async fn embed(&self, text: &str) -> Result<Vec<f64>>;
+}
+
+/// Embedder backed by the Gemini embedding API
+pub struct GeminiEmbedder {
+    api_key: String,
+    model: String,
+}
+
+impl GeminiEmbedder {
+    pub fn new(api_key: String, model: String) -> Self {
+        Self { api_key, model }
+    }
+}
+
+#[async_trait]
+impl Embedder for GeminiEmbedder {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn embed(&self, text: &str) -> Result<Vec<f64>> {
+        generate_embedding(&self.api_key, &self.model, text).await
+    }
+}
+
+/// Embedding vector together with the model that produced it
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedEmbedding {
+    pub values: Vec<f64>,
+    pub model: String,
+}
+
+/// Result of embedding a document on write, reported back to the caller
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum EmbeddingOutcome {
+    Generated { model: String, dimensions: usize },
+    Failed { error: String },
+}
+
+/// Embed canonical content text, returning the vector to store (if any) and the outcome
+pub async fn embed_content(
+    embedder: &dyn Embedder,
+    text: &str,
+) -> (Option<GeneratedEmbedding>, EmbeddingOutcome) {
+    match embedder.embed(text).await {
+        Ok(values) if !values.is_empty() => {
+            let outcome = EmbeddingOutcome::Generated {
+                model: embedder.model().to_string(),
+                dimensions: values.len(),
+            };
+            let embedding = GeneratedEmbedding {
+                values,
+                model: embedder.model().to_string(),
+            };
+            (Some(embedding), outcome)
+        }
+        Ok(_) => (
+            None,
+            EmbeddingOutcome::Failed {
+                error: "Embedding API returned an empty vector".to_string(),
+            },
+        ),
+        Err(e) => {
+            tracing::warn!("Embedding generation failed: {}", e);
+            (
+                None,
+                EmbeddingOutcome::Failed {
+                    error: e.to_string(),
+                },
+            )
+        }
+    }
+}
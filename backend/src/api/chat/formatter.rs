@@ -1,5 +1,20 @@
+use crate::models::{CertificateStatus, ContentDate};
+use chrono::{NaiveDate, Utc};
 use mongodb::bson::Document;
 
+/// Date stored in `field` as shown on the site, text from before the date migration is parsed too
+fn date_label(doc: &Document, field: &str) -> Option<String> {
+    let value = doc.get(field)?.clone();
+    mongodb::bson::from_bson::<ContentDate>(value)
+        .ok()
+        .map(|date| date.to_string())
+}
+
+/// Expiry day of a certificate document, if it has one
+fn expiry(doc: &Document) -> Option<NaiveDate> {
+    doc.get_str("expires_at").ok()?.parse().ok()
+}
+
 /// Format projects into storytelling-friendly context
 /// Presents data in a way that encourages narrative responses
 pub fn format_projects(docs: Vec<Document>) -> String {
@@ -17,7 +32,7 @@ pub fn format_projects(docs: Vec<Document>) -> String {
             project.push(format!("**{}**", title));
 
             // Timeline context
-            if let Ok(date) = doc.get_str("date") {
+            if let Some(date) = date_label(&doc, "date") {
                 project.push(format!("*Built: {}*", date));
             }
 
@@ -96,10 +111,22 @@ pub fn format_certificates(docs: Vec<Document>) -> String {
             }
 
             // When this learning journey happened
-            if let Ok(date) = doc.get_str("issue_date") {
+            if let Some(date) = date_label(&doc, "issue_date") {
                 cert.push(format!("Completed: {}", date));
             }
 
+            // Expired credentials are past learning, not something currently held
+            if let Some(expires_at) = expiry(&doc) {
+                let label = expires_at.format("%-d %B %Y");
+                match CertificateStatus::on(Some(expires_at), Utc::now().date_naive()) {
+                    CertificateStatus::Expired => cert.push(format!(
+                        "Expired: {} (no longer current, mention it as past learning)",
+                        label
+                    )),
+                    _ => cert.push(format!("Valid until: {}", label)),
+                }
+            }
+
             // Verification link
             if let Ok(link) = doc.get_str("link") {
                 cert.push(format!("Verify: {}", link));
@@ -152,4 +179,31 @@ mod tests {
         assert!(result.contains("**AI Certificate**"));
         assert!(result.contains("From: Coursera"));
     }
+
+    #[test]
+    fn test_format_expired_certificate_as_past() {
+        let expired = doc! {
+            "name": "Cloud Associate",
+            "issuer": "AWS",
+            "expires_at": "2001-06-30"
+        };
+        let current = doc! {
+            "name": "Kubernetes Admin",
+            "issuer": "CNCF",
+            "expires_at": "2999-01-31"
+        };
+        let result = format_certificates(vec![expired, current]);
+        assert!(result.contains("Expired: 30 June 2001 (no longer current"));
+        assert!(result.contains("Valid until: 31 January 2999"));
+    }
+
+    #[test]
+    fn test_format_structured_date_range() {
+        let doc = doc! {
+            "title": "Portfolio API",
+            "date": { "start": { "year": 2023, "month": 3 }, "ongoing": true }
+        };
+        let result = format_projects(vec![doc]);
+        assert!(result.contains("Built: March 2023 – Present"));
+    }
 }
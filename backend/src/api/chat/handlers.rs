@@ -1,20 +1,30 @@
 use super::{
     build_system_prompt, client::GeminiClient, config::PortfolioOwner, format_certificates,
-    format_projects, generate_embedding, keyword_search, vector_search,
+    format_projects, keyword_search, keyword_terms, vector_search, Embedder,
 };
 use crate::{
-    database::MongoClient,
-    models::{ChatMessage, ChatRequest, ChatResponse},
+    database::{
+        migrations::{CERTIFICATES_VECTOR_INDEX, PROJECTS_VECTOR_INDEX},
+        MongoClient,
+    },
+    models::{ChatMessage, ChatRequest, ChatResponse, ContentKind},
+    repositories::ReadCache,
 };
 use axum::{extract::State, http::StatusCode, Json};
-use std::sync::Arc;
+use mongodb::{bson::Document, Collection};
+use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    sync::Arc,
+};
 
 /// RAG state containing DB, Gemini clients, and portfolio owner config
 pub struct RagState {
     pub db_client: Arc<MongoClient>,
     pub gemini_client: Arc<GeminiClient>,
-    pub api_key: String,
+    pub embedder: Arc<dyn Embedder>,
     pub portfolio_owner: PortfolioOwner,
+    /// Shared with the content repositories, so writes invalidate cached candidates
+    pub read_cache: Arc<ReadCache>,
 }
 
 /// Handle chat request with RAG (Retrieval-Augmented Generation)
@@ -37,7 +47,8 @@ pub async fn chat_handler(
     );
 
     // Step 1: Generate embedding for user query
-    let query_embedding = match generate_embedding(&rag_state.api_key, &request.messages).await {
+    // Same embedder as stored content so query and document vectors share a model
+    let query_embedding = match rag_state.embedder.embed(&request.messages).await {
         Ok(emb) => emb,
         Err(e) => {
             tracing::warn!("Embedding generation failed: {}, using direct chat", e);
@@ -46,10 +57,11 @@ pub async fn chat_handler(
     };
 
     // Step 2: Vector search for projects
-    let projects_docs = match vector_search(
-        &rag_state.db_client.projects(),
-        query_embedding.clone(),
-        "projects_index",
+    let projects_docs = match cached_vector_search(
+        &rag_state,
+        ContentKind::Project,
+        &query_embedding,
+        PROJECTS_VECTOR_INDEX,
         3,
     )
     .await
@@ -57,8 +69,9 @@ pub async fn chat_handler(
         Ok(docs) if !docs.is_empty() => docs,
         _ => {
             tracing::info!("Vector search failed, using keyword fallback for projects");
-            keyword_search(
-                &rag_state.db_client.projects(),
+            cached_keyword_search(
+                &rag_state,
+                ContentKind::Project,
                 &request.messages,
                 vec!["title", "description.overview"],
                 3,
@@ -69,17 +82,19 @@ pub async fn chat_handler(
     };
 
     // Step 3: Vector search for certificates
-    let certs_docs = match vector_search(
-        &rag_state.db_client.certificates(),
-        query_embedding,
-        "certificates_index",
+    let certs_docs = match cached_vector_search(
+        &rag_state,
+        ContentKind::Certificate,
+        &query_embedding,
+        CERTIFICATES_VECTOR_INDEX,
         3,
     )
     .await
     {
         Ok(docs) if !docs.is_empty() => docs,
-        _ => keyword_search(
-            &rag_state.db_client.certificates(),
+        _ => cached_keyword_search(
+            &rag_state,
+            ContentKind::Certificate,
             &request.messages,
             vec!["name", "issuer"],
             3,
@@ -121,6 +136,60 @@ User Question: {}",
     }
 }
 
+fn collection(rag_state: &RagState, kind: ContentKind) -> Collection<Document> {
+    match kind {
+        ContentKind::Project => rag_state.db_client.projects(),
+        ContentKind::Certificate => rag_state.db_client.certificates(),
+    }
+}
+
+/// Vector search candidates, cached per query vector until the collection changes
+async fn cached_vector_search(
+    rag_state: &RagState,
+    kind: ContentKind,
+    query_embedding: &[f64],
+    index_name: &str,
+    limit: i64,
+) -> anyhow::Result<Vec<Document>> {
+    let mut hasher = DefaultHasher::new();
+    for value in query_embedding {
+        value.to_bits().hash(&mut hasher);
+    }
+    let key = format!("vector:{}:{}:{:x}", index_name, limit, hasher.finish());
+
+    let collection = collection(rag_state, kind);
+    rag_state
+        .read_cache
+        .get_or_load(kind, &key, || {
+            vector_search(&collection, query_embedding.to_vec(), index_name, limit)
+        })
+        .await
+}
+
+/// Keyword fallback candidates, cached per set of search terms
+async fn cached_keyword_search(
+    rag_state: &RagState,
+    kind: ContentKind,
+    query: &str,
+    search_fields: Vec<&str>,
+    limit: i64,
+) -> anyhow::Result<Vec<Document>> {
+    let key = format!(
+        "keyword:{}:{}:{}",
+        search_fields.join(","),
+        limit,
+        keyword_terms(query).join(" ")
+    );
+
+    let collection = collection(rag_state, kind);
+    rag_state
+        .read_cache
+        .get_or_load(kind, &key, || {
+            keyword_search(&collection, query, search_fields, limit)
+        })
+        .await
+}
+
 async fn fallback_chat(
     gemini_client: &GeminiClient,
     message: &str,
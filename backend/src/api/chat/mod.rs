@@ -8,12 +8,15 @@ mod vector_search;
 
 pub use client::GeminiClient;
 pub use config::PortfolioOwner;
-pub use embeddings::generate_embedding;
+pub use embeddings::{
+    embed_content, Embedder, EmbeddingOutcome, GeminiEmbedder, DEFAULT_EMBEDDING_DIMENSIONS,
+    DEFAULT_EMBEDDING_MODEL,
+};
 pub use formatter::{format_certificates, format_projects};
 pub use prompt::build_system_prompt;
-pub use vector_search::{keyword_search, vector_search};
+pub use vector_search::{keyword_search, keyword_terms, vector_search};
 
-use crate::database::MongoClient;
+use crate::{database::MongoClient, repositories::ReadCache};
 use axum::{routing::post, Router};
 use std::sync::Arc;
 
@@ -23,14 +26,16 @@ pub use handlers::RagState;
 pub fn router(
     db_client: Arc<MongoClient>,
     gemini_client: Arc<GeminiClient>,
-    api_key: String,
+    embedder: Arc<dyn Embedder>,
     portfolio_owner: PortfolioOwner,
+    read_cache: Arc<ReadCache>,
 ) -> Router {
     let rag_state = Arc::new(RagState {
         db_client,
         gemini_client,
-        api_key,
+        embedder,
         portfolio_owner,
+        read_cache,
     });
 
     Router::new()
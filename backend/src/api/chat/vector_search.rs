@@ -1,7 +1,26 @@
-use mongodb::{bson::{doc, Document}, Collection};
 use anyhow::Result;
+use mongodb::{
+    bson::{doc, Document},
+    Collection,
+};
+
+/// Nearest neighbours considered per requested result, as Atlas recommends
+const CANDIDATES_PER_RESULT: i64 = 20;
+
+/// Content chat may discuss
+/// Trashed documents keep their embedding until purged, hidden or unpublished content
+/// is not discussed; documents from before these fields count as public and published
+fn discussable() -> Document {
+    doc! {
+        "deleted_at": null,
+        "visibility": { "$ne": "unlisted" },
+        "publication": { "$in": ["published", null] }
+    }
+}
 
 /// Perform MongoDB vector search on embeddings
+/// The index declares the `discussable` fields as filters, so hidden content is excluded
+/// before the nearest neighbours are picked rather than after
 pub async fn vector_search(
     collection: &Collection<Document>,
     query_embedding: Vec<f64>,
@@ -14,8 +33,9 @@ pub async fn vector_search(
                 "index": index_name,
                 "path": "embedding",
                 "queryVector": query_embedding,
-                "numCandidates": 10,
-                "limit": limit
+                "numCandidates": limit * CANDIDATES_PER_RESULT,
+                "limit": limit,
+                "filter": discussable()
             }
         },
         doc! {
@@ -25,6 +45,7 @@ pub async fn vector_search(
                 "name": 1,
                 "date": 1,
                 "issue_date": 1,
+                "expires_at": 1,
                 "description": 1,
                 "technologies": 1,
                 "githubUrl": 1,
@@ -34,53 +55,177 @@ pub async fn vector_search(
                 "link": 1,
                 "score": { "$meta": "vectorSearchScore" }
             }
-        }
+        },
     ];
-    
+
     let mut cursor = collection.aggregate(pipeline).await?;
     let mut results = Vec::new();
-    
+
     use futures::stream::TryStreamExt;
     while let Some(doc) = cursor.try_next().await? {
         results.push(doc);
     }
-    
+
     Ok(results)
 }
 
+/// Most distinct terms taken from one message
+const MAX_KEYWORD_TERMS: usize = 8;
+/// Upper bound on `$or` clauses (terms x fields) sent to MongoDB
+const MAX_KEYWORD_CLAUSES: usize = 24;
+/// Terms shorter than this are too ambiguous to match on
+const MIN_TERM_CHARS: usize = 2;
+
+/// Common English words that would match nearly every document
+const STOPWORDS: &[&str] = &[
+    "a", "about", "all", "an", "and", "any", "are", "as", "at", "be", "by", "can", "did", "do",
+    "does", "for", "from", "has", "have", "he", "her", "his", "how", "i", "in", "is", "it", "its",
+    "me", "my", "of", "on", "or", "she", "show", "tell", "that", "the", "their", "them", "they",
+    "this", "to", "was", "what", "when", "where", "which", "who", "why", "with", "you", "your",
+];
+
+/// Distinct, lowercase, non-stopword terms of a message, in order of appearance
+pub fn keyword_terms(query: &str) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+
+    for word in query.split_whitespace() {
+        // Keep inner punctuation such as "node.js" or "c++", strip it from the edges
+        let term = word
+            .trim_matches(|c: char| !c.is_alphanumeric() && c != '+' && c != '#')
+            .to_lowercase();
+
+        if term.chars().count() < MIN_TERM_CHARS
+            || STOPWORDS.contains(&term.as_str())
+            || terms.contains(&term)
+        {
+            continue;
+        }
+
+        terms.push(term);
+        if terms.len() == MAX_KEYWORD_TERMS {
+            break;
+        }
+    }
+
+    terms
+}
+
+/// Aggregation pipeline ranking documents by the number of distinct terms they contain
+fn keyword_pipeline(terms: &[String], search_fields: &[&str], limit: i64) -> Vec<Document> {
+    let max_terms = (MAX_KEYWORD_CLAUSES / search_fields.len().max(1)).max(1);
+    let terms = &terms[..terms.len().min(max_terms)];
+
+    let mut or_conditions = Vec::new();
+    let mut term_scores = Vec::new();
+
+    for term in terms {
+        // Input is escaped, so the pattern is always a literal substring match
+        let pattern = regex::escape(term);
+
+        let mut field_matches = Vec::new();
+        for field in search_fields {
+            or_conditions.push(doc! {
+                *field: { "$regex": &pattern, "$options": "i" }
+            });
+            // Only string fields are scored, `$regexMatch` rejects other types
+            let value = format!("${}", field);
+            field_matches.push(doc! {
+                "$regexMatch": {
+                    "input": {
+                        "$cond": [{ "$eq": [{ "$type": &value }, "string"] }, &value, ""]
+                    },
+                    "regex": &pattern,
+                    "options": "i"
+                }
+            });
+        }
+
+        term_scores.push(doc! { "$cond": [{ "$or": field_matches }, 1, 0] });
+    }
+
+    let mut filter = discussable();
+    filter.insert("$or", or_conditions);
+
+    vec![
+        doc! { "$match": filter },
+        doc! { "$addFields": { "score": { "$add": term_scores } } },
+        doc! { "$sort": { "score": -1, "_id": 1 } },
+        doc! { "$limit": limit },
+    ]
+}
+
 /// Fallback keyword search when vector search fails
+/// Ranks documents by how many distinct terms of the query they contain
 pub async fn keyword_search(
     collection: &Collection<Document>,
     query: &str,
     search_fields: Vec<&str>,
     limit: i64,
 ) -> Result<Vec<Document>> {
-    let query_words: Vec<&str> = query.split_whitespace().collect();
-    let mut or_conditions = Vec::new();
-    
-    for word in query_words {
-        for field in &search_fields {
-            or_conditions.push(doc! {
-                *field: {
-                    "$regex": word.to_string(),
-                    "$options": "i"
-                }
-            });
-        }
+    let terms = keyword_terms(query);
+    if terms.is_empty() || search_fields.is_empty() {
+        return Ok(Vec::new());
     }
-    
-    let filter = doc! { "$or": or_conditions };
-    let options = mongodb::options::FindOptions::builder()
-        .limit(limit)
-        .build();
-    
-    let mut cursor = collection.find(filter).with_options(options).await?;
+
+    let pipeline = keyword_pipeline(&terms, &search_fields, limit);
+    let mut cursor = collection.aggregate(pipeline).await?;
     let mut results = Vec::new();
-    
+
     use futures::stream::TryStreamExt;
     while let Some(doc) = cursor.try_next().await? {
         results.push(doc);
     }
-    
+
     Ok(results)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keyword_terms_drop_stopwords_and_duplicates() {
+        assert_eq!(
+            keyword_terms("What is your experience with Rust, rust and Node.js? (C++)"),
+            vec!["experience", "rust", "node.js", "c++"]
+        );
+        assert!(keyword_terms("is it a ?").is_empty());
+
+        let long = (0..50)
+            .map(|i| format!("term{}", i))
+            .collect::<Vec<_>>()
+            .join(" ");
+        assert_eq!(keyword_terms(&long).len(), MAX_KEYWORD_TERMS);
+    }
+
+    #[test]
+    fn test_keyword_pipeline_escapes_and_caps_clauses() {
+        let terms = keyword_terms("(a+)+$ .* rust");
+        let fields = ["title", "description.overview", "name", "issuer"];
+        let pipeline = keyword_pipeline(&terms, &fields, 3);
+
+        let clauses = pipeline[0]
+            .get_document("$match")
+            .unwrap()
+            .get_array("$or")
+            .unwrap();
+        assert!(clauses.len() <= MAX_KEYWORD_CLAUSES);
+        let first = clauses[0]
+            .as_document()
+            .unwrap()
+            .get_document("title")
+            .unwrap();
+        assert_eq!(first.get_str("$regex").unwrap(), r"a\+\)\+");
+
+        let many: Vec<String> = (0..MAX_KEYWORD_TERMS)
+            .map(|i| format!("term{}", i))
+            .collect();
+        let pipeline = keyword_pipeline(&many, &fields, 3);
+        let clauses = pipeline[0]
+            .get_document("$match")
+            .unwrap()
+            .get_array("$or")
+            .unwrap();
+        assert_eq!(clauses.len(), MAX_KEYWORD_CLAUSES);
+    }
+}
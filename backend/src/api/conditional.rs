@@ -0,0 +1,300 @@
+//! Conditional requests: version `ETag`s, `If-Match` preconditions and cache validators
+
+use crate::{models::ContentKind, repositories::VersionCheck};
+use axum::{
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use mongodb::bson::oid::ObjectId;
+use sha2::{Digest, Sha256};
+use std::{collections::HashMap, sync::Mutex};
+
+/// `Cache-Control` of public reads unless the `HTTP_CACHE_CONTROL` secret overrides it
+pub const DEFAULT_CACHE_CONTROL: &str = "public, max-age=60";
+
+/// IMF-fixdate format of HTTP date headers
+const HTTP_DATE: &str = "%a, %d %b %Y %H:%M:%S GMT";
+
+/// Entity tags whose first sighting is remembered, beyond that the record starts over
+const MAX_SEEN_TAGS: usize = 4096;
+
+/// Strong entity tag of a document version
+/// The id keeps tags of a purged document apart from those of a new one reusing its slug
+pub fn etag(id: Option<ObjectId>, version: i64) -> HeaderValue {
+    let id = id.map(|id| id.to_hex()).unwrap_or_default();
+    HeaderValue::from_str(&format!("\"{}-{}\"", id, version))
+        .expect("quoted hex and digits are a valid header")
+}
+
+/// Precondition of a write from its `If-Match` header
+/// Weak or foreign tags never match, as If-Match requires strong comparison
+/// A date suffix added by `Validators::dated` is ignored
+pub fn if_match(headers: &HeaderMap) -> VersionCheck {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return VersionCheck::Any;
+    };
+
+    let mut versions = Vec::new();
+    for tag in value.to_str().unwrap_or_default().split(',').map(str::trim) {
+        if tag == "*" {
+            return VersionCheck::Any;
+        }
+        let accepted = tag
+            .strip_prefix('"')
+            .and_then(|tag| tag.strip_suffix('"'))
+            .and_then(|tag| {
+                let mut parts = tag.splitn(3, '-');
+                let id = ObjectId::parse_str(parts.next()?).ok()?;
+                Some((id, parts.next()?.parse::<i64>().ok()?))
+            });
+        versions.extend(accepted);
+    }
+    VersionCheck::OneOf(versions)
+}
+
+/// Validators of one response
+#[derive(Debug, Clone)]
+pub struct Validators {
+    pub etag: HeaderValue,
+    pub last_modified: DateTime<Utc>,
+}
+
+impl Validators {
+    /// Validators of a response that also depends on the day, e.g. a certificate's status
+    /// Cached copies go stale at midnight UTC even when nothing was written
+    pub fn dated(self, today: NaiveDate) -> Self {
+        let tag = self.etag.to_str().unwrap_or_default();
+        let tag = tag.strip_suffix('"').unwrap_or(tag);
+        let midnight = today.and_time(chrono::NaiveTime::MIN).and_utc();
+        Self {
+            etag: HeaderValue::from_str(&format!("{}-{}\"", tag, today.format("%Y%m%d")))
+                .expect("dated ETag is a valid header"),
+            last_modified: self.last_modified.max(midnight),
+        }
+    }
+}
+
+/// Cache validators and `Cache-Control` for public reads
+/// Entity tags derive from the stored documents, so every instance serving the same
+/// content agrees on them and a write through any instance invalidates cached copies
+pub struct HttpCache {
+    cache_control: HeaderValue,
+    /// When this process first served each entity tag, its Last-Modified
+    /// Later than the write on other instances or after a restart, which turns some
+    /// `If-Modified-Since` revalidations into full responses but never into a stale 304
+    seen: Mutex<HashMap<String, DateTime<Utc>>>,
+}
+
+impl Default for HttpCache {
+    fn default() -> Self {
+        Self::new(HeaderValue::from_static(DEFAULT_CACHE_CONTROL))
+    }
+}
+
+impl HttpCache {
+    pub fn new(cache_control: HeaderValue) -> Self {
+        Self {
+            cache_control,
+            seen: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Validators of a list of `kind` from the `(id, version)` of every document on the
+    /// page and its pagination, so any write to a listed document changes them
+    pub fn list_validators(
+        &self,
+        kind: ContentKind,
+        documents: impl IntoIterator<Item = (Option<ObjectId>, i64)>,
+        total: u64,
+        next_offset: Option<u64>,
+    ) -> Validators {
+        let mut digest = Sha256::new();
+        digest.update(format!("{}:{}:{:?}", kind.as_str(), total, next_offset));
+        for (id, version) in documents {
+            let id = id.map(|id| id.to_hex()).unwrap_or_default();
+            digest.update(format!("|{}-{}", id, version));
+        }
+        let digest = hex::encode(digest.finalize());
+        let tag = HeaderValue::from_str(&format!("W/\"{}-{}\"", kind.as_str(), &digest[..32]))
+            .expect("list ETag is a valid header");
+        self.validators(tag)
+    }
+
+    /// Validators of the document `id` at `version`
+    pub fn document_validators(&self, id: Option<ObjectId>, version: i64) -> Validators {
+        self.validators(etag(id, version))
+    }
+
+    /// Validators with `etag`, last modified when this process first served it
+    fn validators(&self, etag: HeaderValue) -> Validators {
+        let key = etag.to_str().unwrap_or_default().to_string();
+        let mut seen = self.seen.lock().unwrap();
+        if seen.len() >= MAX_SEEN_TAGS && !seen.contains_key(&key) {
+            seen.clear();
+        }
+        let last_modified = *seen.entry(key).or_insert_with(Utc::now);
+        Validators {
+            etag,
+            last_modified,
+        }
+    }
+
+    /// `304 Not Modified` if the client's copy is still current
+    /// `If-None-Match` takes precedence over `If-Modified-Since`, as in RFC 9110
+    pub fn not_modified(&self, headers: &HeaderMap, validators: &Validators) -> Option<Response> {
+        let current = if let Some(value) = headers.get(header::IF_NONE_MATCH) {
+            let ours = weak_tag(validators.etag.to_str().unwrap_or_default());
+            value
+                .to_str()
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .any(|tag| tag == "*" || weak_tag(tag) == ours)
+        } else if let Some(since) = headers
+            .get(header::IF_MODIFIED_SINCE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| DateTime::parse_from_rfc2822(value).ok())
+        {
+            // HTTP dates have second precision
+            validators.last_modified.timestamp() <= since.timestamp()
+        } else {
+            false
+        };
+
+        current.then(|| self.fresh(validators, StatusCode::NOT_MODIFIED))
+    }
+
+    /// Attach validators and `Cache-Control` to a response
+    pub fn fresh(&self, validators: &Validators, response: impl IntoResponse) -> Response {
+        let mut response = response.into_response();
+        let headers = response.headers_mut();
+        headers.insert(header::ETAG, validators.etag.clone());
+        let last_modified = validators.last_modified.format(HTTP_DATE).to_string();
+        if let Ok(value) = HeaderValue::from_str(&last_modified) {
+            headers.insert(header::LAST_MODIFIED, value);
+        }
+        headers.insert(header::CACHE_CONTROL, self.cache_control.clone());
+        response
+    }
+
+    /// Like `fresh`, but kept out of shared caches - for admin previews of unpublished content
+    pub fn private(&self, validators: &Validators, response: impl IntoResponse) -> Response {
+        let mut response = self.fresh(validators, response);
+        response.headers_mut().insert(
+            header::CACHE_CONTROL,
+            HeaderValue::from_static("private, no-store"),
+        );
+        response
+    }
+}
+
+/// Opaque part of an entity tag, for weak comparison
+fn weak_tag(tag: &str) -> &str {
+    tag.strip_prefix("W/").unwrap_or(tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(if_match: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_MATCH, HeaderValue::from_str(if_match).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_if_match_parsing() {
+        let id = ObjectId::parse_str("65a1b2c3d4e5f60718293a4b").unwrap();
+        assert_eq!(if_match(&HeaderMap::new()), VersionCheck::Any);
+        assert_eq!(if_match(&headers("*")), VersionCheck::Any);
+        assert_eq!(
+            if_match(&headers(&format!(r#""{0}-3", "{0}-5""#, id.to_hex()))),
+            VersionCheck::OneOf(vec![(id, 3), (id, 5)])
+        );
+        assert_eq!(
+            if_match(&headers(&format!(r#"W/"{}-3", "3", "abc""#, id.to_hex()))),
+            VersionCheck::OneOf(vec![])
+        );
+        assert_eq!(etag(Some(id), 7), r#""65a1b2c3d4e5f60718293a4b-7""#);
+        assert_eq!(
+            if_match(&headers(&format!(r#""{}-3-20261018""#, id.to_hex()))),
+            VersionCheck::OneOf(vec![(id, 3)])
+        );
+    }
+
+    #[test]
+    fn test_dated_validators_change_daily() {
+        let cache = HttpCache::default();
+        let today = Utc::now().date_naive();
+        let document = cache.document_validators(None, 2);
+        let dated = document.clone().dated(today);
+        assert_eq!(
+            dated.etag.to_str().unwrap(),
+            format!(r#""-2-{}""#, today.format("%Y%m%d"))
+        );
+        let list = cache
+            .list_validators(ContentKind::Certificate, [], 0, None)
+            .dated(today);
+        assert!(list.etag.to_str().unwrap().starts_with("W/\""));
+
+        let tomorrow = today.succ_opt().unwrap();
+        let next_day = document.dated(tomorrow);
+        assert_ne!(next_day.etag, dated.etag);
+        assert!(next_day.last_modified > dated.last_modified);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, dated.etag.clone());
+        assert!(cache.not_modified(&headers, &dated).is_some());
+        assert!(cache.not_modified(&headers, &next_day).is_none());
+    }
+
+    #[test]
+    fn test_not_modified_follows_validators() {
+        let cache = HttpCache::default();
+        let kind = ContentKind::Project;
+        let id = Some(ObjectId::new());
+        let before = cache.list_validators(kind, [(id, 1)], 1, None);
+
+        let request = |name, value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(name, HeaderValue::from_str(value).unwrap());
+            headers
+        };
+        let if_none_match = request(header::IF_NONE_MATCH, before.etag.to_str().unwrap());
+        let response = cache.not_modified(&if_none_match, &before).unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(
+            response.headers()[header::CACHE_CONTROL],
+            DEFAULT_CACHE_CONTROL
+        );
+
+        let since = before.last_modified.format(HTTP_DATE).to_string();
+        let if_modified_since = request(header::IF_MODIFIED_SINCE, &since);
+        assert!(cache.not_modified(&if_modified_since, &before).is_some());
+        assert!(cache.not_modified(&HeaderMap::new(), &before).is_none());
+
+        // A write changes the list tag and is newer than the client's copy
+        let after = cache.list_validators(kind, [(id, 2)], 1, None);
+        assert_ne!(after.etag, before.etag);
+        assert!(cache.not_modified(&if_none_match, &after).is_none());
+        assert!(after.last_modified >= before.last_modified);
+
+        // Another instance derives the same tag from the same documents
+        let other_instance = HttpCache::default();
+        let same = other_instance.list_validators(kind, [(id, 1)], 1, None);
+        assert_eq!(same.etag, before.etag);
+        assert!(other_instance.not_modified(&if_none_match, &same).is_some());
+        assert_ne!(
+            cache
+                .list_validators(ContentKind::Certificate, [(id, 1)], 1, None)
+                .etag,
+            before.etag
+        );
+        assert_ne!(
+            cache.list_validators(kind, [(id, 1)], 2, Some(1)).etag,
+            before.etag
+        );
+    }
+}
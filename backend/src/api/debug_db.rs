@@ -1,11 +1,7 @@
-use axum::{
-    extract::State,
-    http::StatusCode,
-    Json,
-};
-use std::sync::Arc;
-use serde::{Deserialize, Serialize};
 use crate::database::DatabaseConnection;
+use axum::{extract::State, http::StatusCode, Json};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DatabaseInfo {
@@ -25,21 +21,25 @@ pub async fn list_databases(
     State(db_conn): State<Arc<DatabaseConnection>>,
 ) -> Result<Json<DatabaseInfo>, (StatusCode, String)> {
     // List all databases
-    let databases = db_conn.client()
-        .list_database_names()
-        .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to list databases: {}", e)))?;
-    
+    let databases = db_conn.client().list_database_names().await.map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to list databases: {}", e),
+        )
+    })?;
+
     // Get current database name
     let current_db = db_conn.database();
     let db_name = current_db.name().to_string();
-    
+
     // List collections in current database
-    let collection_names = current_db
-        .list_collection_names()
-        .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to list collections: {}", e)))?;
-    
+    let collection_names = current_db.list_collection_names().await.map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to list collections: {}", e),
+        )
+    })?;
+
     // Get document counts for each collection
     let mut collections = Vec::new();
     for name in collection_names {
@@ -48,13 +48,13 @@ pub async fn list_databases(
             .count_documents(mongodb::bson::doc! {})
             .await
             .unwrap_or(0);
-        
+
         collections.push(CollectionInfo {
             name,
             document_count: count as i64,
         });
     }
-    
+
     Ok(Json(DatabaseInfo {
         databases,
         current_database: db_name,
@@ -0,0 +1,184 @@
+use crate::{
+    error::{ApiError, ApiResult},
+    models::ContentKind,
+    repositories::{ListQuery, ListSort, Page},
+};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "swagger")]
+use utoipa::{IntoParams, ToSchema};
+
+const DEFAULT_LIMIT: u64 = 20;
+const MAX_LIMIT: u64 = 100;
+
+/// Response shape of a list endpoint
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum ListFormat {
+    /// `{ items, total, nextCursor }`
+    #[default]
+    Envelope,
+    /// Legacy bare array, unpaginated unless `limit` is given
+    Array,
+}
+
+/// Query parameters shared by the project and certificate list endpoints
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(feature = "swagger", derive(IntoParams))]
+#[cfg_attr(feature = "swagger", into_params(parameter_in = Query))]
+pub struct ListParams {
+    /// Page size (1-100, default 20)
+    pub limit: Option<u64>,
+    /// Opaque cursor returned as `nextCursor` by the previous page
+    pub cursor: Option<String>,
+    /// `date`, `-date`, `title` or `-title` (default: insertion order)
+    pub sort: Option<ListSort>,
+    /// Projects only: exact technology, case-insensitive
+    pub technology: Option<String>,
+    /// Certificates only: exact issuer, case-insensitive
+    pub issuer: Option<String>,
+    /// Year the date falls in, ranges match every year they span
+    pub year: Option<i32>,
+    /// `envelope` (default) or `array` for the legacy bare-array response
+    pub format: Option<ListFormat>,
+}
+
+impl ListParams {
+    /// Validate the parameters for the given collection and build the repository query
+    pub fn into_query(self, kind: ContentKind) -> ApiResult<(ListQuery, ListFormat)> {
+        let format = self.format.unwrap_or_default();
+
+        match kind {
+            ContentKind::Project if self.issuer.is_some() => {
+                return Err(ApiError::BadRequest(
+                    "issuer filter is only supported for certificates".to_string(),
+                ))
+            }
+            ContentKind::Certificate if self.technology.is_some() => {
+                return Err(ApiError::BadRequest(
+                    "technology filter is only supported for projects".to_string(),
+                ))
+            }
+            _ => {}
+        }
+
+        let limit = match (self.limit, format) {
+            (Some(limit), _) if !(1..=MAX_LIMIT).contains(&limit) => {
+                return Err(ApiError::BadRequest(format!(
+                    "limit must be between 1 and {}",
+                    MAX_LIMIT
+                )))
+            }
+            (Some(limit), _) => Some(limit),
+            (None, ListFormat::Envelope) => Some(DEFAULT_LIMIT),
+            // Legacy clients expect the whole collection
+            (None, ListFormat::Array) => None,
+        };
+
+        let offset = match self.cursor.as_deref() {
+            Some(cursor) => decode_cursor(cursor)?,
+            None => 0,
+        };
+
+        let query = ListQuery {
+            offset,
+            limit,
+            sort: self.sort.unwrap_or_default(),
+            technology: non_empty(self.technology),
+            issuer: non_empty(self.issuer),
+            year: self.year,
+        };
+        Ok((query, format))
+    }
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|v| !v.trim().is_empty())
+}
+
+/// Cursors are opaque to clients, currently the offset of the next page
+fn encode_cursor(offset: u64) -> String {
+    format!("o{}", offset)
+}
+
+fn decode_cursor(cursor: &str) -> ApiResult<u64> {
+    cursor
+        .strip_prefix('o')
+        .and_then(|offset| offset.parse().ok())
+        .ok_or_else(|| ApiError::BadRequest("Invalid cursor".to_string()))
+}
+
+/// One page of a list endpoint with the cursor of the next page
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListEnvelope<T> {
+    pub items: Vec<T>,
+    pub total: u64,
+    pub next_cursor: Option<String>,
+}
+
+/// Body of a list endpoint in the requested format
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum ListResponse<T> {
+    Envelope(ListEnvelope<T>),
+    Array(Vec<T>),
+}
+
+impl<T> ListResponse<T> {
+    pub fn new(page: Page<T>, format: ListFormat) -> Self {
+        match format {
+            ListFormat::Envelope => Self::Envelope(ListEnvelope {
+                items: page.items,
+                total: page.total,
+                next_cursor: page.next_offset.map(encode_cursor),
+            }),
+            ListFormat::Array => Self::Array(page.items),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_params_validation() {
+        let params = ListParams {
+            cursor: Some(encode_cursor(40)),
+            sort: Some(ListSort::DateDesc),
+            ..Default::default()
+        };
+        let (query, format) = params.into_query(ContentKind::Project).unwrap();
+        assert_eq!((query.offset, query.limit), (40, Some(DEFAULT_LIMIT)));
+        assert_eq!(format, ListFormat::Envelope);
+
+        let legacy = ListParams {
+            format: Some(ListFormat::Array),
+            ..Default::default()
+        };
+        let (query, _) = legacy.into_query(ContentKind::Certificate).unwrap();
+        assert_eq!(query.limit, None);
+
+        for params in [
+            ListParams {
+                cursor: Some("garbage".to_string()),
+                ..Default::default()
+            },
+            ListParams {
+                limit: Some(0),
+                ..Default::default()
+            },
+            ListParams {
+                issuer: Some("Coursera".to_string()),
+                ..Default::default()
+            },
+        ] {
+            assert!(matches!(
+                params.into_query(ContentKind::Project),
+                Err(ApiError::BadRequest(_))
+            ));
+        }
+    }
+}
@@ -1,36 +1,88 @@
+pub mod admin;
 pub mod certificates;
 pub mod chat;
+pub mod conditional;
+pub mod listing;
+pub mod patch;
 pub mod projects;
+pub mod search;
 
-use crate::{auth::AuthConfig, database::MongoClient};
+#[cfg(test)]
+mod test_support;
+
+use crate::{
+    auth::AuthConfig,
+    database::MongoClient,
+    repositories::Repositories,
+    services::{link_health::LinkChecker, media::MediaService, verification::CertificateVerifier},
+};
 use axum::Router;
-use chat::{GeminiClient, PortfolioOwner};
+use chat::{Embedder, GeminiClient, PortfolioOwner};
+use conditional::HttpCache;
 use std::sync::Arc;
 
 /// Build API router with all endpoints
 /// Admin-protected routes require auth_config
 /// API is versioned at /v1 prefix for future compatibility
+/// Chat (vector search) is only mounted when a MongoDB client is available
+#[allow(clippy::too_many_arguments)]
 pub fn build_router(
-    db_client: Arc<MongoClient>,
+    repositories: Repositories,
+    db_client: Option<Arc<MongoClient>>,
+    embedder: Arc<dyn Embedder>,
     auth_config: Arc<AuthConfig>,
     gemini_client: Arc<GeminiClient>,
-    api_key: String,
     portfolio_owner: PortfolioOwner,
+    http_cache: Arc<HttpCache>,
+    media: Arc<MediaService>,
+    links: Arc<LinkChecker>,
+    verifier: Arc<CertificateVerifier>,
 ) -> Router {
     // Version 1 API routes
-    let v1_router = Router::new()
+    let mut v1_router = Router::new()
         .nest(
             "/projects",
-            projects::router(db_client.clone(), auth_config.clone()),
+            projects::router(
+                repositories.clone(),
+                embedder.clone(),
+                auth_config.clone(),
+                http_cache.clone(),
+            ),
         )
         .nest(
             "/certificates",
-            certificates::router(db_client.clone(), auth_config.clone()),
+            certificates::router(
+                repositories.clone(),
+                embedder.clone(),
+                auth_config.clone(),
+                http_cache,
+            ),
         )
+        .nest("/search", search::router(repositories.clone()))
         .nest(
+            "/admin",
+            admin::router(
+                repositories.clone(),
+                embedder.clone(),
+                auth_config,
+                media,
+                links,
+                verifier,
+            ),
+        );
+
+    if let Some(db_client) = db_client {
+        v1_router = v1_router.nest(
             "/chat",
-            chat::router(db_client.clone(), gemini_client, api_key, portfolio_owner),
+            chat::router(
+                db_client,
+                gemini_client,
+                embedder,
+                portfolio_owner,
+                repositories.read_cache,
+            ),
         );
+    }
 
     // Nest under /v1 prefix
     Router::new().nest("/v1", v1_router)
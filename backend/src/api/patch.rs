@@ -0,0 +1,164 @@
+//! Partial updates: RFC 7386 merge patches and RFC 6902 JSON Patch documents
+
+use crate::error::{ApiError, ApiResult};
+use axum::http::{header, HeaderMap};
+use json_patch::{Patch, PatchErrorKind};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use validator::Validate;
+
+pub const MERGE_PATCH: &str = "application/merge-patch+json";
+pub const JSON_PATCH: &str = "application/json-patch+json";
+
+/// Patch document format, chosen by `Content-Type`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatchFormat {
+    /// RFC 7386 - a partial document, `null` removes a field
+    Merge,
+    /// RFC 6902 - a list of add/remove/replace/move/copy/test operations
+    JsonPatch,
+}
+
+impl PatchFormat {
+    pub fn from_headers(headers: &HeaderMap) -> ApiResult<Self> {
+        let content_type = headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+        let media_type = content_type.split(';').next().unwrap_or_default().trim();
+
+        match media_type.to_ascii_lowercase().as_str() {
+            MERGE_PATCH => Ok(Self::Merge),
+            JSON_PATCH => Ok(Self::JsonPatch),
+            _ => Err(ApiError::UnsupportedMediaType(format!(
+                "PATCH bodies must be {} or {}",
+                MERGE_PATCH, JSON_PATCH
+            ))),
+        }
+    }
+
+    /// Patched and validated copy of `current`, which is left untouched
+    /// Malformed patches are rejected with 400, a failed `test` with 409 and
+    /// results breaking the model's rules with 422
+    pub fn apply<T>(self, current: &T, body: &[u8]) -> ApiResult<T>
+    where
+        T: Serialize + DeserializeOwned + Validate,
+    {
+        let mut document = serde_json::to_value(current)
+            .map_err(|e| ApiError::InternalError(format!("Failed to serialize document: {}", e)))?;
+
+        match self {
+            Self::Merge => {
+                let patch: Value = serde_json::from_slice(body)
+                    .map_err(|e| ApiError::BadRequest(format!("Invalid merge patch: {}", e)))?;
+                json_patch::merge(&mut document, &patch);
+            }
+            Self::JsonPatch => {
+                let patch: Patch = serde_json::from_slice(body)
+                    .map_err(|e| ApiError::BadRequest(format!("Invalid JSON Patch: {}", e)))?;
+                json_patch::patch(&mut document, &patch).map_err(|e| match e.kind {
+                    PatchErrorKind::TestFailed => ApiError::Conflict(e.to_string()),
+                    _ => ApiError::ValidationError(e.to_string()),
+                })?;
+            }
+        }
+
+        let patched: T = serde_json::from_value(document).map_err(|e| {
+            ApiError::ValidationError(format!("Patched document is invalid: {}", e))
+        })?;
+        patched.validate()?;
+        Ok(patched)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::certificate::CertificateUpdate;
+    use axum::http::HeaderValue;
+    use serde_json::json;
+
+    fn certificate() -> CertificateUpdate {
+        CertificateUpdate {
+            name: "Rust".to_string(),
+            issuer: "Coursera".to_string(),
+            link: "https://coursera.org/verify/abc".to_string(),
+            issue_date: Some("2024".parse().unwrap()),
+            expires_at: None,
+            credential_id: None,
+            image_url: None,
+        }
+    }
+
+    fn body(value: Value) -> Vec<u8> {
+        serde_json::to_vec(&value).unwrap()
+    }
+
+    #[test]
+    fn test_format_follows_content_type() {
+        let mut headers = HeaderMap::new();
+        assert!(matches!(
+            PatchFormat::from_headers(&headers),
+            Err(ApiError::UnsupportedMediaType(_))
+        ));
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/merge-patch+json; charset=utf-8"),
+        );
+        assert_eq!(
+            PatchFormat::from_headers(&headers).unwrap(),
+            PatchFormat::Merge
+        );
+        headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(JSON_PATCH));
+        assert_eq!(
+            PatchFormat::from_headers(&headers).unwrap(),
+            PatchFormat::JsonPatch
+        );
+    }
+
+    #[test]
+    fn test_patches_are_applied_and_validated() {
+        let current = certificate();
+
+        let merged = PatchFormat::Merge
+            .apply(
+                &current,
+                &body(json!({ "issuer": "edX", "issue_date": null })),
+            )
+            .unwrap();
+        assert_eq!(merged.issuer, "edX");
+        assert_eq!(merged.issue_date, None);
+        assert_eq!(merged.name, "Rust");
+
+        let patched = PatchFormat::JsonPatch
+            .apply(
+                &current,
+                &body(json!([
+                    { "op": "test", "path": "/name", "value": "Rust" },
+                    { "op": "replace", "path": "/name", "value": "Rust 2" }
+                ])),
+            )
+            .unwrap();
+        assert_eq!(patched.name, "Rust 2");
+
+        assert!(matches!(
+            PatchFormat::JsonPatch.apply(
+                &current,
+                &body(json!([{ "op": "test", "path": "/name", "value": "Go" }]))
+            ),
+            Err(ApiError::Conflict(_))
+        ));
+        assert!(matches!(
+            PatchFormat::Merge.apply(&current, &body(json!({ "link": "not a url" }))),
+            Err(ApiError::ValidationError(_))
+        ));
+        assert!(matches!(
+            PatchFormat::Merge.apply(&current, &body(json!({ "name": null }))),
+            Err(ApiError::ValidationError(_))
+        ));
+        assert!(matches!(
+            PatchFormat::JsonPatch.apply(&current, b"{"),
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+}
@@ -1,20 +1,37 @@
-use crate::{auth::UserInfo, database::MongoClient, models::project::ProjectUpdate};
+use super::ProjectsState;
+use crate::{
+    api::{
+        chat::embed_content,
+        conditional::{etag, if_match},
+        patch::PatchFormat,
+    },
+    auth::UserInfo,
+    error::{ApiError, ApiResult},
+    models::{project::ProjectUpdate, ContentKind, Revision},
+    repositories::{FieldChanges, VersionCheck},
+};
 use axum::{
+    body::Bytes,
     extract::{Path, State},
-    http::StatusCode,
+    http::{header, HeaderMap},
+    response::{IntoResponse, Response},
     Extension, Json,
 };
 use serde_json::{json, Value};
 use std::sync::Arc;
 use validator::Validate;
 
-/// Delete project by slug (Admin only)
+/// Move project to the trash by slug (Admin only)
 #[cfg_attr(feature = "swagger", utoipa::path(
     delete,
     path = "/api/v1/projects/{slug}",
+    params(
+        ("If-Match" = Option<String>, Header, description = "ETag from the last read, the delete fails with 412 if the project changed since")
+    ),
     responses(
-        (status = 200, description = "Project deleted successfully"),
+        (status = 200, description = "Project moved to the trash"),
         (status = 404, description = "Project not found"),
+        (status = 412, description = "Project was modified since the If-Match version"),
         (status = 401, description = "Unauthorized - Missing or invalid token"),
         (status = 403, description = "Forbidden - Not an admin user"),
         (status = 500, description = "Internal server error")
@@ -25,33 +42,40 @@ use validator::Validate;
     tag = "projects"
 ))]
 pub async fn delete_project(
-    State(db): State<Arc<MongoClient>>,
+    State(state): State<Arc<ProjectsState>>,
     Extension(user): Extension<UserInfo>,
     Path(slug): Path<String>,
-) -> Result<Json<Value>, StatusCode> {
+    headers: HeaderMap,
+) -> ApiResult<Json<Value>> {
     tracing::info!("Admin {} deleting project: {}", user.email, slug);
-    match db.delete_by_slug("projects", &slug).await {
-        Ok(true) => {
-            tracing::info!("Project '{}' deleted by {}", slug, user.email);
-            Ok(Json(json!({"message": "Project deleted successfully"})))
-        }
-        Ok(false) => Err(StatusCode::NOT_FOUND),
-        Err(e) => {
-            tracing::error!("Failed to delete project: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
+
+    if !state
+        .repo
+        .trash(&slug, &user.email, &if_match(&headers))
+        .await?
+    {
+        return Err(ApiError::NotFound(format!("Project '{}' not found", slug)));
     }
+
+    tracing::info!("Project '{}' moved to trash by {}", slug, user.email);
+    Ok(Json(json!({"message": "Project moved to trash"})))
 }
 
-/// Update project by slug (Admin only)
+/// Update project by slug, keeping the previous version as a revision (Admin only)
 #[cfg_attr(feature = "swagger", utoipa::path(
     put,
     path = "/api/v1/projects/{slug}",
     request_body = ProjectUpdate,
+    params(
+        ("If-Match" = Option<String>, Header, description = "ETag from the last read, the update fails with 412 if the project changed since")
+    ),
     responses(
-        (status = 200, description = "Project updated successfully"),
+        (status = 200, description = "Project updated successfully",
+            headers(("ETag" = String, description = "New version of the project"))),
         (status = 404, description = "Project not found"),
+        (status = 412, description = "Project was modified since the If-Match version"),
         (status = 400, description = "Invalid request body"),
+        (status = 409, description = "New slug already in use"),
         (status = 401, description = "Unauthorized - Missing or invalid token"),
         (status = 403, description = "Forbidden - Not an admin user"),
         (status = 500, description = "Internal server error")
@@ -62,33 +86,205 @@ pub async fn delete_project(
     tag = "projects"
 ))]
 pub async fn update_project(
-    State(db): State<Arc<MongoClient>>,
+    State(state): State<Arc<ProjectsState>>,
     Extension(user): Extension<UserInfo>,
     Path(slug): Path<String>,
-    Json(project): Json<ProjectUpdate>,
-) -> Result<Json<Value>, StatusCode> {
+    headers: HeaderMap,
+    Json(mut project): Json<ProjectUpdate>,
+) -> ApiResult<Response> {
     tracing::info!("Admin {} updating project: {}", user.email, slug);
 
     // Validate input
     project.validate().map_err(|e| {
         tracing::warn!("Validation failed for project update: {}", e);
-        StatusCode::BAD_REQUEST
+        ApiError::BadRequest(e.to_string())
     })?;
+    project.retire_draft_visibility();
+
+    project.slug = state
+        .slugs
+        .rename(ContentKind::Project, &slug, &project.slug)
+        .await?;
+    let embedding_text = project.embedding_text();
+    let stored_slug = project.slug.clone();
 
-    let update_doc = mongodb::bson::to_document(&project).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let before = state
+        .repo
+        .update(&slug, project, &if_match(&headers))
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Project '{}' not found", slug)))?;
+    state
+        .revisions
+        .record(Revision::capture(
+            ContentKind::Project,
+            &before,
+            &user.email,
+        )?)
+        .await?;
 
-    match db.update_by_slug("projects", &slug, update_doc).await {
-        Ok(true) => {
-            tracing::info!("Project '{}' updated by {}", slug, user.email);
-            Ok(Json(json!({
-                "message": "Project updated successfully",
-                "slug": slug
-            })))
+    state
+        .slugs
+        .record_rename(ContentKind::Project, &slug, &stored_slug)
+        .await?;
+
+    let (embedding, embedding_outcome) =
+        embed_content(state.embedder.as_ref(), &embedding_text).await;
+    match embedding {
+        Some(embedding) => {
+            state
+                .repo
+                .set_embedding(&stored_slug, embedding.values, &embedding.model)
+                .await?;
+        }
+        None => {
+            state.repo.clear_embedding(&stored_slug).await?;
         }
-        Ok(false) => Err(StatusCode::NOT_FOUND),
-        Err(e) => {
-            tracing::error!("Failed to update project: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    tracing::info!("Project '{}' updated by {}", slug, user.email);
+    Ok((
+        [(header::ETAG, etag(before.id, before.version + 1))],
+        Json(json!({
+            "message": "Project updated successfully",
+            "slug": stored_slug,
+            "embedding": embedding_outcome
+        })),
+    )
+        .into_response())
+}
+
+/// Partially update project by slug with a merge patch or JSON Patch (Admin only)
+/// Only the changed fields are written; without If-Match the patch still only
+/// applies to the version it was validated against
+#[cfg_attr(feature = "swagger", utoipa::path(
+    patch,
+    path = "/api/v1/projects/{slug}",
+    request_body(
+        description = "RFC 7386 merge patch or RFC 6902 JSON Patch of the ProjectUpdate fields",
+        content(
+            (Object = "application/merge-patch+json"),
+            (Object = "application/json-patch+json")
+        )
+    ),
+    params(
+        ("If-Match" = Option<String>, Header, description = "ETag from the last read, the patch fails with 412 if the project changed since")
+    ),
+    responses(
+        (status = 200, description = "Project patched",
+            headers(("ETag" = String, description = "New version of the project"))),
+        (status = 400, description = "Malformed patch document"),
+        (status = 404, description = "Project not found"),
+        (status = 409, description = "A JSON Patch test failed, or the new slug is already in use"),
+        (status = 412, description = "Project was modified since the If-Match version"),
+        (status = 415, description = "Content-Type is not a supported patch format"),
+        (status = 422, description = "Patched project fails validation"),
+        (status = 401, description = "Unauthorized - Missing or invalid token"),
+        (status = 403, description = "Forbidden - Not an admin user"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("google_oauth" = ["openid", "email", "profile"])
+    ),
+    tag = "projects"
+))]
+pub async fn patch_project(
+    State(state): State<Arc<ProjectsState>>,
+    Extension(user): Extension<UserInfo>,
+    Path(slug): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ApiResult<Response> {
+    tracing::info!("Admin {} patching project: {}", user.email, slug);
+
+    let format = PatchFormat::from_headers(&headers)?;
+    let current = state
+        .repo
+        .get_by_slug(&slug)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Project '{}' not found", slug)))?;
+    let check = match if_match(&headers) {
+        VersionCheck::Any => VersionCheck::OneOf(
+            current
+                .id
+                .map(|id| (id, current.version))
+                .into_iter()
+                .collect(),
+        ),
+        check => check,
+    };
+
+    let original = ProjectUpdate::from(current.clone());
+    let mut update = format.apply(&original, &body).inspect_err(|e| {
+        tracing::warn!("Rejected patch of project '{}': {}", slug, e);
+    })?;
+    update.retire_draft_visibility();
+    update.slug = state
+        .slugs
+        .rename(ContentKind::Project, &slug, &update.slug)
+        .await?;
+    let changes = FieldChanges::between(&original, &update)?;
+    if changes.is_empty() {
+        return Ok((
+            [(header::ETAG, etag(current.id, current.version))],
+            Json(json!({
+                "message": "Project unchanged",
+                "slug": slug,
+                "embedding": null
+            })),
+        )
+            .into_response());
+    }
+
+    let before = state
+        .repo
+        .patch(&slug, &changes, &check)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Project '{}' not found", slug)))?;
+    state
+        .revisions
+        .record(Revision::capture(
+            ContentKind::Project,
+            &before,
+            &user.email,
+        )?)
+        .await?;
+    state
+        .slugs
+        .record_rename(ContentKind::Project, &slug, &update.slug)
+        .await?;
+
+    // Re-embed only when the embedded text changed
+    let mut embedding_outcome = None;
+    if update.embedding_text() != original.embedding_text() {
+        let (embedding, outcome) =
+            embed_content(state.embedder.as_ref(), &update.embedding_text()).await;
+        match embedding {
+            Some(embedding) => {
+                state
+                    .repo
+                    .set_embedding(&update.slug, embedding.values, &embedding.model)
+                    .await?;
+            }
+            None => {
+                state.repo.clear_embedding(&update.slug).await?;
+            }
         }
+        embedding_outcome = Some(outcome);
     }
+
+    tracing::info!(
+        "Project '{}' patched by {}: {:?}",
+        slug,
+        user.email,
+        changes.paths()
+    );
+    Ok((
+        [(header::ETAG, etag(before.id, before.version + 1))],
+        Json(json!({
+            "message": "Project patched successfully",
+            "slug": update.slug,
+            "embedding": embedding_outcome
+        })),
+    )
+        .into_response())
 }
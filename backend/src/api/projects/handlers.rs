@@ -1,96 +1,120 @@
-use crate::{database::MongoClient, models::Project};
+use super::ProjectsState;
+use crate::{
+    api::{
+        chat::embed_content,
+        listing::{ListParams, ListResponse},
+    },
+    auth::middleware::admin_user,
+    error::{ApiError, ApiResult},
+    models::{ContentKind, Project, ProjectResponse},
+};
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Redirect, Response},
     Json,
 };
-use mongodb::bson::{doc};
 use serde_json::{json, Value};
 use std::sync::Arc;
 use validator::Validate;
 
-/// List all projects
+/// List projects with filtering, sorting and cursor pagination
 #[cfg_attr(feature = "swagger", utoipa::path(
     get,
     path = "/api/v1/projects",
+    params(ListParams),
     responses(
-        (status = 200, description = "List of projects retrieved successfully"),
+        (status = 200, description = "Page of projects (`{ items, total, nextCursor }`, or a bare array with `format=array`)", body = Vec<ProjectResponse>,
+            headers(
+                ("ETag" = String, description = "Changes whenever a listed project or the pagination changes"),
+                ("Last-Modified" = String, description = "When the server first served this ETag"),
+                ("Cache-Control" = String, description = "Configured with HTTP_CACHE_CONTROL")
+            )),
+        (status = 304, description = "Not modified since the If-None-Match / If-Modified-Since validators"),
+        (status = 400, description = "Invalid query parameters"),
         (status = 500, description = "Internal server error")
     ),
     tag = "projects"
 ))]
-pub async fn list_projects(State(db): State<Arc<MongoClient>>) -> Result<Json<Value>, StatusCode> {
-    let collection = db.projects();
-
-    let cursor_result = collection.find(doc! {}).await;
-
-    match cursor_result {
-        Ok(mut cursor) => {
-            let mut projects = Vec::new();
-            let mut success_count = 0;
-            let mut error_count = 0;
-
-            use futures::stream::StreamExt;
-            while let Some(result) = cursor.next().await {
-                match result {
-                    Ok(doc) => {
-                        projects.push(doc);
-                        success_count += 1;
-                    }
-                    Err(e) => {
-                        error_count += 1;
-                        tracing::warn!(
-                            "Failed to deserialize project document {}: {}",
-                            error_count,
-                            e
-                        );
-                    }
-                }
-            }
+pub async fn list_projects(
+    State(state): State<Arc<ProjectsState>>,
+    Query(params): Query<ListParams>,
+    headers: HeaderMap,
+) -> ApiResult<Response> {
+    let (query, format) = params.into_query(ContentKind::Project)?;
 
-            tracing::info!(
-                "Projects retrieval: {} successful, {} failed",
-                success_count,
-                error_count
-            );
-
-            // Return direct array for consistency with frontend expectations
-            Ok(Json(json!(projects)))
-        }
-        Err(error) => {
-            tracing::error!("Failed to fetch projects: {}", error);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
+    let page = state.repo.find(&query).await?;
+    let validators = state.http_cache.list_validators(
+        ContentKind::Project,
+        page.items
+            .iter()
+            .map(|project| (project.id, project.version)),
+        page.total,
+        page.next_offset,
+    );
+    if let Some(not_modified) = state.http_cache.not_modified(&headers, &validators) {
+        return Ok(not_modified);
     }
+
+    let list = ListResponse::new(page.map(ProjectResponse::from), format);
+    Ok(state.http_cache.fresh(&validators, Json(list)))
 }
 
 /// Get single project by slug
+/// Admins may preview unpublished projects by sending their token
 #[cfg_attr(feature = "swagger", utoipa::path(
     get,
     path = "/api/v1/projects/{slug}",
     responses(
-        (status = 200, description = "Project retrieved successfully"),
-        (status = 404, description = "Project not found")
+        (status = 200, description = "Project retrieved successfully", body = ProjectResponse,
+            headers(
+                ("ETag" = String, description = "Version of the project, send it back as If-Match"),
+                ("Last-Modified" = String, description = "When the server first served this ETag"),
+                ("Cache-Control" = String, description = "Configured with HTTP_CACHE_CONTROL")
+            )),
+        (status = 304, description = "Not modified since the If-None-Match / If-Modified-Since validators"),
+        (status = 308, description = "Project was renamed, `Location` holds its current URL"),
+        (status = 404, description = "Project not found or not published")
     ),
     tag = "projects"
 ))]
 pub async fn get_project(
-    State(db): State<Arc<MongoClient>>,
+    State(state): State<Arc<ProjectsState>>,
     Path(slug): Path<String>,
-) -> Result<Json<Value>, StatusCode> {
-    let collection = db.projects();
+    headers: HeaderMap,
+) -> ApiResult<Response> {
+    // Unlisted projects are served to anyone with the URL, unpublished ones to admins only
+    let project = state.repo.get_by_slug(&slug).await?.filter(|project| {
+        project.is_published() || admin_user(&state.auth_config, &headers).is_some()
+    });
+    if let Some(project) = project {
+        let validators = state
+            .http_cache
+            .document_validators(project.id, project.version);
+        if !project.is_published() {
+            return Ok(state
+                .http_cache
+                .private(&validators, Json(ProjectResponse::from(project))));
+        }
+        if let Some(not_modified) = state.http_cache.not_modified(&headers, &validators) {
+            return Ok(not_modified);
+        }
+        return Ok(state
+            .http_cache
+            .fresh(&validators, Json(ProjectResponse::from(project))));
+    }
 
-    match collection.find_one(doc! { "slug": slug }).await {
-        Ok(Some(project)) => Ok(Json(json!(project))),
-        Ok(None) => Err(StatusCode::NOT_FOUND),
-        Err(error) => {
-            tracing::error!("Database error: {}", error);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+    // Renamed projects keep their old URLs working
+    match state.slugs.resolve(ContentKind::Project, &slug).await? {
+        Some(current) => {
+            Ok(Redirect::permanent(&format!("/api/v1/projects/{}", current)).into_response())
         }
+        None => Err(ApiError::NotFound(format!("Project '{}' not found", slug))),
     }
 }
 
 /// Create new project (Admin only)
+/// Goes live at once unless `publication` is `draft`, or `scheduled` with a `publish_at`
 #[cfg_attr(feature = "swagger", utoipa::path(
     post,
     path = "/api/v1/projects",
@@ -98,7 +122,8 @@ pub async fn get_project(
         (status = 201, description = "Project created successfully"),
         (status = 400, description = "Invalid request body"),
         (status = 401, description = "Unauthorized - Missing or invalid token"),
-        (status = 403, description = "Forbidden - Not an admin user")
+        (status = 403, description = "Forbidden - Not an admin user"),
+        (status = 409, description = "Slug already in use")
     ),
     security(
         ("google_oauth" = ["openid", "email", "profile"])
@@ -106,37 +131,48 @@ pub async fn get_project(
     tag = "projects"
 ))]
 pub async fn create_project(
-    State(db): State<Arc<MongoClient>>,
-    Json(project): Json<Project>,
-) -> Result<(StatusCode, Json<Value>), StatusCode> {
+    State(state): State<Arc<ProjectsState>>,
+    Json(mut project): Json<Project>,
+) -> ApiResult<(StatusCode, Json<Value>)> {
+    project.retire_draft_visibility();
+
     // Validate input
     project.validate().map_err(|e| {
         tracing::warn!("Validation failed for project creation: {}", e);
-        StatusCode::BAD_REQUEST
+        ApiError::BadRequest(e.to_string())
     })?;
 
-    let collection = db.projects();
+    // Client slug is only a hint, uniqueness is decided here
+    let requested_slug = match project.slug.trim() {
+        "" => project.title.clone(),
+        slug => slug.to_string(),
+    };
+    project.slug = state
+        .slugs
+        .generate(ContentKind::Project, &requested_slug)
+        .await?;
 
-    let doc = mongodb::bson::to_document(&project).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let (embedding, embedding_outcome) =
+        embed_content(state.embedder.as_ref(), &project.embedding_text()).await;
+    match embedding {
+        Some(embedding) => project.set_embedding(embedding.values, embedding.model),
+        None => project.clear_embedding(),
+    }
 
-    match collection.insert_one(doc).await {
-        Ok(result) => {
-            let inserted_id = result
-                .inserted_id
-                .as_object_id()
-                .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+    // Versions and trash state are server-managed, whatever the client sent starts over
+    project.version = 0;
+    project.deleted_at = None;
+    project.deleted_by = None;
+    let slug = project.slug.clone();
+    let inserted_id = state.repo.create(project).await?;
 
-            Ok((
-                StatusCode::CREATED,
-                Json(json!({
-                    "id": inserted_id.to_hex(),
-                    "message": "Project created successfully"
-                })),
-            ))
-        }
-        Err(error) => {
-            tracing::error!("Failed to create project: {}", error);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
-    }
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "id": inserted_id.to_hex(),
+            "slug": slug,
+            "message": "Project created successfully",
+            "embedding": embedding_outcome
+        })),
+    ))
 }
@@ -1,17 +1,48 @@
 pub mod delete_update;
 pub mod handlers;
 
-use crate::{auth::AuthConfig, database::MongoClient};
+use crate::{
+    api::{chat::Embedder, conditional::HttpCache},
+    auth::AuthConfig,
+    repositories::{ProjectRepository, Repositories, RevisionRepository},
+    services::SlugService,
+};
 use axum::{
     middleware,
-    routing::{delete, get, post, put},
+    routing::{delete, get, patch, post, put},
     Router,
 };
 use std::sync::Arc;
 
+/// Shared state for project handlers
+pub struct ProjectsState {
+    pub repo: Arc<dyn ProjectRepository>,
+    pub embedder: Arc<dyn Embedder>,
+    pub slugs: SlugService,
+    pub revisions: Arc<dyn RevisionRepository>,
+    pub http_cache: Arc<HttpCache>,
+    /// Recognizes admins previewing unpublished content on the public routes
+    pub auth_config: Arc<AuthConfig>,
+}
+
 /// Build projects router with CRUD endpoints
 /// POST operations require admin authentication
-pub fn router(db_client: Arc<MongoClient>, auth_config: Arc<AuthConfig>) -> Router {
+/// Writes embed the project content for RAG retrieval
+pub fn router(
+    repositories: Repositories,
+    embedder: Arc<dyn Embedder>,
+    auth_config: Arc<AuthConfig>,
+    http_cache: Arc<HttpCache>,
+) -> Router {
+    let state = Arc::new(ProjectsState {
+        repo: repositories.projects.clone(),
+        embedder,
+        revisions: repositories.revisions.clone(),
+        http_cache,
+        auth_config: auth_config.clone(),
+        slugs: SlugService::new(repositories),
+    });
+
     Router::new()
         .route("/", get(handlers::list_projects))
         .route(
@@ -32,9 +63,620 @@ pub fn router(db_client: Arc<MongoClient>, auth_config: Arc<AuthConfig>) -> Rout
         .route(
             "/{slug}",
             put(delete_update::update_project).layer(middleware::from_fn_with_state(
+                auth_config.clone(),
+                crate::auth::middleware::require_admin,
+            )),
+        )
+        .route(
+            "/{slug}",
+            patch(delete_update::patch_project).layer(middleware::from_fn_with_state(
                 auth_config,
                 crate::auth::middleware::require_admin,
             )),
         )
-        .with_state(db_client)
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::{
+            conditional::HttpCache,
+            test_support::{body_json, test_auth, StubEmbedder},
+        },
+        models::{Project, ProjectVisibility, PublicationStatus},
+        repositories::InMemoryProjectRepository,
+    };
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    fn test_router(repo: Arc<InMemoryProjectRepository>, fail_embedding: bool) -> Router {
+        let (auth, _) = test_auth();
+        let embedder = if fail_embedding {
+            StubEmbedder::failing()
+        } else {
+            StubEmbedder::ok()
+        };
+        let repositories = Repositories {
+            projects: repo,
+            ..Repositories::in_memory()
+        };
+        router(repositories, embedder, auth, Arc::new(HttpCache::default()))
+    }
+
+    fn sample_project() -> Value {
+        json!({
+            "slug": "rust-api",
+            "date": "2024",
+            "title": "Rust API",
+            "description": null,
+            "technologies": ["Rust", "Axum"],
+            "features": [],
+            "githubUrl": "https://github.com/example/rust-api",
+            "ReportUrl": null,
+            "demoUrl": null,
+            "youtubeUrl": null,
+            "embedding": [0.1, 0.2],
+            "images": null
+        })
+    }
+
+    #[tokio::test]
+    async fn test_create_and_read_project_without_mongodb() {
+        let (_, token) = test_auth();
+        let repo = Arc::new(InMemoryProjectRepository::default());
+        let app = test_router(repo.clone(), false);
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::post("/")
+                    .header("Authorization", format!("Bearer {}", token))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(sample_project().to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(
+            body_json(response).await["embedding"]["status"],
+            "generated"
+        );
+
+        // Server-side embedding replaces whatever the client sent
+        let stored = repo.get_by_slug("rust-api").await.unwrap().unwrap();
+        assert_eq!(stored.embedding, Some(vec![0.5; 4]));
+
+        let response = app
+            .clone()
+            .oneshot(Request::get("/").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let page = body_json(response).await;
+        assert_eq!(page["total"], 1);
+        assert!(page["nextCursor"].is_null());
+
+        // Public DTO: hex string id under its legacy name, embedding never exposed
+        let project = &page["items"][0];
+        assert!(project["_id"].as_str().is_some_and(|id| id.len() == 24));
+        assert_eq!(project["githubUrl"], "https://github.com/example/rust-api");
+        assert!(project.get("ReportUrl").is_some());
+        assert!(project.get("embedding").is_none());
+
+        let response = app
+            .oneshot(Request::get("/missing").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_list_filters_sorts_and_paginates() {
+        let repo = Arc::new(InMemoryProjectRepository::default());
+        for (slug, date, tech) in [
+            ("alpha", "March 2023", "Rust"),
+            ("beta", "2024", "rust"),
+            ("gamma", "Jan 2024", "Python"),
+            ("delta", "2021 - 2024", "Rust"),
+        ] {
+            let mut project: Project = serde_json::from_value(sample_project()).unwrap();
+            project.slug = slug.to_string();
+            project.title = slug.to_string();
+            project.date = Some(date.parse().unwrap());
+            project.technologies = vec![tech.to_string()];
+            repo.create(project).await.unwrap();
+        }
+        let app = test_router(repo, false);
+
+        let get = |uri: &str| {
+            app.clone()
+                .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        };
+
+        let page = body_json(get("/?technology=RUST&sort=title&limit=2").await.unwrap()).await;
+        assert_eq!(page["total"], 3);
+        assert_eq!(page["items"][0]["slug"], "alpha");
+        assert_eq!(page["items"][1]["slug"], "beta");
+        let cursor = page["nextCursor"].as_str().unwrap().to_string();
+
+        let uri = format!("/?technology=rust&sort=title&limit=2&cursor={}", cursor);
+        let page = body_json(get(&uri).await.unwrap()).await;
+        assert_eq!(page["items"][0]["slug"], "delta");
+        assert!(page["nextCursor"].is_null());
+
+        // Ranges match every year they span
+        let page = body_json(get("/?year=2024&sort=-title").await.unwrap()).await;
+        assert_eq!(page["items"][0]["slug"], "gamma");
+        assert_eq!(page["items"][1]["date"], "2021 – 2024");
+        assert_eq!(page["items"][1]["dateDetail"]["end"]["year"], 2024);
+        assert_eq!(page["total"], 3);
+
+        let page = body_json(get("/?sort=date").await.unwrap()).await;
+        let slugs: Vec<_> = page["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|item| item["slug"].as_str().unwrap())
+            .collect();
+        assert_eq!(slugs, ["delta", "alpha", "beta", "gamma"]);
+
+        // Legacy clients keep receiving the full bare array
+        let legacy = body_json(get("/?format=array").await.unwrap()).await;
+        assert_eq!(legacy.as_array().unwrap().len(), 4);
+
+        let response = get("/?issuer=Coursera").await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_slugs_are_unique_and_renames_redirect() {
+        let (_, token) = test_auth();
+        let repo = Arc::new(InMemoryProjectRepository::default());
+        let app = test_router(repo.clone(), false);
+
+        let send = |method: &str, uri: &str, body: Option<Value>| {
+            let request = Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("Authorization", format!("Bearer {}", token))
+                .header("Content-Type", "application/json");
+            let body = body.map_or_else(Body::empty, |b| Body::from(b.to_string()));
+            app.clone().oneshot(request.body(body).unwrap())
+        };
+
+        // Same requested slug twice: the second one is suffixed
+        let response = send("POST", "/", Some(sample_project())).await.unwrap();
+        assert_eq!(body_json(response).await["slug"], "rust-api");
+        let response = send("POST", "/", Some(sample_project())).await.unwrap();
+        assert_eq!(body_json(response).await["slug"], "rust-api-2");
+
+        // Missing slug is derived from the title
+        let mut untitled = sample_project();
+        untitled["slug"] = json!("");
+        untitled["title"] = json!("Vector Search Engine");
+        let response = send("POST", "/", Some(untitled)).await.unwrap();
+        assert_eq!(body_json(response).await["slug"], "vector-search-engine");
+
+        // Renaming onto another project's slug is a conflict, not a silent suffix
+        let mut update = sample_project();
+        update["slug"] = json!("rust-api-2");
+        let response = send("PUT", "/rust-api", Some(update.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        update["slug"] = json!("Rust Web API");
+        let response = send("PUT", "/rust-api", Some(update)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_json(response).await["slug"], "rust-web-api");
+        assert!(repo.get_by_slug("rust-web-api").await.unwrap().is_some());
+
+        let response = send("GET", "/rust-api", None).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(
+            response.headers()["location"],
+            "/api/v1/projects/rust-web-api"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stale_if_match_is_rejected() {
+        let (_, token) = test_auth();
+        let repo = Arc::new(InMemoryProjectRepository::default());
+        let app = test_router(repo.clone(), false);
+
+        let send = |method: &str, if_match: Option<&str>| {
+            let mut request = Request::builder()
+                .method(method)
+                .uri("/rust-api")
+                .header("Authorization", format!("Bearer {}", token))
+                .header("Content-Type", "application/json");
+            if let Some(tag) = if_match {
+                request = request.header("If-Match", tag);
+            }
+            let body = match method {
+                "PUT" => Body::from(sample_project().to_string()),
+                _ => Body::empty(),
+            };
+            app.clone().oneshot(request.body(body).unwrap())
+        };
+
+        let id = repo
+            .create(serde_json::from_value(sample_project()).unwrap())
+            .await
+            .unwrap();
+        let tag = |version: i64| format!("\"{}-{}\"", id.to_hex(), version);
+        let response = send("GET", None).await.unwrap();
+        let read_tag = response.headers()["etag"].to_str().unwrap().to_string();
+        assert_eq!(read_tag, tag(0));
+
+        // First tab saves, the second one still holds the old tag
+        let response = send("PUT", Some(&read_tag)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["etag"], tag(1).as_str());
+        let response = send("PUT", Some(&read_tag)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+        let response = send("DELETE", Some(&read_tag)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+
+        let response = send("DELETE", Some(&tag(1))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        // Gone is not a failed precondition
+        let response = send("PUT", Some(&tag(2))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        // A new project under the purged one's slug does not match its tags
+        repo.purge_trash(chrono::Utc::now()).await.unwrap();
+        repo.create(serde_json::from_value(sample_project()).unwrap())
+            .await
+            .unwrap();
+        let response = send("PUT", Some(&read_tag)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+        let response = app
+            .clone()
+            .oneshot(
+                Request::get("/rust-api")
+                    .header("If-None-Match", &read_tag)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_conditional_get_until_a_write() {
+        let (_, token) = test_auth();
+        let repo = Arc::new(InMemoryProjectRepository::default());
+        let app = test_router(repo.clone(), false);
+        let id = repo
+            .create(serde_json::from_value(sample_project()).unwrap())
+            .await
+            .unwrap();
+        let read_tag = format!("\"{}-0\"", id.to_hex());
+
+        let get = |uri: &str, if_none_match: Option<&str>| {
+            let mut request = Request::get(uri);
+            if let Some(tag) = if_none_match {
+                request = request.header("If-None-Match", tag);
+            }
+            app.clone().oneshot(request.body(Body::empty()).unwrap())
+        };
+
+        let response = get("/", None).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["cache-control"], "public, max-age=60");
+        assert!(response.headers().contains_key("last-modified"));
+        let list_tag = response.headers()["etag"].to_str().unwrap().to_string();
+
+        let response = get("/", Some(&list_tag)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        let response = get("/rust-api", Some(&read_tag)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::put("/rust-api")
+                    .header("Authorization", format!("Bearer {}", token))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(sample_project().to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = get("/", Some(&list_tag)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = get("/rust-api", Some(&read_tag)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_patch_merges_and_validates() {
+        let (_, token) = test_auth();
+        let repo = Arc::new(InMemoryProjectRepository::default());
+        let app = test_router(repo.clone(), false);
+        let mut project = sample_project();
+        project["description"] = json!({ "overview": "REST API", "impact": "Fast" });
+        let id = repo
+            .create(serde_json::from_value(project).unwrap())
+            .await
+            .unwrap();
+        let tag = |version: i64| format!("\"{}-{}\"", id.to_hex(), version);
+
+        let send = |content_type: &str, if_match: Option<&str>, body: Value| {
+            let mut request = Request::patch("/rust-api")
+                .header("Authorization", format!("Bearer {}", token))
+                .header("Content-Type", content_type);
+            if let Some(tag) = if_match {
+                request = request.header("If-Match", tag);
+            }
+            app.clone()
+                .oneshot(request.body(Body::from(body.to_string())).unwrap())
+        };
+
+        let response = send(
+            "application/merge-patch+json",
+            Some(&tag(0)),
+            json!({ "description": { "impact": "Faster" } }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["etag"], tag(1).as_str());
+        assert_eq!(
+            body_json(response).await["embedding"]["status"],
+            "generated"
+        );
+        let stored = repo.get_by_slug("rust-api").await.unwrap().unwrap();
+        let description = stored.description.unwrap();
+        assert_eq!(description.impact.as_deref(), Some("Faster"));
+        assert_eq!(description.overview.as_deref(), Some("REST API"));
+
+        let response = send(
+            "application/json-patch+json",
+            None,
+            json!([{ "op": "replace", "path": "/title", "value": "" }]),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let response = send("application/json", None, json!({ "title": "Other" }))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+        let response = send(
+            "application/merge-patch+json",
+            Some(&tag(0)),
+            json!({ "title": "Other" }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+    }
+
+    #[tokio::test]
+    async fn test_create_never_starts_in_the_trash() {
+        let (_, token) = test_auth();
+        let repo = Arc::new(InMemoryProjectRepository::default());
+        let app = test_router(repo.clone(), false);
+
+        let mut project = sample_project();
+        project["deleted_at"] = json!({ "$date": { "$numberLong": "1700000000000" } });
+        project["deleted_by"] = json!("someone@example.com");
+        let response = app
+            .clone()
+            .oneshot(
+                Request::post("/")
+                    .header("Authorization", format!("Bearer {}", token))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(project.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let stored = repo.get_by_slug("rust-api").await.unwrap().unwrap();
+        assert!(!stored.is_deleted());
+        assert_eq!(stored.deleted_by, None);
+        let response = app
+            .oneshot(Request::get("/rust-api").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_create_project_requires_admin_token() {
+        let app = test_router(Arc::new(InMemoryProjectRepository::default()), false);
+
+        let response = app
+            .oneshot(
+                Request::post("/")
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(sample_project().to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_failed_embedding_is_reported_not_dropped() {
+        let (_, token) = test_auth();
+        let repo = Arc::new(InMemoryProjectRepository::default());
+        let app = test_router(repo.clone(), true);
+
+        let response = app
+            .oneshot(
+                Request::post("/")
+                    .header("Authorization", format!("Bearer {}", token))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(sample_project().to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let body = body_json(response).await;
+        assert_eq!(body["embedding"]["status"], "failed");
+        assert!(body["embedding"]["error"]
+            .as_str()
+            .unwrap()
+            .contains("unavailable"));
+
+        let stored = repo.get_by_slug("rust-api").await.unwrap().unwrap();
+        assert!(stored.embedding.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_failed_reembedding_drops_the_stale_vector() {
+        let (_, token) = test_auth();
+        let repo = Arc::new(InMemoryProjectRepository::default());
+        let app = test_router(repo.clone(), true);
+        repo.create(serde_json::from_value(sample_project()).unwrap())
+            .await
+            .unwrap();
+
+        let send = |method: &str, content_type: &str, body: Value| {
+            let request = Request::builder()
+                .method(method)
+                .uri("/rust-api")
+                .header("Authorization", format!("Bearer {}", token))
+                .header("Content-Type", content_type)
+                .body(Body::from(body.to_string()))
+                .unwrap();
+            app.clone().oneshot(request)
+        };
+        let embedding = || async {
+            repo.get_by_slug("rust-api")
+                .await
+                .unwrap()
+                .unwrap()
+                .embedding
+        };
+
+        let mut project = sample_project();
+        project["title"] = json!("Rust API v2");
+        let response = send("PUT", "application/json", project).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_json(response).await["embedding"]["status"], "failed");
+        assert!(embedding().await.is_none());
+
+        repo.set_embedding("rust-api", vec![0.1, 0.2], "old-model")
+            .await
+            .unwrap();
+        let response = send(
+            "PATCH",
+            "application/merge-patch+json",
+            json!({ "title": "Rust API v3" }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(embedding().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_put_keeps_placement_it_leaves_out() {
+        let (_, token) = test_auth();
+        let repo = Arc::new(InMemoryProjectRepository::default());
+        let app = test_router(repo.clone(), false);
+        let mut project: Project = serde_json::from_value(sample_project()).unwrap();
+        project.featured = true;
+        project.order = 3;
+        project.visibility = ProjectVisibility::Unlisted;
+        repo.create(project).await.unwrap();
+
+        // The dashboard form and older clients send none of them, and `order` is ignored
+        let mut update = sample_project();
+        update["title"] = json!("Rust API v2");
+        update["order"] = json!(0);
+        let put = |body: Value| {
+            Request::put("/rust-api")
+                .header("Authorization", format!("Bearer {}", token))
+                .header("Content-Type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+        let response = app.clone().oneshot(put(update.clone())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let stored = repo.get_by_slug("rust-api").await.unwrap().unwrap();
+        assert_eq!(stored.title, "Rust API v2");
+        assert!(stored.featured);
+        assert_eq!(stored.order, 3);
+        assert_eq!(stored.visibility, ProjectVisibility::Unlisted);
+
+        update["featured"] = json!(false);
+        update["visibility"] = json!("public");
+        let response = app.oneshot(put(update)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let stored = repo.get_by_slug("rust-api").await.unwrap().unwrap();
+        assert!(!stored.featured);
+        assert_eq!(stored.order, 3);
+        assert_eq!(stored.visibility, ProjectVisibility::Public);
+    }
+
+    #[tokio::test]
+    async fn test_legacy_draft_visibility_becomes_a_draft() {
+        let (_, token) = test_auth();
+        let repo = Arc::new(InMemoryProjectRepository::default());
+        let app = test_router(repo.clone(), false);
+        let send = |method: &str, uri: &str, content_type: &str, body: Value| {
+            let request = Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("Authorization", format!("Bearer {}", token))
+                .header("Content-Type", content_type)
+                .body(Body::from(body.to_string()))
+                .unwrap();
+            app.clone().oneshot(request)
+        };
+        let stored = || async { repo.get_by_slug("rust-api").await.unwrap().unwrap() };
+
+        let mut project = sample_project();
+        project["visibility"] = json!("draft");
+        let response = send("POST", "/", "application/json", project.clone())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let created = stored().await;
+        assert_eq!(created.visibility, ProjectVisibility::Public);
+        assert_eq!(created.publication, PublicationStatus::Draft);
+
+        for (method, content_type, body) in [
+            ("PUT", "application/json", project),
+            (
+                "PATCH",
+                "application/merge-patch+json",
+                json!({ "visibility": "draft" }),
+            ),
+        ] {
+            repo.set_publication("rust-api", PublicationStatus::Published, None)
+                .await
+                .unwrap();
+            assert!(stored().await.is_published());
+
+            let response = send(method, "/rust-api", content_type, body).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK, "{}", method);
+            let updated = stored().await;
+            assert_eq!(updated.visibility, ProjectVisibility::Public);
+            assert_eq!(updated.publication, PublicationStatus::Draft, "{}", method);
+        }
+    }
 }
@@ -0,0 +1,376 @@
+use crate::{
+    error::{ApiError, ApiResult},
+    models::{Certificate, Project},
+};
+use serde::Serialize;
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+/// Longest accepted query string, in characters
+pub const MAX_QUERY_CHARS: usize = 200;
+/// Most words and phrases considered from one query
+pub const MAX_CLAUSES: usize = 8;
+/// Characters of context kept around the first match in a snippet
+const SNIPPET_CONTEXT: usize = 60;
+/// Prefix matches count for less than whole-word matches
+const PREFIX_FACTOR: f64 = 0.6;
+/// Phrase matches count for more than the same words found separately
+const PHRASE_FACTOR: f64 = 1.5;
+
+/// One word, prefix (`rus*`) or quoted phrase (`"machine learning"`) of a search query
+#[derive(Debug, Clone, PartialEq)]
+pub struct Clause {
+    tokens: Vec<String>,
+    prefix: bool,
+}
+
+/// Parsed search query, every clause must match for a document to be a hit
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchQuery {
+    clauses: Vec<Clause>,
+}
+
+impl SearchQuery {
+    /// Parse `q` into word, prefix and phrase clauses
+    pub fn parse(q: &str) -> ApiResult<Self> {
+        let q = q.trim();
+        if q.is_empty() {
+            return Err(ApiError::BadRequest("Query must not be empty".to_string()));
+        }
+        if q.chars().count() > MAX_QUERY_CHARS {
+            return Err(ApiError::BadRequest(format!(
+                "Query must be at most {} characters",
+                MAX_QUERY_CHARS
+            )));
+        }
+
+        let mut clauses: Vec<Clause> = Vec::new();
+        // Odd segments of a split on '"' are inside quotes
+        for (index, segment) in q.split('"').enumerate() {
+            let quoted = index % 2 == 1;
+            let parts: Vec<&str> = if quoted {
+                vec![segment]
+            } else {
+                segment.split_whitespace().collect()
+            };
+
+            for part in parts {
+                let prefix = !quoted && part.ends_with('*');
+                let tokens: Vec<String> = tokenize(part).into_iter().map(|t| t.word).collect();
+                let clause = Clause { tokens, prefix };
+                if !clause.tokens.is_empty() && !clauses.contains(&clause) {
+                    clauses.push(clause);
+                }
+            }
+        }
+
+        if clauses.is_empty() {
+            return Err(ApiError::BadRequest(
+                "Query must contain at least one word".to_string(),
+            ));
+        }
+        clauses.truncate(MAX_CLAUSES);
+
+        Ok(Self { clauses })
+    }
+}
+
+/// A word of indexed text with its character range in the original string
+#[derive(Debug, Clone)]
+struct Token {
+    word: String,
+    start: usize,
+    end: usize,
+}
+
+/// Split text into lowercase alphanumeric words, keeping character offsets
+fn tokenize(text: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut current: Option<Token> = None;
+
+    for (index, c) in text.chars().enumerate() {
+        if c.is_alphanumeric() {
+            let token = current.get_or_insert_with(|| Token {
+                word: String::new(),
+                start: index,
+                end: index,
+            });
+            token.word.extend(c.to_lowercase());
+            token.end = index + 1;
+        } else if let Some(token) = current.take() {
+            tokens.push(token);
+        }
+    }
+    tokens.extend(current);
+
+    tokens
+}
+
+/// A searchable field of a document
+#[derive(Debug, Clone)]
+pub struct SearchField {
+    pub name: &'static str,
+    pub text: String,
+    pub weight: f64,
+}
+
+impl SearchField {
+    fn new(name: &'static str, text: impl Into<String>, weight: f64) -> Self {
+        Self {
+            name,
+            text: text.into(),
+            weight,
+        }
+    }
+}
+
+/// Searchable fields of a project, most significant first
+pub fn project_fields(project: &Project) -> Vec<SearchField> {
+    let mut fields = vec![
+        SearchField::new("title", &project.title, 3.0),
+        SearchField::new("technologies", project.technologies.join(", "), 2.0),
+    ];
+
+    if let Some(desc) = &project.description {
+        let parts = [
+            ("overview", &desc.overview),
+            ("problem", &desc.problem),
+            ("solution", &desc.solution),
+            ("impact", &desc.impact),
+        ];
+        fields.extend(
+            parts
+                .into_iter()
+                .filter_map(|(name, text)| text.as_ref().map(|t| SearchField::new(name, t, 1.0))),
+        );
+    }
+    if !project.features.is_empty() {
+        fields.push(SearchField::new(
+            "features",
+            project.features.join(". "),
+            1.0,
+        ));
+    }
+
+    fields
+}
+
+/// Searchable fields of a certificate, most significant first
+pub fn certificate_fields(certificate: &Certificate) -> Vec<SearchField> {
+    vec![
+        SearchField::new("name", &certificate.name, 3.0),
+        SearchField::new("issuer", &certificate.issuer, 2.0),
+    ]
+}
+
+/// Part of a field around the first match, with the matched ranges
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct Snippet {
+    /// Field the snippet was taken from
+    pub field: String,
+    pub text: String,
+    /// `[start, end)` character offsets of the matches within `text`
+    pub highlights: Vec<[usize; 2]>,
+}
+
+/// Relevance of a document for a query
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scored {
+    pub score: f64,
+    pub snippet: Snippet,
+}
+
+/// Character ranges where a clause matches the tokens of a field
+fn clause_spans(tokens: &[Token], clause: &Clause) -> Vec<(usize, usize, bool)> {
+    let n = clause.tokens.len();
+    if tokens.len() < n {
+        return Vec::new();
+    }
+
+    tokens
+        .windows(n)
+        .filter_map(|window| {
+            let last = n - 1;
+            let mut exact = true;
+            for (i, (token, wanted)) in window.iter().zip(&clause.tokens).enumerate() {
+                if token.word == *wanted {
+                    continue;
+                }
+                if i == last && clause.prefix && token.word.starts_with(wanted.as_str()) {
+                    exact = false;
+                    continue;
+                }
+                return None;
+            }
+            Some((window[0].start, window[last].end, exact))
+        })
+        .collect()
+}
+
+/// Score a document's fields against the query, `None` unless every clause matches
+pub fn score(query: &SearchQuery, fields: &[SearchField]) -> Option<Scored> {
+    let tokenized: Vec<Vec<Token>> = fields.iter().map(|f| tokenize(&f.text)).collect();
+    let mut total = 0.0;
+    let mut spans_per_field: Vec<Vec<(usize, usize)>> = vec![Vec::new(); fields.len()];
+
+    for clause in &query.clauses {
+        let mut best = 0.0_f64;
+        for (index, (field, tokens)) in fields.iter().zip(&tokenized).enumerate() {
+            let spans = clause_spans(tokens, clause);
+            if spans.is_empty() {
+                continue;
+            }
+
+            let mut weight = field.weight;
+            if spans.iter().all(|(_, _, exact)| !exact) {
+                weight *= PREFIX_FACTOR;
+            }
+            if clause.tokens.len() > 1 {
+                weight *= PHRASE_FACTOR;
+            }
+            // Repeated mentions help, with diminishing returns
+            weight *= 1.0 + (spans.len() as f64).ln();
+            best = best.max(weight);
+
+            spans_per_field[index].extend(spans.into_iter().map(|(start, end, _)| (start, end)));
+        }
+
+        if best == 0.0 {
+            return None;
+        }
+        total += best;
+    }
+
+    let snippet = best_snippet(fields, &mut spans_per_field)?;
+    Some(Scored {
+        score: (total * 1000.0).round() / 1000.0,
+        snippet,
+    })
+}
+
+/// Snippet from the field with the most weighted matches
+fn best_snippet(fields: &[SearchField], spans: &mut [Vec<(usize, usize)>]) -> Option<Snippet> {
+    let (index, _) = fields
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !spans[*i].is_empty())
+        .max_by(|(a, fa), (b, fb)| {
+            let wa = fa.weight * spans[*a].len() as f64;
+            let wb = fb.weight * spans[*b].len() as f64;
+            // Earlier (more significant) fields win ties
+            wa.total_cmp(&wb).then(b.cmp(a))
+        })?;
+
+    let field = &fields[index];
+    let spans = &mut spans[index];
+    spans.sort_unstable();
+    spans.dedup();
+
+    let chars: Vec<char> = field.text.chars().collect();
+    let first = spans[0].0;
+    let start = first.saturating_sub(SNIPPET_CONTEXT);
+    let end = (spans[0].1 + SNIPPET_CONTEXT * 2).min(chars.len());
+
+    let mut text: String = chars[start..end].iter().collect();
+    let mut offset = start;
+    if start > 0 {
+        text.insert(0, '…');
+        offset = offset.saturating_sub(1);
+    }
+    if end < chars.len() {
+        text.push('…');
+    }
+
+    let highlights = spans
+        .iter()
+        .filter(|(s, e)| *s >= start && *e <= end)
+        .map(|(s, e)| [s - offset, e - offset])
+        .collect();
+
+    Some(Snippet {
+        field: field.name.to_string(),
+        text,
+        highlights,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(title: &str, body: &str) -> Vec<SearchField> {
+        vec![
+            SearchField::new("title", title, 3.0),
+            SearchField::new("overview", body, 1.0),
+        ]
+    }
+
+    #[test]
+    fn test_parse_words_prefixes_and_phrases() {
+        let query = SearchQuery::parse(r#"rus* "Machine  Learning" node.js rus*"#).unwrap();
+        let clauses: Vec<(Vec<&str>, bool)> = query
+            .clauses
+            .iter()
+            .map(|c| (c.tokens.iter().map(String::as_str).collect(), c.prefix))
+            .collect();
+        assert_eq!(
+            clauses,
+            vec![
+                (vec!["rus"], true),
+                (vec!["machine", "learning"], false),
+                (vec!["node", "js"], false),
+            ]
+        );
+
+        assert!(SearchQuery::parse("   ").is_err());
+        assert!(SearchQuery::parse("\"\" ***").is_err());
+        assert!(SearchQuery::parse(&"a".repeat(MAX_QUERY_CHARS + 1)).is_err());
+    }
+
+    #[test]
+    fn test_every_clause_must_match() {
+        let doc = fields("Rust API", "A REST service built with Axum");
+        assert!(score(&SearchQuery::parse("rust axum").unwrap(), &doc).is_some());
+        assert!(score(&SearchQuery::parse("rust python").unwrap(), &doc).is_none());
+        // Whole words only unless a prefix is requested
+        assert!(score(&SearchQuery::parse("ax").unwrap(), &doc).is_none());
+        assert!(score(&SearchQuery::parse("ax*").unwrap(), &doc).is_some());
+    }
+
+    #[test]
+    fn test_ranking_prefers_title_phrase_and_exact_matches() {
+        let title_hit = fields("Machine Learning Pipeline", "Trains models");
+        let body_hit = fields("Pipeline", "Uses machine learning to train models");
+        let scattered = fields("Pipeline", "A learning machine");
+
+        let phrase = SearchQuery::parse("\"machine learning\"").unwrap();
+        let title_score = score(&phrase, &title_hit).unwrap().score;
+        let body_score = score(&phrase, &body_hit).unwrap().score;
+        assert!(title_score > body_score);
+        assert!(score(&phrase, &scattered).is_none());
+
+        let exact = score(&SearchQuery::parse("models").unwrap(), &body_hit).unwrap();
+        let prefix = score(&SearchQuery::parse("mod*").unwrap(), &body_hit).unwrap();
+        assert!(exact.score > prefix.score);
+    }
+
+    #[test]
+    fn test_snippet_highlights_matches() {
+        let long = format!(
+            "{} built a Rust service for search. {}",
+            "x ".repeat(50),
+            "y ".repeat(80)
+        );
+        let doc = fields("Portfolio", &long);
+        let scored = score(&SearchQuery::parse("rust").unwrap(), &doc).unwrap();
+
+        let snippet = scored.snippet;
+        assert_eq!(snippet.field, "overview");
+        assert!(snippet.text.starts_with('…') && snippet.text.ends_with('…'));
+        let [start, end] = snippet.highlights[0];
+        let highlighted: String = snippet.text.chars().skip(start).take(end - start).collect();
+        assert_eq!(highlighted, "Rust");
+    }
+}
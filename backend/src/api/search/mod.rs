@@ -0,0 +1,234 @@
+pub mod engine;
+
+use crate::{
+    error::{ApiError, ApiResult},
+    models::{CertificateResponse, ContentKind, ProjectResponse},
+    repositories::Repositories,
+};
+use axum::{
+    extract::{Query, State},
+    routing::get,
+    Json, Router,
+};
+use engine::{certificate_fields, project_fields, score, SearchQuery, Snippet};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[cfg(feature = "swagger")]
+use utoipa::{IntoParams, ToSchema};
+
+const DEFAULT_LIMIT: usize = 10;
+const MAX_LIMIT: usize = 50;
+
+/// Shared state for search handlers
+pub struct SearchState {
+    pub repositories: Repositories,
+}
+
+/// Build the public search router
+pub fn router(repositories: Repositories) -> Router {
+    let state = Arc::new(SearchState { repositories });
+
+    Router::new().route("/", get(search)).with_state(state)
+}
+
+/// Query parameters of the search endpoint
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "swagger", derive(IntoParams))]
+#[cfg_attr(feature = "swagger", into_params(parameter_in = Query))]
+pub struct SearchParams {
+    /// Words, `prefix*` terms and `"quoted phrases"`, all of which must match
+    pub q: String,
+    /// Restrict results to one kind of content
+    pub kind: Option<ContentKind>,
+    /// Maximum number of results (1-50, default 10)
+    pub limit: Option<usize>,
+}
+
+/// Matched document, tagged with its kind
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", content = "item", rename_all = "lowercase")]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum SearchItem {
+    Project(Box<ProjectResponse>),
+    Certificate(Box<CertificateResponse>),
+}
+
+/// One ranked search result
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct SearchHit {
+    #[serde(flatten)]
+    pub item: SearchItem,
+    pub score: f64,
+    pub snippet: Snippet,
+}
+
+/// Ranked results across projects and certificates
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct SearchResponse {
+    pub query: String,
+    /// Number of matching documents before `limit` is applied
+    pub total: usize,
+    pub results: Vec<SearchHit>,
+}
+
+/// Search projects and certificates
+#[cfg_attr(feature = "swagger", utoipa::path(
+    get,
+    path = "/api/v1/search",
+    params(SearchParams),
+    responses(
+        (status = 200, description = "Ranked search results", body = SearchResponse),
+        (status = 400, description = "Empty or invalid query"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "search"
+))]
+pub async fn search(
+    State(state): State<Arc<SearchState>>,
+    Query(params): Query<SearchParams>,
+) -> ApiResult<Json<SearchResponse>> {
+    let limit = params.limit.unwrap_or(DEFAULT_LIMIT);
+    if !(1..=MAX_LIMIT).contains(&limit) {
+        return Err(ApiError::BadRequest(format!(
+            "limit must be between 1 and {}",
+            MAX_LIMIT
+        )));
+    }
+    let query = SearchQuery::parse(&params.q)?;
+    let wants = |kind| params.kind.is_none_or(|wanted| wanted == kind);
+
+    let mut hits = Vec::new();
+
+    if wants(ContentKind::Project) {
+        let projects = state.repositories.projects.searchable().await?;
+        for project in projects {
+            if let Some(scored) = score(&query, &project_fields(&project)) {
+                hits.push(SearchHit {
+                    item: SearchItem::Project(Box::new(project.into())),
+                    score: scored.score,
+                    snippet: scored.snippet,
+                });
+            }
+        }
+    }
+
+    if wants(ContentKind::Certificate) {
+        let certificates = state.repositories.certificates.searchable().await?;
+        for certificate in certificates {
+            if let Some(scored) = score(&query, &certificate_fields(&certificate)) {
+                hits.push(SearchHit {
+                    item: SearchItem::Certificate(Box::new(certificate.into())),
+                    score: scored.score,
+                    snippet: scored.snippet,
+                });
+            }
+        }
+    }
+
+    // Stable sort keeps projects before certificates on equal scores
+    hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+    let total = hits.len();
+    hits.truncate(limit);
+
+    Ok(Json(SearchResponse {
+        query: params.q,
+        total,
+        results: hits,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::slug::slugify;
+    use crate::{api::test_support::body_json, models::Certificate};
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use tower::ServiceExt;
+
+    fn certificate(name: &str, issuer: &str) -> Certificate {
+        Certificate {
+            id: None,
+            name: name.to_string(),
+            issuer: issuer.to_string(),
+            link: "https://example.com/verify".to_string(),
+            slug: slugify(name),
+            issue_date: None,
+            embedding: None,
+            embedding_model: None,
+            embedding_dimensions: None,
+            expires_at: None,
+            credential_id: None,
+            image_url: None,
+            verified: false,
+            verified_at: None,
+            publication: Default::default(),
+            publish_at: None,
+            deleted_at: None,
+            deleted_by: None,
+            version: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_ranks_across_collections() {
+        let repositories = Repositories::in_memory();
+        let project = serde_json::from_value(serde_json::json!({
+            "slug": "rust-search",
+            "date": "2024",
+            "title": "Search Service",
+            "description": { "overview": "Full-text search written in Rust" },
+            "technologies": ["Rust", "Axum"],
+            "features": [],
+            "githubUrl": "https://github.com/example/search",
+            "embedding": null,
+            "images": null
+        }))
+        .unwrap();
+        repositories.projects.create(project).await.unwrap();
+        for (name, issuer) in [("Rust Programming", "Coursera"), ("Python Basics", "Udemy")] {
+            repositories
+                .certificates
+                .create(certificate(name, issuer))
+                .await
+                .unwrap();
+        }
+        let app = router(repositories);
+
+        let response = app
+            .clone()
+            .oneshot(Request::get("/?q=rust").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = body_json(response).await;
+        assert_eq!(body["total"], 2);
+        // Title match on the certificate outranks the technology match
+        assert_eq!(body["results"][0]["kind"], "certificate");
+        assert_eq!(body["results"][0]["item"]["slug"], "rust-programming");
+        assert_eq!(body["results"][1]["kind"], "project");
+        assert_eq!(body["results"][1]["snippet"]["field"], "technologies");
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::get("/?q=rust&kind=project")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(body_json(response).await["total"], 1);
+
+        let response = app
+            .oneshot(Request::get("/?q=%20").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,159 @@
+//! Shared helpers for router tests running against in-memory repositories
+
+use crate::{
+    api::{admin, chat::Embedder},
+    auth::{jwt::Claims, AuthConfig},
+    repositories::Repositories,
+    services::{
+        link_health::LinkChecker,
+        media::{MediaService, MediaStore},
+        verification::CertificateVerifier,
+    },
+};
+use async_trait::async_trait;
+use axum::{body::to_bytes, response::Response, Router};
+use jsonwebtoken::{encode, EncodingKey, Header};
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+
+/// Auth config for a test admin together with a valid bearer token
+pub fn test_auth() -> (Arc<AuthConfig>, String) {
+    let config = AuthConfig {
+        admin_email: "admin@example.com".to_string(),
+        admin_password_hash: String::new(),
+        jwt_secret: "test-secret".to_string(),
+        jwt_expiry_hours: 1,
+    };
+    let now = chrono::Utc::now().timestamp() as usize;
+    let claims = Claims {
+        sub: config.admin_email.clone(),
+        exp: now + 3600,
+        iat: now,
+    };
+    let token = encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(config.jwt_secret.as_bytes()),
+    )
+    .unwrap();
+    (Arc::new(config), token)
+}
+
+/// Embedder returning a fixed vector, failing for texts containing `fail_on`
+pub struct StubEmbedder {
+    pub fail_on: Option<&'static str>,
+}
+
+impl StubEmbedder {
+    pub fn ok() -> Arc<Self> {
+        Arc::new(Self { fail_on: None })
+    }
+
+    pub fn failing() -> Arc<Self> {
+        Arc::new(Self { fail_on: Some("") })
+    }
+}
+
+#[async_trait]
+impl Embedder for StubEmbedder {
+    fn model(&self) -> &str {
+        "stub-embedding"
+    }
+
+    async fn embed(&self, text: &str) -> anyhow::Result<Vec<f64>> {
+        if self.fail_on.is_some_and(|needle| text.contains(needle)) {
+            anyhow::bail!("embedding API unavailable");
+        }
+        Ok(vec![0.5; 4])
+    }
+}
+
+/// Media store keeping uploads in memory, keyed like the real backends
+#[derive(Default)]
+pub struct StubMediaStore {
+    pub files: Mutex<Vec<(String, Vec<u8>)>>,
+}
+
+#[async_trait]
+impl MediaStore for StubMediaStore {
+    fn backend(&self) -> &'static str {
+        "stub"
+    }
+
+    async fn put(&self, key: &str, _content_type: &str, bytes: Vec<u8>) -> anyhow::Result<String> {
+        self.files.lock().unwrap().push((key.to_string(), bytes));
+        Ok(format!("https://media.example.com/{}", key))
+    }
+
+    async fn delete(&self, key: &str) -> anyhow::Result<()> {
+        self.files
+            .lock()
+            .unwrap()
+            .retain(|(stored, _)| stored != key);
+        Ok(())
+    }
+}
+
+/// Media service over a fresh `StubMediaStore`, accepting images up to 1 MiB
+pub fn test_media() -> (Arc<MediaService>, Arc<StubMediaStore>) {
+    let store = Arc::new(StubMediaStore::default());
+    (
+        Arc::new(MediaService::new(store.clone(), 1024 * 1024)),
+        store,
+    )
+}
+
+/// Link checker over `repositories` that gives up on a link after one second
+pub fn test_links(repositories: &Repositories) -> Arc<LinkChecker> {
+    Arc::new(LinkChecker::new(
+        repositories.clone(),
+        std::time::Duration::from_secs(1),
+    ))
+}
+
+/// Verifier expecting certificates issued to "Jane Doe", gives up on a page after one second
+pub fn test_verifier() -> Arc<CertificateVerifier> {
+    Arc::new(CertificateVerifier::new(
+        "Jane Doe".to_string(),
+        std::time::Duration::from_secs(1),
+    ))
+}
+
+/// Services behind a test admin router, the defaults stub out everything
+/// Tests replace one, e.g. a `links` checker probing a local server
+pub struct AdminServices {
+    pub media: Arc<MediaService>,
+    pub links: Arc<LinkChecker>,
+}
+
+impl AdminServices {
+    pub fn new(repositories: &Repositories) -> Self {
+        Self {
+            media: test_media().0,
+            links: test_links(repositories),
+        }
+    }
+}
+
+/// Admin router over `repositories` with stubbed services, accepting `test_auth` tokens
+pub fn admin_app(repositories: &Repositories) -> Router {
+    admin_app_with(repositories, AdminServices::new(repositories))
+}
+
+/// Admin router over `repositories` with the given services
+pub fn admin_app_with(repositories: &Repositories, services: AdminServices) -> Router {
+    admin::router(
+        repositories.clone(),
+        StubEmbedder::ok(),
+        test_auth().0,
+        services.media,
+        services.links,
+        test_verifier(),
+    )
+}
+
+/// Read a response body as JSON
+pub async fn body_json(response: Response) -> Value {
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
@@ -1,6 +1,6 @@
 use super::DatabaseConnection;
 use bson::{doc, Document};
-use mongodb::Collection;
+use mongodb::{ClientSession, Collection};
 
 /// MongoDB client wrapper for collection access
 #[derive(Debug, Clone)]
@@ -24,19 +24,38 @@ impl MongoClient {
         self.connection.database().collection("certificates")
     }
 
+    /// Get slug redirects collection (retired slug -> current slug)
+    pub fn slug_redirects(&self) -> Collection<Document> {
+        self.connection.database().collection("slug_redirects")
+    }
+
+    /// Get revisions collection (prior versions of edited content)
+    pub fn revisions(&self) -> Collection<Document> {
+        self.connection.database().collection("revisions")
+    }
+
+    /// Get media collection (uploaded assets and the content referencing them)
+    pub fn media(&self) -> Collection<Document> {
+        self.connection.database().collection("media")
+    }
+
+    /// Get link checks collection (latest health check of every content URL)
+    pub fn link_checks(&self) -> Collection<Document> {
+        self.connection.database().collection("link_checks")
+    }
+
+    /// Start a session for a multi-document transaction
+    /// Transactions need a replica set, which every Atlas cluster is
+    pub async fn start_session(&self) -> mongodb::error::Result<ClientSession> {
+        self.connection.start_session().await
+    }
+
     /// Get generic collection by name
     pub fn collection(&self, name: &str) -> Collection<Document> {
         self.connection.database().collection(name)
     }
 
-    /// Delete document by slug from collection
-    pub async fn delete_by_slug(&self, collection_name: &str, slug: &str) -> anyhow::Result<bool> {
-        let collection = self.collection(collection_name);
-        let result = collection.delete_one(doc! { "slug": slug }).await?;
-        Ok(result.deleted_count > 0)
-    }
-
-    /// Update document by slug in collection
+    /// Update a live (not trashed) document by slug in collection
     pub async fn update_by_slug(
         &self,
         collection_name: &str,
@@ -45,8 +64,11 @@ impl MongoClient {
     ) -> anyhow::Result<bool> {
         let collection = self.collection(collection_name);
         let result = collection
-            .update_one(doc! { "slug": slug }, doc! { "$set": update_data })
+            .update_one(
+                doc! { "slug": slug, "deleted_at": null },
+                doc! { "$set": update_data },
+            )
             .await?;
-        Ok(result.modified_count > 0)
+        Ok(result.matched_count > 0)
     }
 }
@@ -1,5 +1,5 @@
 use anyhow::{Context, Result};
-use mongodb::{Client, Database};
+use mongodb::{Client, ClientSession, Database};
 use std::sync::Arc;
 
 /// Database connection manager with pooling
@@ -36,4 +36,9 @@ impl DatabaseConnection {
     pub fn database(&self) -> Database {
         self.client.database(&self.database_name)
     }
+
+    /// Start a session, needed for multi-document transactions
+    pub async fn start_session(&self) -> mongodb::error::Result<ClientSession> {
+        self.client.start_session().await
+    }
 }
@@ -0,0 +1,1102 @@
+use super::MongoClient;
+use crate::{
+    models::{ContentDate, ContentKind},
+    services::slug::slugify,
+};
+use anyhow::Result;
+use bson::{doc, Bson, DateTime, Document};
+use futures::stream::TryStreamExt;
+use mongodb::{options::IndexOptions, Collection, IndexModel, SearchIndexModel, SearchIndexType};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// Collection recording which migrations have been applied
+pub const MIGRATIONS_COLLECTION: &str = "_migrations";
+/// Atlas Vector Search index over project embeddings, queried by chat
+pub const PROJECTS_VECTOR_INDEX: &str = "projects_index";
+/// Atlas Vector Search index over certificate embeddings, queried by chat
+pub const CERTIFICATES_VECTOR_INDEX: &str = "certificates_index";
+/// Fields chat pre-filters vector search on, so hidden content never takes a result slot
+const VECTOR_FILTER_FIELDS: [&str; 3] = ["deleted_at", "visibility", "publication"];
+
+/// Values some index definitions depend on
+#[derive(Debug, Clone)]
+pub struct MigrationSettings {
+    /// Vector size of the configured embedding model
+    pub embedding_dimensions: u32,
+}
+
+/// Desired state of a single index
+#[derive(Debug, Clone)]
+pub enum IndexSpec {
+    /// Regular index, created with `createIndexes`
+    Standard {
+        collection: &'static str,
+        name: &'static str,
+        keys: Document,
+        unique: bool,
+    },
+    /// Atlas Vector Search index
+    VectorSearch {
+        collection: &'static str,
+        name: &'static str,
+        definition: Document,
+    },
+}
+
+impl IndexSpec {
+    pub fn collection(&self) -> &'static str {
+        match self {
+            Self::Standard { collection, .. } | Self::VectorSearch { collection, .. } => collection,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Standard { name, .. } | Self::VectorSearch { name, .. } => name,
+        }
+    }
+}
+
+/// Rewrite of stored values, run once with its migration
+#[derive(Debug, Clone)]
+pub enum Backfill {
+    /// Parse free-form date text in `field` into a structured `ContentDate`
+    /// Text that does not parse moves to `{field}_unparsed`, reported per document
+    StructuredDates {
+        collection: &'static str,
+        field: &'static str,
+    },
+    /// Store `value` in `field` of every document that lacks it
+    Default {
+        collection: &'static str,
+        field: &'static str,
+        value: Bson,
+    },
+    /// Suffix duplicate slugs with `-2`, `-3`, ... so a unique index can be built
+    /// The oldest document keeps a slug, renamed ones leave a redirect behind
+    /// Missing slugs are derived from `source`
+    UniqueSlugs {
+        collection: &'static str,
+        kind: ContentKind,
+        source: &'static str,
+    },
+    /// Apply `set` to every document whose `field` holds `value`
+    Replace {
+        collection: &'static str,
+        field: &'static str,
+        value: Bson,
+        set: Document,
+    },
+}
+
+/// A numbered, named set of indexes and backfills, applied once and recorded in `_migrations`
+#[derive(Debug, Clone)]
+pub struct Migration {
+    pub version: u32,
+    pub name: &'static str,
+    pub indexes: Vec<IndexSpec>,
+    pub backfills: Vec<Backfill>,
+}
+
+fn index(collection: &'static str, name: &'static str, keys: Document) -> IndexSpec {
+    IndexSpec::Standard {
+        collection,
+        name,
+        keys,
+        unique: false,
+    }
+}
+
+fn unique_index(collection: &'static str, name: &'static str, keys: Document) -> IndexSpec {
+    IndexSpec::Standard {
+        collection,
+        name,
+        keys,
+        unique: true,
+    }
+}
+
+fn vector_index(collection: &'static str, name: &'static str, dimensions: u32) -> IndexSpec {
+    let mut fields = vec![Bson::Document(doc! {
+        "type": "vector",
+        "path": "embedding",
+        "numDimensions": dimensions as i32,
+        "similarity": "cosine",
+    })];
+    fields.extend(
+        VECTOR_FILTER_FIELDS
+            .iter()
+            .map(|path| Bson::Document(doc! { "type": "filter", "path": *path })),
+    );
+    IndexSpec::VectorSearch {
+        collection,
+        name,
+        definition: doc! { "fields": fields },
+    }
+}
+
+/// Every migration in version order - append new ones, never edit applied ones
+pub fn migrations(settings: &MigrationSettings) -> Vec<Migration> {
+    vec![
+        Migration {
+            version: 1,
+            name: "unique_slug_indexes",
+            indexes: vec![
+                unique_index("projects", "slug_unique", doc! { "slug": 1 }),
+                unique_index("certificates", "slug_unique", doc! { "slug": 1 }),
+                unique_index(
+                    "slug_redirects",
+                    "kind_from_unique",
+                    doc! { "kind": 1, "from": 1 },
+                ),
+            ],
+            backfills: vec![
+                Backfill::UniqueSlugs {
+                    collection: "projects",
+                    kind: ContentKind::Project,
+                    source: "title",
+                },
+                Backfill::UniqueSlugs {
+                    collection: "certificates",
+                    kind: ContentKind::Certificate,
+                    source: "name",
+                },
+            ],
+        },
+        Migration {
+            version: 2,
+            name: "vector_search_indexes",
+            indexes: vec![
+                vector_index(
+                    "projects",
+                    PROJECTS_VECTOR_INDEX,
+                    settings.embedding_dimensions,
+                ),
+                vector_index(
+                    "certificates",
+                    CERTIFICATES_VECTOR_INDEX,
+                    settings.embedding_dimensions,
+                ),
+            ],
+            backfills: Vec::new(),
+        },
+        Migration {
+            version: 3,
+            name: "revision_history_index",
+            indexes: vec![index(
+                "revisions",
+                "kind_content_created",
+                doc! { "kind": 1, "content_id": 1, "created_at": -1 },
+            )],
+            backfills: Vec::new(),
+        },
+        Migration {
+            version: 4,
+            name: "media_library_indexes",
+            indexes: vec![
+                unique_index("media", "key_unique", doc! { "key": 1 }),
+                index("media", "urls", doc! { "urls": 1 }),
+                index(
+                    "media",
+                    "references_content",
+                    doc! { "references.kind": 1, "references.content_id": 1 },
+                ),
+                index(
+                    "media",
+                    "orphans",
+                    doc! { "reference_count": 1, "uploaded_at": 1 },
+                ),
+            ],
+            backfills: Vec::new(),
+        },
+        Migration {
+            version: 5,
+            name: "link_check_indexes",
+            indexes: vec![
+                unique_index(
+                    "link_checks",
+                    "content_field_unique",
+                    doc! { "kind": 1, "slug": 1, "field": 1 },
+                ),
+                index("link_checks", "state", doc! { "state": 1 }),
+                index("link_checks", "checked_at", doc! { "checked_at": 1 }),
+            ],
+            backfills: Vec::new(),
+        },
+        Migration {
+            version: 6,
+            name: "structured_dates",
+            indexes: Vec::new(),
+            backfills: vec![
+                Backfill::StructuredDates {
+                    collection: "projects",
+                    field: "date",
+                },
+                Backfill::StructuredDates {
+                    collection: "certificates",
+                    field: "issue_date",
+                },
+            ],
+        },
+        Migration {
+            version: 7,
+            name: "project_ordering",
+            indexes: vec![index(
+                "projects",
+                "listing_order",
+                doc! { "visibility": 1, "featured": -1, "order": 1 },
+            )],
+            // Featured-first sorting would otherwise put projects missing the fields last
+            backfills: vec![
+                Backfill::Default {
+                    collection: "projects",
+                    field: "featured",
+                    value: Bson::Boolean(false),
+                },
+                Backfill::Default {
+                    collection: "projects",
+                    field: "order",
+                    value: Bson::Int32(0),
+                },
+                Backfill::Default {
+                    collection: "projects",
+                    field: "visibility",
+                    value: Bson::String("public".to_string()),
+                },
+            ],
+        },
+        Migration {
+            version: 8,
+            name: "publication_workflow",
+            indexes: vec![
+                index(
+                    "projects",
+                    "publication_schedule",
+                    doc! { "publication": 1, "publish_at": 1 },
+                ),
+                index(
+                    "certificates",
+                    "publication_schedule",
+                    doc! { "publication": 1, "publish_at": 1 },
+                ),
+            ],
+            // Draft became a publication status, everything stored before was live
+            backfills: vec![
+                Backfill::Replace {
+                    collection: "projects",
+                    field: "visibility",
+                    value: Bson::String("draft".to_string()),
+                    set: doc! { "visibility": "public", "publication": "draft" },
+                },
+                Backfill::Default {
+                    collection: "projects",
+                    field: "publication",
+                    value: Bson::String("published".to_string()),
+                },
+                Backfill::Default {
+                    collection: "certificates",
+                    field: "publication",
+                    value: Bson::String("published".to_string()),
+                },
+            ],
+        },
+    ]
+}
+
+/// An index as reported by the database
+#[derive(Debug, Clone, Default)]
+pub struct ExistingIndex {
+    pub name: String,
+    pub keys: Document,
+    pub unique: bool,
+    /// Latest definition of a search index
+    pub definition: Option<Document>,
+}
+
+/// Difference between a desired index and the database
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum IndexState {
+    InSync,
+    Missing,
+    Differs {
+        expected: String,
+        actual: String,
+    },
+    /// The index could not be inspected or applied, e.g. search indexes outside Atlas
+    Unavailable {
+        error: String,
+    },
+}
+
+/// Normalize numbers so `768`, `768i64` and `768.0` compare equal
+fn normalized(value: &Bson) -> serde_json::Value {
+    fn walk(value: serde_json::Value) -> serde_json::Value {
+        match value {
+            serde_json::Value::Number(n) => n
+                .as_f64()
+                .map_or(serde_json::Value::Number(n), |f| serde_json::json!(f)),
+            serde_json::Value::Array(items) => items.into_iter().map(walk).collect(),
+            serde_json::Value::Object(map) => map.into_iter().map(|(k, v)| (k, walk(v))).collect(),
+            other => other,
+        }
+    }
+    walk(value.clone().into_relaxed_extjson())
+}
+
+fn same(a: &Document, b: &Document) -> bool {
+    normalized(&Bson::Document(a.clone())) == normalized(&Bson::Document(b.clone()))
+}
+
+/// Compare a desired index with what the database reports
+pub fn index_state(spec: &IndexSpec, existing: &[ExistingIndex]) -> IndexState {
+    let Some(actual) = existing.iter().find(|index| index.name == spec.name()) else {
+        return IndexState::Missing;
+    };
+
+    let differs = |expected: &Document, actual: &Document| IndexState::Differs {
+        expected: expected.to_string(),
+        actual: actual.to_string(),
+    };
+
+    match spec {
+        IndexSpec::Standard { keys, unique, .. } => {
+            if !same(keys, &actual.keys) {
+                differs(keys, &actual.keys)
+            } else if *unique != actual.unique {
+                differs(
+                    &doc! { "unique": *unique },
+                    &doc! { "unique": actual.unique },
+                )
+            } else {
+                IndexState::InSync
+            }
+        }
+        IndexSpec::VectorSearch { definition, .. } => match &actual.definition {
+            Some(actual_definition) if same(definition, actual_definition) => IndexState::InSync,
+            actual_definition => {
+                differs(definition, &actual_definition.clone().unwrap_or_default())
+            }
+        },
+    }
+}
+
+/// State of one index of a migration
+#[derive(Debug, Clone, Serialize)]
+pub struct IndexReport {
+    pub collection: &'static str,
+    pub name: &'static str,
+    #[serde(flatten)]
+    pub state: IndexState,
+    /// True if this run created or replaced the index
+    pub changed: bool,
+}
+
+/// A stored value a backfill could not convert
+#[derive(Debug, Clone, Serialize)]
+pub struct BackfillFailure {
+    pub id: String,
+    pub slug: String,
+    pub value: String,
+    pub error: String,
+}
+
+/// Outcome of one backfill
+#[derive(Debug, Clone, Serialize)]
+pub struct BackfillReport {
+    pub collection: &'static str,
+    pub field: &'static str,
+    /// Values converted, or that would be in a dry run
+    pub converted: u64,
+    pub failures: Vec<BackfillFailure>,
+    /// Set when the backfill stopped on a database error
+    pub error: Option<String>,
+}
+
+/// Outcome of one migration
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationReport {
+    pub version: u32,
+    pub name: &'static str,
+    /// Recorded in `_migrations` before this run
+    pub previously_applied: bool,
+    /// Recorded in `_migrations` by this run
+    pub applied_now: bool,
+    pub indexes: Vec<IndexReport>,
+    /// Empty for migrations applied before this run, backfills only run once
+    pub backfills: Vec<BackfillReport>,
+}
+
+/// Outcome of a migration run
+#[derive(Debug, Clone, Serialize)]
+pub struct RunReport {
+    pub dry_run: bool,
+    pub migrations: Vec<MigrationReport>,
+}
+
+impl RunReport {
+    /// Indexes that don't match their definition after the run
+    pub fn drift(&self) -> impl Iterator<Item = (&MigrationReport, &IndexReport)> {
+        self.migrations.iter().flat_map(|migration| {
+            migration
+                .indexes
+                .iter()
+                .filter(|index| !index.changed && index.state != IndexState::InSync)
+                .map(move |index| (migration, index))
+        })
+    }
+
+    /// Log applied migrations and any remaining drift
+    pub fn log(&self) {
+        for migration in self.migrations.iter().filter(|m| m.applied_now) {
+            tracing::info!(
+                "Applied migration {} ({})",
+                migration.version,
+                migration.name
+            );
+        }
+
+        let mut drift_count = 0;
+        for (migration, index) in self.drift() {
+            drift_count += 1;
+            tracing::warn!(
+                "Index drift in migration {} ({}): {}.{} is {:?}",
+                migration.version,
+                migration.name,
+                index.collection,
+                index.name,
+                index.state
+            );
+        }
+
+        for migration in &self.migrations {
+            for backfill in &migration.backfills {
+                tracing::info!(
+                    "Backfill of {}.{} in migration {}: {} converted, {} not parseable",
+                    backfill.collection,
+                    backfill.field,
+                    migration.version,
+                    backfill.converted,
+                    backfill.failures.len()
+                );
+                for failure in &backfill.failures {
+                    tracing::warn!(
+                        "Cannot parse {}.{} of '{}' ({}): '{}' - {}; kept as {}_unparsed",
+                        backfill.collection,
+                        backfill.field,
+                        failure.slug,
+                        failure.id,
+                        failure.value,
+                        failure.error,
+                        backfill.field
+                    );
+                }
+                if let Some(error) = &backfill.error {
+                    tracing::error!(
+                        "Backfill of {}.{} failed: {}",
+                        backfill.collection,
+                        backfill.field,
+                        error
+                    );
+                }
+            }
+        }
+
+        let mode = if self.dry_run { "dry run" } else { "run" };
+        tracing::info!(
+            "Migration {} finished: {} migrations, {} drifted indexes",
+            mode,
+            self.migrations.len(),
+            drift_count
+        );
+    }
+}
+
+/// Applies pending migrations at startup and reports drift on applied ones
+pub struct MigrationRunner<'a> {
+    db: &'a MongoClient,
+    migrations: Vec<Migration>,
+}
+
+impl<'a> MigrationRunner<'a> {
+    pub fn new(db: &'a MongoClient, settings: &MigrationSettings) -> Self {
+        Self {
+            db,
+            migrations: migrations(settings),
+        }
+    }
+
+    /// Apply pending migrations, or with `dry_run` only report what differs
+    pub async fn run(&self, dry_run: bool) -> Result<RunReport> {
+        let applied = self.applied_versions().await?;
+        let mut reports = Vec::new();
+
+        for migration in &self.migrations {
+            let previously_applied = applied.contains(&migration.version);
+            // Applied migrations are only checked, fixing drift is a new migration's job
+            let apply = !dry_run && !previously_applied;
+
+            // Backfills go first, they may clean up data an index requires
+            let mut backfills = Vec::new();
+            if !previously_applied {
+                for backfill in &migration.backfills {
+                    backfills.push(self.backfill(backfill, apply).await);
+                }
+            }
+            let mut indexes = Vec::new();
+            for spec in &migration.indexes {
+                indexes.push(self.ensure(spec, apply).await);
+            }
+
+            let complete = indexes
+                .iter()
+                .all(|index| index.changed || index.state == IndexState::InSync)
+                && backfills.iter().all(|backfill| backfill.error.is_none());
+            let applied_now = apply && complete;
+            if applied_now {
+                self.record(migration).await?;
+            } else if apply {
+                tracing::warn!(
+                    "Migration {} ({}) incomplete, it will be retried on next start",
+                    migration.version,
+                    migration.name
+                );
+            }
+
+            reports.push(MigrationReport {
+                version: migration.version,
+                name: migration.name,
+                previously_applied,
+                applied_now,
+                indexes,
+                backfills,
+            });
+        }
+
+        Ok(RunReport {
+            dry_run,
+            migrations: reports,
+        })
+    }
+
+    async fn applied_versions(&self) -> Result<HashSet<u32>> {
+        let records: Vec<Document> = self
+            .db
+            .collection(MIGRATIONS_COLLECTION)
+            .find(doc! {})
+            .await?
+            .try_collect()
+            .await?;
+
+        Ok(records
+            .iter()
+            .filter_map(|record| record.get_i32("_id").ok())
+            .map(|version| version as u32)
+            .collect())
+    }
+
+    async fn record(&self, migration: &Migration) -> Result<()> {
+        let indexes: Vec<String> = migration
+            .indexes
+            .iter()
+            .map(|spec| format!("{}.{}", spec.collection(), spec.name()))
+            .collect();
+
+        self.db
+            .collection(MIGRATIONS_COLLECTION)
+            .insert_one(doc! {
+                "_id": migration.version as i32,
+                "name": migration.name,
+                "indexes": indexes,
+                "applied_at": DateTime::now(),
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Convert the values of one backfill, with `apply` false only report what would change
+    async fn backfill(&self, backfill: &Backfill, apply: bool) -> BackfillReport {
+        let (collection, field) = match backfill {
+            Backfill::StructuredDates { collection, field }
+            | Backfill::Default {
+                collection, field, ..
+            }
+            | Backfill::Replace {
+                collection, field, ..
+            } => (*collection, *field),
+            Backfill::UniqueSlugs { collection, .. } => (*collection, "slug"),
+        };
+        let mut report = BackfillReport {
+            collection,
+            field,
+            converted: 0,
+            failures: Vec::new(),
+            error: None,
+        };
+        let collection = self.db.collection(collection);
+        let result = match backfill {
+            Backfill::StructuredDates { .. } => {
+                self.backfill_dates(collection, field, apply, &mut report)
+                    .await
+            }
+            Backfill::Default { value, .. } => {
+                backfill_default(collection, field, value, apply, &mut report).await
+            }
+            Backfill::Replace { value, set, .. } => {
+                backfill_replace(collection, field, value, set, apply, &mut report).await
+            }
+            Backfill::UniqueSlugs { kind, source, .. } => {
+                self.backfill_slugs(collection, *kind, source, apply, &mut report)
+                    .await
+            }
+        };
+        if let Err(e) = result {
+            report.error = Some(e.to_string());
+        }
+        report
+    }
+
+    async fn backfill_dates(
+        &self,
+        collection: Collection<Document>,
+        field: &str,
+        apply: bool,
+        report: &mut BackfillReport,
+    ) -> Result<()> {
+        // Converted values are documents, so a rerun after an error picks up where it stopped
+        let documents: Vec<Document> = collection
+            .find(doc! { field: { "$type": "string" } })
+            .projection(doc! { "slug": 1, field: 1 })
+            .await?
+            .try_collect()
+            .await?;
+
+        for document in documents {
+            let Ok(id) = document.get_object_id("_id") else {
+                continue;
+            };
+            let text = document.get_str(field).unwrap_or_default();
+            let update = match structured_date(text) {
+                Ok(value) => {
+                    report.converted += 1;
+                    doc! { "$set": { field: value } }
+                }
+                Err(error) => {
+                    report.failures.push(BackfillFailure {
+                        id: id.to_hex(),
+                        slug: document.get_str("slug").unwrap_or_default().to_string(),
+                        value: text.to_string(),
+                        error,
+                    });
+                    doc! { "$set": { field: Bson::Null, format!("{}_unparsed", field): text } }
+                }
+            };
+            if apply {
+                collection.update_one(doc! { "_id": id }, update).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn backfill_slugs(
+        &self,
+        collection: Collection<Document>,
+        kind: ContentKind,
+        source: &str,
+        apply: bool,
+        report: &mut BackfillReport,
+    ) -> Result<()> {
+        let documents: Vec<Document> = collection
+            .find(doc! {})
+            .projection(doc! { "slug": 1, source: 1 })
+            .sort(doc! { "_id": 1 })
+            .await?
+            .try_collect()
+            .await?;
+        let slugs: Vec<(&str, &str)> = documents
+            .iter()
+            .map(|document| {
+                (
+                    document.get_str("slug").unwrap_or_default(),
+                    document.get_str(source).unwrap_or_default(),
+                )
+            })
+            .collect();
+
+        let redirects = self.db.slug_redirects();
+        for (document, renamed) in documents.iter().zip(unique_slugs(kind, &slugs)) {
+            let (Ok(id), Some(to)) = (document.get_object_id("_id"), renamed) else {
+                continue;
+            };
+            let from = document.get_str("slug").unwrap_or_default();
+            report.converted += 1;
+            tracing::warn!(
+                "Slug '{}' of {} {} is taken, renamed to '{}'",
+                from,
+                kind.as_str(),
+                id,
+                to
+            );
+            if !apply {
+                continue;
+            }
+            collection
+                .update_one(doc! { "_id": id }, doc! { "$set": { "slug": &to } })
+                .await?;
+            if !from.is_empty() {
+                // The first rename of a shared slug wins, like the oldest holder keeping it
+                redirects
+                    .update_one(
+                        doc! { "kind": kind.as_str(), "from": from },
+                        doc! { "$setOnInsert": { "to": &to, "created_at": DateTime::now() } },
+                    )
+                    .upsert(true)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Inspect one index and, if `apply`, create or replace it when it differs
+    async fn ensure(&self, spec: &IndexSpec, apply: bool) -> IndexReport {
+        let collection = self.db.collection(spec.collection());
+        let report = |state, changed| IndexReport {
+            collection: spec.collection(),
+            name: spec.name(),
+            state,
+            changed,
+        };
+
+        let existing = match spec {
+            IndexSpec::Standard { .. } => standard_indexes(&collection).await,
+            IndexSpec::VectorSearch { .. } => search_indexes(&collection).await,
+        };
+        let state = match existing {
+            Ok(existing) => index_state(spec, &existing),
+            Err(e) => IndexState::Unavailable {
+                error: e.to_string(),
+            },
+        };
+
+        if !apply || matches!(state, IndexState::InSync | IndexState::Unavailable { .. }) {
+            return report(state, false);
+        }
+
+        match apply_index(&collection, spec, &state).await {
+            Ok(()) => report(state, true),
+            Err(e) => report(
+                IndexState::Unavailable {
+                    error: e.to_string(),
+                },
+                false,
+            ),
+        }
+    }
+}
+
+/// Set a missing field to its default value
+async fn backfill_default(
+    collection: Collection<Document>,
+    field: &str,
+    value: &Bson,
+    apply: bool,
+    report: &mut BackfillReport,
+) -> Result<()> {
+    let missing = doc! { field: { "$exists": false } };
+    report.converted = if apply {
+        collection
+            .update_many(missing, doc! { "$set": { field: value.clone() } })
+            .await?
+            .modified_count
+    } else {
+        collection.count_documents(missing).await?
+    };
+    Ok(())
+}
+
+/// Rewrite documents holding a retired value
+async fn backfill_replace(
+    collection: Collection<Document>,
+    field: &str,
+    value: &Bson,
+    set: &Document,
+    apply: bool,
+    report: &mut BackfillReport,
+) -> Result<()> {
+    let matching = doc! { field: value.clone() };
+    report.converted = if apply {
+        collection
+            .update_many(matching, doc! { "$set": set.clone() })
+            .await?
+            .modified_count
+    } else {
+        collection.count_documents(matching).await?
+    };
+    Ok(())
+}
+
+/// New slug of each document, in `_id` order, None for documents keeping theirs
+/// `slugs` pairs the stored slug with the text a missing one is derived from
+fn unique_slugs(kind: ContentKind, slugs: &[(&str, &str)]) -> Vec<Option<String>> {
+    let mut taken: HashSet<String> = slugs
+        .iter()
+        .filter(|(slug, _)| !slug.is_empty())
+        .map(|(slug, _)| slug.to_string())
+        .collect();
+    let mut kept = HashSet::new();
+    let mut suffixes: HashMap<String, u32> = HashMap::new();
+
+    slugs
+        .iter()
+        .map(|(slug, source)| {
+            if !slug.is_empty() && kept.insert(*slug) {
+                return None;
+            }
+            let base = match (slug.is_empty(), slugify(source)) {
+                (false, _) => slug.to_string(),
+                (true, derived) if derived.is_empty() => kind.as_str().to_string(),
+                (true, derived) => derived,
+            };
+            let n = suffixes.entry(base.clone()).or_insert(1);
+            loop {
+                let candidate = match *n {
+                    1 => base.clone(),
+                    n => format!("{}-{}", base, n),
+                };
+                *n += 1;
+                if taken.insert(candidate.clone()) {
+                    return Some(candidate);
+                }
+            }
+        })
+        .collect()
+}
+
+/// Stored form of date text: the structured date, or null for empty text
+fn structured_date(text: &str) -> std::result::Result<Bson, String> {
+    if text.trim().is_empty() {
+        return Ok(Bson::Null);
+    }
+    let date: ContentDate = text.parse()?;
+    bson::to_bson(&date).map_err(|e| e.to_string())
+}
+
+async fn standard_indexes(collection: &Collection<Document>) -> Result<Vec<ExistingIndex>> {
+    let models: Vec<IndexModel> = match collection.list_indexes().await {
+        Ok(cursor) => cursor.try_collect().await?,
+        // The collection does not exist yet
+        Err(e) if e.to_string().contains("ns does not exist") => Vec::new(),
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(models
+        .into_iter()
+        .map(|model| {
+            let options = model.options.unwrap_or_default();
+            ExistingIndex {
+                name: options.name.unwrap_or_default(),
+                keys: model.keys,
+                unique: options.unique.unwrap_or(false),
+                definition: None,
+            }
+        })
+        .collect())
+}
+
+async fn search_indexes(collection: &Collection<Document>) -> Result<Vec<ExistingIndex>> {
+    let indexes: Vec<Document> = collection
+        .list_search_indexes()
+        .await?
+        .try_collect()
+        .await?;
+
+    Ok(indexes
+        .into_iter()
+        .map(|index| ExistingIndex {
+            name: index.get_str("name").unwrap_or_default().to_string(),
+            definition: index.get_document("latestDefinition").ok().cloned(),
+            ..Default::default()
+        })
+        .collect())
+}
+
+async fn apply_index(
+    collection: &Collection<Document>,
+    spec: &IndexSpec,
+    state: &IndexState,
+) -> Result<()> {
+    let replace = matches!(state, IndexState::Differs { .. });
+
+    match spec {
+        IndexSpec::Standard {
+            name, keys, unique, ..
+        } => {
+            if replace {
+                collection.drop_index(*name).await?;
+            }
+            let options = IndexOptions::builder()
+                .name(name.to_string())
+                .unique(unique.then_some(true))
+                .build();
+            let model = IndexModel::builder()
+                .keys(keys.clone())
+                .options(options)
+                .build();
+            collection.create_index(model).await?;
+        }
+        IndexSpec::VectorSearch {
+            name, definition, ..
+        } => {
+            if replace {
+                collection
+                    .update_search_index(*name, definition.clone())
+                    .await?;
+            } else {
+                let model = SearchIndexModel::builder()
+                    .name(name.to_string())
+                    .index_type(SearchIndexType::VectorSearch)
+                    .definition(definition.clone())
+                    .build();
+                collection.create_search_index(model).await?;
+            }
+        }
+    }
+
+    tracing::info!(
+        "{} index {}.{}",
+        if replace { "Replaced" } else { "Created" },
+        spec.collection(),
+        spec.name()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> MigrationSettings {
+        MigrationSettings {
+            embedding_dimensions: 768,
+        }
+    }
+
+    #[test]
+    fn test_migrations_are_contiguous_and_unique() {
+        let migrations = migrations(&settings());
+        // Contiguous from 1, a gap would read as a deleted applied migration
+        assert_eq!(migrations[0].version, 1);
+        assert!(migrations
+            .windows(2)
+            .all(|w| w[1].version == w[0].version + 1));
+
+        let mut names: Vec<_> = migrations
+            .iter()
+            .flat_map(|m| m.indexes.iter())
+            .map(|spec| (spec.collection(), spec.name()))
+            .collect();
+        let count = names.len();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), count);
+    }
+
+    #[test]
+    fn test_date_text_backfills_to_structured_dates() {
+        assert_eq!(
+            structured_date("Jan 2023 - Present").unwrap(),
+            Bson::Document(doc! { "start": { "year": 2023, "month": 1_i64 }, "ongoing": true })
+        );
+        assert_eq!(structured_date("  ").unwrap(), Bson::Null);
+        assert!(structured_date("Summer 2023")
+            .unwrap_err()
+            .contains("Summer 2023"));
+    }
+
+    #[test]
+    fn test_duplicate_slugs_are_suffixed_oldest_first() {
+        let slugs = [
+            ("rust", "Rust"),
+            ("rust-2", "Rust 2"),
+            ("rust", "Rust again"),
+            ("", "Rust"),
+            ("", "!!"),
+            ("rust", "Rust once more"),
+        ];
+        assert_eq!(
+            unique_slugs(ContentKind::Project, &slugs),
+            [
+                None,
+                None,
+                Some("rust-3".to_string()),
+                Some("rust-4".to_string()),
+                Some("project".to_string()),
+                Some("rust-5".to_string()),
+            ]
+        );
+        assert_eq!(
+            unique_slugs(ContentKind::Project, &[("a", ""), ("b", "")]),
+            [None, None]
+        );
+    }
+
+    #[test]
+    fn test_index_state_detects_drift() {
+        let slug = unique_index("projects", "slug_unique", doc! { "slug": 1 });
+        let existing = ExistingIndex {
+            name: "slug_unique".to_string(),
+            keys: doc! { "slug": 1_i64 },
+            unique: true,
+            ..Default::default()
+        };
+        assert_eq!(index_state(&slug, &[]), IndexState::Missing);
+        assert_eq!(
+            index_state(&slug, std::slice::from_ref(&existing)),
+            IndexState::InSync
+        );
+
+        let not_unique = ExistingIndex {
+            unique: false,
+            ..existing
+        };
+        assert!(matches!(
+            index_state(&slug, &[not_unique]),
+            IndexState::Differs { .. }
+        ));
+    }
+
+    #[test]
+    fn test_vector_index_compares_definitions() {
+        let spec = vector_index("projects", PROJECTS_VECTOR_INDEX, 768);
+        let existing = |dimensions: f64, filters: &[&str]| {
+            let mut fields = vec![Bson::Document(doc! {
+                "type": "vector",
+                "path": "embedding",
+                "numDimensions": dimensions,
+                "similarity": "cosine",
+            })];
+            for path in filters {
+                fields.push(Bson::Document(doc! { "type": "filter", "path": *path }));
+            }
+            ExistingIndex {
+                name: PROJECTS_VECTOR_INDEX.to_string(),
+                definition: Some(doc! { "fields": fields }),
+                ..Default::default()
+            }
+        };
+
+        assert_eq!(
+            index_state(&spec, &[existing(768.0, &VECTOR_FILTER_FIELDS)]),
+            IndexState::InSync
+        );
+        assert!(matches!(
+            index_state(&spec, &[existing(1536.0, &VECTOR_FILTER_FIELDS)]),
+            IndexState::Differs { .. }
+        ));
+        assert!(matches!(
+            index_state(&spec, &[existing(768.0, &[])]),
+            IndexState::Differs { .. }
+        ));
+    }
+}
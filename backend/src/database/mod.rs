@@ -1,5 +1,6 @@
-pub mod connection;
 pub mod client;
+pub mod connection;
+pub mod migrations;
 
-pub use connection::DatabaseConnection;
 pub use client::MongoClient;
+pub use connection::DatabaseConnection;
@@ -25,12 +25,24 @@ pub enum ApiError {
     /// Conflict - resource already exists or state conflict (409)
     Conflict(String),
 
+    /// Precondition failed - `If-Match` does not match the stored version (412)
+    PreconditionFailed(String),
+
+    /// Payload too large - an uploaded file exceeds the configured size limit (413)
+    PayloadTooLarge(String),
+
+    /// Unsupported media type - request body in a format the endpoint doesn't accept (415)
+    UnsupportedMediaType(String),
+
     /// Unprocessable entity - validation failed (422)
     ValidationError(String),
 
     /// Internal server error - unexpected errors (500)
     InternalError(String),
 
+    /// Bad gateway - a third-party site could not be loaded (502)
+    BadGateway(String),
+
     /// Service unavailable - temporary failure (503)
     ServiceUnavailable(String),
 }
@@ -43,8 +55,12 @@ impl fmt::Display for ApiError {
             Self::Forbidden(msg) => write!(f, "Forbidden: {}", msg),
             Self::NotFound(msg) => write!(f, "Not found: {}", msg),
             Self::Conflict(msg) => write!(f, "Conflict: {}", msg),
+            Self::PreconditionFailed(msg) => write!(f, "Precondition failed: {}", msg),
+            Self::PayloadTooLarge(msg) => write!(f, "Payload too large: {}", msg),
+            Self::UnsupportedMediaType(msg) => write!(f, "Unsupported media type: {}", msg),
             Self::ValidationError(msg) => write!(f, "Validation error: {}", msg),
             Self::InternalError(msg) => write!(f, "Internal error: {}", msg),
+            Self::BadGateway(msg) => write!(f, "Bad gateway: {}", msg),
             Self::ServiceUnavailable(msg) => write!(f, "Service unavailable: {}", msg),
         }
     }
@@ -61,8 +77,12 @@ impl ApiError {
             Self::Forbidden(_) => StatusCode::FORBIDDEN,
             Self::NotFound(_) => StatusCode::NOT_FOUND,
             Self::Conflict(_) => StatusCode::CONFLICT,
+            Self::PreconditionFailed(_) => StatusCode::PRECONDITION_FAILED,
+            Self::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
             Self::ValidationError(_) => StatusCode::UNPROCESSABLE_ENTITY,
             Self::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::BadGateway(_) => StatusCode::BAD_GATEWAY,
             Self::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
         }
     }
@@ -75,8 +95,12 @@ impl ApiError {
             Self::Forbidden(_) => "forbidden",
             Self::NotFound(_) => "not_found",
             Self::Conflict(_) => "conflict",
+            Self::PreconditionFailed(_) => "precondition_failed",
+            Self::PayloadTooLarge(_) => "payload_too_large",
+            Self::UnsupportedMediaType(_) => "unsupported_media_type",
             Self::ValidationError(_) => "validation_error",
             Self::InternalError(_) => "internal_error",
+            Self::BadGateway(_) => "bad_gateway",
             Self::ServiceUnavailable(_) => "service_unavailable",
         }
     }
@@ -106,8 +130,44 @@ impl IntoResponse for ApiError {
 
 // Conversion implementations for common error types
 
+/// MongoDB server error code for unique index violations
+const DUPLICATE_KEY_CODE: i32 = 11000;
+
+/// Server message of a unique index violation, None for any other error
+fn duplicate_key_detail(err: &mongodb::error::Error) -> Option<&str> {
+    use mongodb::error::{ErrorKind, WriteFailure};
+
+    match err.kind.as_ref() {
+        ErrorKind::Write(WriteFailure::WriteError(e)) if e.code == DUPLICATE_KEY_CODE => {
+            Some(&e.message)
+        }
+        ErrorKind::Command(e) if e.code == DUPLICATE_KEY_CODE => Some(&e.message),
+        _ => None,
+    }
+}
+
+/// Conflict message naming the violated index, taken from "... index: <name> dup key: ..."
+fn duplicate_key_message(detail: &str) -> String {
+    let index = detail
+        .split("index: ")
+        .nth(1)
+        .and_then(|rest| rest.split_whitespace().next());
+    match index {
+        Some(index) if index.starts_with("slug") => {
+            "A document with this slug already exists".to_string()
+        }
+        Some(index) => format!("A document with the same '{}' key already exists", index),
+        None => "A document with the same unique key already exists".to_string(),
+    }
+}
+
 impl From<mongodb::error::Error> for ApiError {
     fn from(err: mongodb::error::Error) -> Self {
+        if let Some(detail) = duplicate_key_detail(&err) {
+            tracing::warn!("Duplicate key: {}", err);
+            return ApiError::Conflict(duplicate_key_message(detail));
+        }
+
         tracing::error!("MongoDB error: {}", err);
         ApiError::InternalError("Database operation failed".to_string())
     }
@@ -151,6 +211,11 @@ impl From<validator::ValidationErrors> for ApiError {
 
 impl From<anyhow::Error> for ApiError {
     fn from(err: anyhow::Error) -> Self {
+        let err = match err.downcast::<mongodb::error::Error>() {
+            Ok(mongo_err) => return mongo_err.into(),
+            Err(err) => err,
+        };
+
         tracing::error!("Anyhow error: {}", err);
         ApiError::InternalError(err.to_string())
     }
@@ -159,3 +224,30 @@ impl From<anyhow::Error> for ApiError {
 /// Result type alias using ApiError
 #[allow(dead_code)]
 pub type ApiResult<T> = Result<T, ApiError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duplicate_key_message_names_the_index() {
+        let detail = |index: &str| {
+            format!(
+                "E11000 duplicate key error collection: portfolio.media index: {} dup key: {{ key: \"a\" }}",
+                index
+            )
+        };
+        assert_eq!(
+            duplicate_key_message(&detail("slug_unique")),
+            "A document with this slug already exists"
+        );
+        assert_eq!(
+            duplicate_key_message(&detail("key_unique")),
+            "A document with the same 'key_unique' key already exists"
+        );
+        assert_eq!(
+            duplicate_key_message("E11000 duplicate key error"),
+            "A document with the same unique key already exists"
+        );
+    }
+}
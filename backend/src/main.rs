@@ -1,11 +1,11 @@
 use axum::{
-    http::StatusCode,
+    http::{HeaderValue, StatusCode},
     response::IntoResponse,
     routing::{get, post},
     Extension, Json, Router,
 };
 use serde_json::json;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 mod api;
 mod auth;
@@ -14,9 +14,23 @@ mod error;
 mod middleware;
 mod models;
 mod repositories;
+mod services;
 
-use api::chat::{GeminiClient, PortfolioOwner};
+use api::chat::{
+    GeminiClient, GeminiEmbedder, PortfolioOwner, DEFAULT_EMBEDDING_DIMENSIONS,
+    DEFAULT_EMBEDDING_MODEL,
+};
+use api::conditional::HttpCache;
 use auth::{AuthConfig, LoginRequest, LoginResponse};
+use database::migrations::{MigrationRunner, MigrationSettings};
+use repositories::{ReadCache, Repositories, DEFAULT_READ_CACHE_ENTRIES, DEFAULT_READ_CACHE_TTL};
+use services::link_health::{LinkChecker, DEFAULT_LINK_CHECK_INTERVAL, DEFAULT_LINK_TIMEOUT};
+use services::media::{
+    CloudinaryMediaStore, LocalMediaStore, MediaService, MediaStore, DEFAULT_MAX_UPLOAD_BYTES,
+};
+use services::publisher::{ScheduledPublisher, DEFAULT_PUBLISH_INTERVAL};
+use services::verification::{CertificateVerifier, DEFAULT_VERIFY_TIMEOUT};
+use tower_http::services::ServeDir;
 
 #[shuttle_runtime::main]
 async fn main(
@@ -25,9 +39,6 @@ async fn main(
     tracing::info!("Starting Portfolio Rust Backend...");
 
     // Validate required secrets
-    let mongodb_uri = secrets
-        .get("MONGODB_URI")
-        .expect("MONGODB_URI must be set in Secrets.toml");
     let admin_email = secrets
         .get("ADMIN_EMAIL")
         .expect("ADMIN_EMAIL must be set in Secrets.toml");
@@ -43,13 +54,74 @@ async fn main(
 
     tracing::info!("Secrets validated successfully");
 
-    // Initialize MongoDB connection
-    let db_connection = database::DatabaseConnection::new(&mongodb_uri, "portfolio")
-        .await
-        .expect("Failed to initialize MongoDB connection");
+    // Storage backend: MongoDB by default, "memory" for local development without a database
+    let use_memory_store = secrets
+        .get("STORAGE_BACKEND")
+        .is_some_and(|backend| backend.eq_ignore_ascii_case("memory"));
+
+    let db_client = if use_memory_store {
+        tracing::warn!("STORAGE_BACKEND=memory - content is not persisted and chat is disabled");
+        None
+    } else {
+        let mongodb_uri = secrets
+            .get("MONGODB_URI")
+            .expect("MONGODB_URI must be set in Secrets.toml");
+
+        // Initialize MongoDB connection
+        let db_connection = database::DatabaseConnection::new(&mongodb_uri, "portfolio")
+            .await
+            .expect("Failed to initialize MongoDB connection");
 
-    let db_client = Arc::new(database::MongoClient::new(db_connection));
-    tracing::info!("MongoDB connection initialized successfully");
+        tracing::info!("MongoDB connection initialized successfully");
+        let db_client = database::MongoClient::new(db_connection);
+
+        // Versioned index migrations - MIGRATIONS_DRY_RUN=true only reports drift
+        let dry_run = secrets
+            .get("MIGRATIONS_DRY_RUN")
+            .is_some_and(|value| value.eq_ignore_ascii_case("true"));
+        let embedding_dimensions = secrets
+            .get("EMBEDDING_DIMENSIONS")
+            .map(|value| {
+                value
+                    .parse()
+                    .expect("EMBEDDING_DIMENSIONS must be a number")
+            })
+            .unwrap_or(DEFAULT_EMBEDDING_DIMENSIONS);
+        let settings = MigrationSettings {
+            embedding_dimensions,
+        };
+        match MigrationRunner::new(&db_client, &settings)
+            .run(dry_run)
+            .await
+        {
+            Ok(report) => report.log(),
+            Err(e) => tracing::error!("Failed to run migrations: {}", e),
+        }
+
+        Some(Arc::new(db_client))
+    };
+
+    // Read-through cache of content reads - READ_CACHE_TTL_SECS=0 disables it
+    let read_cache_ttl = secrets
+        .get("READ_CACHE_TTL_SECS")
+        .map(|value| {
+            Duration::from_secs(value.parse().expect("READ_CACHE_TTL_SECS must be a number"))
+        })
+        .unwrap_or(DEFAULT_READ_CACHE_TTL);
+    let read_cache_entries = secrets
+        .get("READ_CACHE_MAX_ENTRIES")
+        .map(|value| {
+            value
+                .parse()
+                .expect("READ_CACHE_MAX_ENTRIES must be a number")
+        })
+        .unwrap_or(DEFAULT_READ_CACHE_ENTRIES);
+    let read_cache = Arc::new(ReadCache::new(read_cache_ttl, read_cache_entries));
+
+    let repositories = match &db_client {
+        Some(db_client) => Repositories::mongo(db_client.clone(), read_cache),
+        None => Repositories::in_memory(),
+    };
 
     // Initialize JWT Auth Config
     let auth_config = Arc::new(AuthConfig::from_env(
@@ -64,6 +136,13 @@ async fn main(
     let gemini_client = Arc::new(GeminiClient::new(google_api_key.clone()));
     tracing::info!("Gemini client initialized");
 
+    // Embedder shared by content writes, admin reindex and chat queries
+    let embedding_model = secrets
+        .get("EMBEDDING_MODEL")
+        .unwrap_or_else(|| DEFAULT_EMBEDDING_MODEL.to_string());
+    tracing::info!("Embedding model: {}", embedding_model);
+    let embedder = Arc::new(GeminiEmbedder::new(google_api_key, embedding_model));
+
     // Load portfolio owner configuration from secrets
     let portfolio_owner = PortfolioOwner::from_secrets(&secrets);
     tracing::info!(
@@ -72,13 +151,150 @@ async fn main(
         portfolio_owner.title
     );
 
+    // Cache-Control of public reads, validators come from content writes
+    let http_cache = Arc::new(match secrets.get("HTTP_CACHE_CONTROL") {
+        Some(value) => HttpCache::new(
+            HeaderValue::from_str(&value).expect("HTTP_CACHE_CONTROL must be a valid header value"),
+        ),
+        None => HttpCache::default(),
+    });
+
+    // Image uploads: local files served under /media by default, MEDIA_BACKEND=cloudinary for Shuttle
+    let mut media_dir = None;
+    let media_store: Arc<dyn MediaStore> = match secrets.get("MEDIA_BACKEND").as_deref() {
+        Some(backend) if backend.eq_ignore_ascii_case("cloudinary") => {
+            let store = CloudinaryMediaStore::new(
+                secrets
+                    .get("CLOUDINARY_CLOUD_NAME")
+                    .expect("CLOUDINARY_CLOUD_NAME must be set when MEDIA_BACKEND=cloudinary"),
+                secrets
+                    .get("CLOUDINARY_API_KEY")
+                    .expect("CLOUDINARY_API_KEY must be set when MEDIA_BACKEND=cloudinary"),
+                secrets
+                    .get("CLOUDINARY_API_SECRET")
+                    .expect("CLOUDINARY_API_SECRET must be set when MEDIA_BACKEND=cloudinary"),
+            );
+            match secrets.get("CLOUDINARY_FOLDER") {
+                Some(folder) => Arc::new(store.with_folder(folder)),
+                None => Arc::new(store),
+            }
+        }
+        None | Some("local") => {
+            let store = LocalMediaStore::new(
+                secrets
+                    .get("MEDIA_ROOT")
+                    .unwrap_or_else(|| "media".to_string()),
+                &secrets
+                    .get("MEDIA_BASE_URL")
+                    .unwrap_or_else(|| "/media".to_string()),
+            );
+            media_dir = Some(store.root().to_path_buf());
+            Arc::new(store)
+        }
+        Some(other) => panic!(
+            "MEDIA_BACKEND must be 'local' or 'cloudinary', got '{}'",
+            other
+        ),
+    };
+    let media_max_bytes = secrets
+        .get("MEDIA_MAX_UPLOAD_BYTES")
+        .map(|value| {
+            value
+                .parse()
+                .expect("MEDIA_MAX_UPLOAD_BYTES must be a number")
+        })
+        .unwrap_or(DEFAULT_MAX_UPLOAD_BYTES);
+    tracing::info!(
+        "Media backend: {} (max {} bytes per image)",
+        media_store.backend(),
+        media_max_bytes
+    );
+    let media = Arc::new(MediaService::new(media_store, media_max_bytes));
+
+    // Link health checks - LINK_CHECK_INTERVAL_HOURS=0 leaves them to the admin endpoint
+    let link_timeout = secrets
+        .get("LINK_CHECK_TIMEOUT_SECS")
+        .map(|value| {
+            Duration::from_secs(
+                value
+                    .parse()
+                    .expect("LINK_CHECK_TIMEOUT_SECS must be a number"),
+            )
+        })
+        .unwrap_or(DEFAULT_LINK_TIMEOUT);
+    let link_interval = secrets
+        .get("LINK_CHECK_INTERVAL_HOURS")
+        .map(|value| {
+            Duration::from_secs(
+                value
+                    .parse::<u64>()
+                    .expect("LINK_CHECK_INTERVAL_HOURS must be a number")
+                    * 3600,
+            )
+        })
+        .unwrap_or(DEFAULT_LINK_CHECK_INTERVAL);
+    let links = Arc::new(LinkChecker::new(repositories.clone(), link_timeout));
+    if link_interval.is_zero() {
+        tracing::info!("Scheduled link checks disabled");
+    } else {
+        tracing::info!(
+            "Checking content links every {} hours",
+            link_interval.as_secs() / 3600
+        );
+        let links = links.clone();
+        tokio::spawn(async move { links.run_every(link_interval).await });
+    }
+
+    // Scheduled publishing - PUBLISH_INTERVAL_SECS=0 leaves scheduled content unpublished
+    let publish_interval = secrets
+        .get("PUBLISH_INTERVAL_SECS")
+        .map(|value| {
+            Duration::from_secs(
+                value
+                    .parse()
+                    .expect("PUBLISH_INTERVAL_SECS must be a number"),
+            )
+        })
+        .unwrap_or(DEFAULT_PUBLISH_INTERVAL);
+    if publish_interval.is_zero() {
+        tracing::warn!("Scheduled publishing disabled");
+    } else {
+        tracing::info!(
+            "Publishing scheduled content every {} seconds",
+            publish_interval.as_secs()
+        );
+        let publisher = ScheduledPublisher::new(repositories.clone());
+        tokio::spawn(async move { publisher.run_every(publish_interval).await });
+    }
+
+    // Certificate verification - names on certificates may differ from the chat persona's
+    let certificate_holder = secrets
+        .get("CERTIFICATE_HOLDER_NAME")
+        .unwrap_or_else(|| portfolio_owner.name.clone());
+    let verify_timeout = secrets
+        .get("CERTIFICATE_VERIFY_TIMEOUT_SECS")
+        .map(|value| {
+            Duration::from_secs(
+                value
+                    .parse()
+                    .expect("CERTIFICATE_VERIFY_TIMEOUT_SECS must be a number"),
+            )
+        })
+        .unwrap_or(DEFAULT_VERIFY_TIMEOUT);
+    let verifier = Arc::new(CertificateVerifier::new(certificate_holder, verify_timeout));
+
     // Build API router with admin authentication
     let api_router = api::build_router(
+        repositories,
         db_client.clone(),
+        embedder,
         auth_config.clone(),
         gemini_client,
-        google_api_key,
         portfolio_owner,
+        http_cache,
+        media,
+        links,
+        verifier,
     );
 
     // Auth routes
@@ -87,11 +303,15 @@ async fn main(
         .route("/verify", get(verify_handler))
         .with_state(auth_config.clone());
 
-    let router = Router::new()
+    let mut router = Router::new()
         .route("/", get(root))
         .route("/health", get(health_check))
         .nest("/api", api_router)
-        .nest("/auth", auth_router)
+        .nest("/auth", auth_router);
+    if let Some(media_dir) = media_dir {
+        router = router.nest_service("/media", ServeDir::new(media_dir));
+    }
+    let router = router
         .layer(Extension(db_client))
         .layer(middleware::configure_cors())
         .layer(middleware::configure_tracing());
@@ -123,11 +343,14 @@ async fn root() -> impl IntoResponse {
 
 /// Health check endpoint with database connectivity
 async fn health_check(
-    Extension(db_client): Extension<Arc<database::MongoClient>>,
+    Extension(db_client): Extension<Option<Arc<database::MongoClient>>>,
 ) -> Json<serde_json::Value> {
-    let db_status = match db_client.projects().estimated_document_count().await {
-        Ok(_) => "connected",
-        Err(_) => "disconnected",
+    let db_status = match db_client {
+        Some(db_client) => match db_client.projects().estimated_document_count().await {
+            Ok(_) => "connected",
+            Err(_) => "disconnected",
+        },
+        None => "in-memory",
     };
 
     Json(json!({
@@ -41,7 +41,13 @@ pub fn configure_cors() -> CorsLayer {
             axum::http::header::AUTHORIZATION,
             axum::http::header::ACCEPT,
             axum::http::header::ORIGIN,
+            // Conditional requests (optimistic locking and cache revalidation)
+            axum::http::header::IF_MATCH,
+            axum::http::header::IF_NONE_MATCH,
+            axum::http::header::IF_MODIFIED_SINCE,
         ])
+        // Let browser clients read the validators they send back
+        .expose_headers([axum::http::header::ETAG, axum::http::header::LAST_MODIFIED])
         // Allow specific origins
         .allow_origin(origins)
         // Enable credentials for authenticated requests
@@ -0,0 +1,79 @@
+use super::{Certificate, Project};
+use crate::error::{ApiError, ApiResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+/// Current bundle format, imports of newer bundles are refused
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Fields owned by the server - left out of exports and ignored on import
+const SERVER_MANAGED: [&str; 9] = [
+    "_id",
+    "embedding",
+    "embedding_model",
+    "embedding_dimensions",
+    "deleted_at",
+    "deleted_by",
+    "version",
+    "verified",
+    "verified_at",
+];
+
+/// Portable copy of all live portfolio content, for moving it between environments
+/// Items are kept as plain JSON so one malformed entry doesn't reject the whole bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ContentBundle {
+    /// Bundle format version
+    pub version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exported_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    #[cfg_attr(feature = "swagger", schema(value_type = Vec<Object>))]
+    pub projects: Vec<Value>,
+    #[serde(default)]
+    #[cfg_attr(feature = "swagger", schema(value_type = Vec<Object>))]
+    pub certificates: Vec<Value>,
+}
+
+impl ContentBundle {
+    pub fn new(projects: &[Project], certificates: &[Certificate]) -> ApiResult<Self> {
+        Ok(Self {
+            version: BUNDLE_VERSION,
+            exported_at: Some(Utc::now()),
+            projects: projects.iter().map(portable).collect::<ApiResult<_>>()?,
+            certificates: certificates
+                .iter()
+                .map(portable)
+                .collect::<ApiResult<_>>()?,
+        })
+    }
+
+    /// Refuse bundles written by a newer format
+    pub fn check_version(&self) -> ApiResult<()> {
+        match self.version {
+            1..=BUNDLE_VERSION => Ok(()),
+            version => Err(ApiError::BadRequest(format!(
+                "Unsupported bundle version {} (expected 1 to {})",
+                version, BUNDLE_VERSION
+            ))),
+        }
+    }
+}
+
+/// Editable content of an item, without server-managed fields
+fn portable<T: Serialize>(item: &T) -> ApiResult<Value> {
+    let mut value = serde_json::to_value(item)
+        .map_err(|e| ApiError::InternalError(format!("Failed to serialize content: {}", e)))?;
+    if let Some(fields) = value.as_object_mut() {
+        for field in SERVER_MANAGED {
+            fields.remove(field);
+        }
+    }
+    Ok(value)
+}
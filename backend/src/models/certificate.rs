@@ -1,12 +1,68 @@
+use super::{date, publication, ContentDate, PublicationStatus};
+use chrono::{DateTime, NaiveDate, Utc};
 use mongodb::bson::oid::ObjectId;
 use serde::{Deserialize, Serialize};
-use validator::Validate;
+use validator::{Validate, ValidationError};
 
 #[cfg(feature = "swagger")]
 use utoipa::ToSchema;
 
-#[derive(Debug, Serialize, Deserialize, Validate)]
+/// Fields a verification vouches for, changing one voids the verification
+pub const VERIFIED_FIELDS: [&str; 2] = ["name", "link"];
+
+/// Days before `expires_at` during which a certificate is reported as expiring soon
+pub const EXPIRING_SOON_DAYS: i64 = 30;
+
+/// Validity of a certificate on a given day, derived from `expires_at`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "swagger", derive(ToSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum CertificateStatus {
+    /// No expiry, or more than `EXPIRING_SOON_DAYS` left
+    Active,
+    /// Still valid, but expires within `EXPIRING_SOON_DAYS`
+    ExpiringSoon,
+    /// Past its expiry day
+    Expired,
+}
+
+impl CertificateStatus {
+    /// Status of a credential valid through `expires_at`, as seen on `today`
+    pub fn on(expires_at: Option<NaiveDate>, today: NaiveDate) -> Self {
+        match expires_at {
+            Some(expiry) if expiry < today => Self::Expired,
+            Some(expiry) if (expiry - today).num_days() <= EXPIRING_SOON_DAYS => Self::ExpiringSoon,
+            _ => Self::Active,
+        }
+    }
+}
+
+/// Reject an expiry before the credential was issued
+fn expiry_after_issue(
+    issue_date: Option<&ContentDate>,
+    expires_at: Option<NaiveDate>,
+) -> Result<(), ValidationError> {
+    match (issue_date, expires_at) {
+        (Some(issued), Some(expiry)) if expiry < issued.start.first_day() => {
+            Err(ValidationError::new("expires_before_issue")
+                .with_message("expires_at is before the issue date".into()))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn validate_certificate(certificate: &Certificate) -> Result<(), ValidationError> {
+    expiry_after_issue(certificate.issue_date.as_ref(), certificate.expires_at)?;
+    publication::check_schedule(certificate.publication, certificate.publish_at)
+}
+
+fn validate_update_dates(update: &CertificateUpdate) -> Result<(), ValidationError> {
+    expiry_after_issue(update.issue_date.as_ref(), update.expires_at)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+#[validate(schema(function = "validate_certificate"))]
 pub struct Certificate {
     #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
     #[cfg_attr(feature = "swagger", schema(value_type = Option<String>))]
@@ -23,31 +79,172 @@ pub struct Certificate {
 
     pub slug: String,
 
-    #[serde(rename = "issue_date")]
-    pub issue_date: Option<String>,
+    /// Also accepted as text ("May 2024")
+    #[serde(rename = "issue_date", default, deserialize_with = "date::optional")]
+    pub issue_date: Option<ContentDate>,
+
+    /// Last day the credential is valid, None when it does not expire
+    #[serde(default)]
+    pub expires_at: Option<NaiveDate>,
+
+    /// Identifier printed on the credential by its issuer
+    #[serde(default)]
+    pub credential_id: Option<String>,
 
     pub embedding: Option<Vec<f64>>,
 
+    /// Model and vector size that produced `embedding`, used to detect stale vectors
+    pub embedding_model: Option<String>,
+    pub embedding_dimensions: Option<i32>,
+
     #[serde(rename = "image_url")]
     pub image_url: Option<String>,
+    /// Set when the issuer page at `link` was found to name this credential and its holder
+    #[serde(default)]
+    pub verified: bool,
+    /// When the issuer page last confirmed the credential, None while unverified
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "bson::serde_helpers::chrono_datetime_as_bson_datetime_optional"
+    )]
+    #[cfg_attr(feature = "swagger", schema(value_type = Option<String>))]
+    pub verified_at: Option<DateTime<Utc>>,
+    /// Publishing workflow state, changed through the admin publication endpoint
+    #[serde(default)]
+    pub publication: PublicationStatus,
+    /// When a scheduled certificate goes live
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "publication::publish_at"
+    )]
+    #[cfg_attr(feature = "swagger", schema(value_type = Option<String>))]
+    pub publish_at: Option<DateTime<Utc>>,
+    /// Set when the item is moved to the trash, hidden from public reads until restored
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "bson::serde_helpers::chrono_datetime_as_bson_datetime_optional"
+    )]
+    #[cfg_attr(feature = "swagger", schema(value_type = Option<String>))]
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// Admin who moved the item to the trash
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_by: Option<String>,
+    /// Incremented on every content write, exposed as the document's `ETag`
+    #[serde(default)]
+    pub version: i64,
 }
 
 impl Certificate {
-    pub fn generate_slug(name: &str) -> String {
-        name.to_lowercase()
-            .chars()
-            .map(|c| if c.is_alphanumeric() { c } else { '-' })
-            .collect::<String>()
-            .split('-')
-            .filter(|s| !s.is_empty())
-            .collect::<Vec<_>>()
-            .join("-")
+    /// Store an embedding along with the model that produced it
+    pub fn set_embedding(&mut self, values: Vec<f64>, model: String) {
+        self.embedding_dimensions = Some(values.len() as i32);
+        self.embedding_model = Some(model);
+        self.embedding = Some(values);
+    }
+
+    /// Drop any embedding, e.g. a client-supplied one that could not be regenerated
+    pub fn clear_embedding(&mut self) {
+        self.embedding = None;
+        self.embedding_model = None;
+        self.embedding_dimensions = None;
+    }
+
+    /// True while the item is in the trash
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    /// Canonical text used to generate this certificate's embedding
+    pub fn embedding_text(&self) -> String {
+        format!("{} issued by {}", self.name, self.issuer)
+    }
+
+    /// Record the outcome of a verification, `verified_at` is None when it failed
+    pub fn set_verification(&mut self, verified_at: Option<DateTime<Utc>>) {
+        self.verified = verified_at.is_some();
+        self.verified_at = verified_at;
+    }
+
+    /// Move to `status` in the publishing workflow, `publish_at` only matters while scheduled
+    pub fn set_publication(
+        &mut self,
+        status: PublicationStatus,
+        publish_at: Option<DateTime<Utc>>,
+    ) {
+        self.publication = status;
+        self.publish_at = publish_at;
+    }
+
+    /// True if the certificate is served publicly
+    pub fn is_published(&self) -> bool {
+        self.publication == PublicationStatus::Published
+    }
+
+    /// Validity of the credential today
+    pub fn status(&self) -> CertificateStatus {
+        CertificateStatus::on(self.expires_at, Utc::now().date_naive())
     }
 }
 
-/// DTO for updating certificates - excludes _id, slug, and embedding (auto-managed)
+/// Public API representation of a certificate - string id, no embedding, camelCase fields
+/// `_id`, `issue_date` and `image_url` keep the names the frontend and dashboard already read
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CertificateResponse {
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub name: String,
+    pub issuer: String,
+    pub link: String,
+    pub slug: String,
+    /// Issue date as shown on the site ("May 2024")
+    #[serde(rename = "issue_date")]
+    pub issue_date: Option<String>,
+    /// Structured form of `issue_date`, with its precision
+    pub issue_date_detail: Option<ContentDate>,
+    pub expires_at: Option<NaiveDate>,
+    pub credential_id: Option<String>,
+    /// Derived from `expires_at` when the response is built
+    pub status: CertificateStatus,
+    pub verified: bool,
+    pub verified_at: Option<DateTime<Utc>>,
+    pub publication: PublicationStatus,
+    pub publish_at: Option<DateTime<Utc>>,
+    #[serde(rename = "image_url")]
+    pub image_url: Option<String>,
+}
+
+impl From<Certificate> for CertificateResponse {
+    fn from(certificate: Certificate) -> Self {
+        let status = certificate.status();
+        Self {
+            id: certificate.id.map(|id| id.to_hex()).unwrap_or_default(),
+            name: certificate.name,
+            issuer: certificate.issuer,
+            link: certificate.link,
+            slug: certificate.slug,
+            issue_date: certificate.issue_date.as_ref().map(ToString::to_string),
+            issue_date_detail: certificate.issue_date,
+            expires_at: certificate.expires_at,
+            credential_id: certificate.credential_id,
+            status,
+            verified: certificate.verified,
+            verified_at: certificate.verified_at,
+            publication: certificate.publication,
+            publish_at: certificate.publish_at,
+            image_url: certificate.image_url,
+        }
+    }
+}
+
+/// DTO for updating certificates - excludes _id, slug, embedding and publication (auto-managed)
 #[derive(Debug, Serialize, Deserialize, Validate)]
 #[cfg_attr(feature = "swagger", derive(ToSchema))]
+#[validate(schema(function = "validate_update_dates"))]
 pub struct CertificateUpdate {
     #[validate(length(min = 1))]
     pub name: String,
@@ -58,9 +255,59 @@ pub struct CertificateUpdate {
     #[validate(url)]
     pub link: String,
 
-    #[serde(rename = "issue_date")]
-    pub issue_date: Option<String>,
+    /// Also accepted as text ("May 2024")
+    #[serde(rename = "issue_date", default, deserialize_with = "date::optional")]
+    pub issue_date: Option<ContentDate>,
+
+    /// Last day the credential is valid, None when it does not expire
+    #[serde(default)]
+    pub expires_at: Option<NaiveDate>,
+
+    /// Identifier printed on the credential by its issuer
+    #[serde(default)]
+    pub credential_id: Option<String>,
 
     #[serde(rename = "image_url")]
     pub image_url: Option<String>,
 }
+
+impl From<Certificate> for CertificateUpdate {
+    fn from(certificate: Certificate) -> Self {
+        Self {
+            name: certificate.name,
+            issuer: certificate.issuer,
+            link: certificate.link,
+            issue_date: certificate.issue_date,
+            expires_at: certificate.expires_at,
+            credential_id: certificate.credential_id,
+            image_url: certificate.image_url,
+        }
+    }
+}
+
+impl CertificateUpdate {
+    /// Canonical text used to generate the updated certificate's embedding
+    pub fn embedding_text(&self) -> String {
+        format!("{} issued by {}", self.name, self.issuer)
+    }
+
+    /// True if applying the update changes a field in `VERIFIED_FIELDS`
+    pub fn voids_verification(&self, certificate: &Certificate) -> bool {
+        self.name != certificate.name || self.link != certificate.link
+    }
+
+    /// Apply the update onto an existing certificate, keeping auto-managed fields
+    /// A new name or link voids an earlier verification
+    pub fn apply_to(self, certificate: &mut Certificate) {
+        if self.voids_verification(certificate) {
+            certificate.set_verification(None);
+        }
+        certificate.name = self.name;
+        certificate.issuer = self.issuer;
+        certificate.link = self.link;
+        certificate.issue_date = self.issue_date;
+        certificate.expires_at = self.expires_at;
+        certificate.credential_id = self.credential_id;
+        certificate.image_url = self.image_url;
+    }
+}
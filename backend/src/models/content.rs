@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+/// Kind of portfolio content, one per MongoDB collection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum ContentKind {
+    Project,
+    Certificate,
+}
+
+impl ContentKind {
+    /// Lowercase name, as stored in MongoDB documents that reference content
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Project => "project",
+            Self::Certificate => "certificate",
+        }
+    }
+}
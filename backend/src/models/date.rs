@@ -0,0 +1,456 @@
+use chrono::{Datelike, NaiveDate, Utc};
+use serde::{
+    de::{self, MapAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+use std::{fmt, str::FromStr};
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Words closing a range that is still running ("2023 - Present")
+const ONGOING_WORDS: [&str; 5] = ["present", "now", "ongoing", "current", "today"];
+
+/// Separators between the start and end of a range, tried in order
+/// A bare hyphen comes last so ISO dates (`2024-05`) are not split
+const RANGE_SEPARATORS: [&str; 8] = [" - ", " – ", " — ", "–", "—", " to ", " until ", "-"];
+
+/// How much of a date is known
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatePrecision {
+    Day,
+    Month,
+    Year,
+}
+
+/// A date known to the day, the month or only the year
+/// Ordered by year, then month, then day, with missing parts first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+#[serde(try_from = "PartialDateParts")]
+pub struct PartialDate {
+    pub year: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub month: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub day: Option<u32>,
+}
+
+/// Unchecked fields of a `PartialDate`, validated on conversion
+#[derive(Deserialize)]
+struct PartialDateParts {
+    year: i32,
+    #[serde(default)]
+    month: Option<u32>,
+    #[serde(default)]
+    day: Option<u32>,
+}
+
+impl TryFrom<PartialDateParts> for PartialDate {
+    type Error = String;
+
+    fn try_from(parts: PartialDateParts) -> Result<Self, String> {
+        Self::new(parts.year, parts.month, parts.day)
+    }
+}
+
+impl PartialDate {
+    /// Checked constructor: a day needs a month, and the date must exist
+    pub fn new(year: i32, month: Option<u32>, day: Option<u32>) -> Result<Self, String> {
+        if !(1000..=9999).contains(&year) {
+            return Err(format!("{} is not a four-digit year", year));
+        }
+        match (month, day) {
+            (None, Some(_)) => return Err("A day needs a month".to_string()),
+            (Some(month), None) if !(1..=12).contains(&month) => {
+                return Err(format!("{} is not a month", month));
+            }
+            (Some(month), Some(day)) if NaiveDate::from_ymd_opt(year, month, day).is_none() => {
+                return Err(format!("{}-{:02}-{:02} is not a date", year, month, day));
+            }
+            _ => {}
+        }
+        Ok(Self { year, month, day })
+    }
+
+    pub fn precision(&self) -> DatePrecision {
+        match (self.month, self.day) {
+            (Some(_), Some(_)) => DatePrecision::Day,
+            (Some(_), None) => DatePrecision::Month,
+            _ => DatePrecision::Year,
+        }
+    }
+
+    /// Earliest day the date may stand for
+    pub fn first_day(&self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.year, self.month.unwrap_or(1), self.day.unwrap_or(1))
+            .expect("validated date")
+    }
+
+    /// Latest day the date may stand for
+    pub fn last_day(&self) -> NaiveDate {
+        match (self.month, self.day) {
+            (Some(month), Some(day)) => NaiveDate::from_ymd_opt(self.year, month, day),
+            (Some(12), None) | (None, _) => NaiveDate::from_ymd_opt(self.year, 12, 31),
+            (Some(month), None) => {
+                NaiveDate::from_ymd_opt(self.year, month + 1, 1).and_then(|next| next.pred_opt())
+            }
+        }
+        .expect("validated date")
+    }
+}
+
+/// Month number of an English month name or its abbreviation ("Sep", "Sept.")
+fn month_number(word: &str) -> Option<u32> {
+    let word = word.trim_end_matches('.').to_ascii_lowercase();
+    if word.len() < 3 {
+        return None;
+    }
+    MONTHS
+        .iter()
+        .position(|name| {
+            let name = name.to_ascii_lowercase();
+            name == word || (name.starts_with(&word) && word.len() <= 4)
+        })
+        .map(|index| index as u32 + 1)
+}
+
+/// Day of month with an optional ordinal suffix ("14th")
+fn day_number(word: &str) -> Option<u32> {
+    let digits = word
+        .trim_end_matches(|c: char| c.is_ascii_alphabetic())
+        .trim_end_matches('.');
+    if !(1..=2).contains(&digits.len()) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+fn year_number(word: &str) -> Option<i32> {
+    (word.len() == 4 && word.bytes().all(|b| b.is_ascii_digit()))
+        .then(|| word.parse().ok())
+        .flatten()
+}
+
+impl FromStr for PartialDate {
+    type Err = String;
+
+    /// Accepts `2024`, `2024-05`, `2024-05-14`, `05/2024`, `May 2024`,
+    /// `14 May 2024` and `May 14, 2024`
+    fn from_str(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        let unrecognized = || format!("'{}' is not a recognized date", text);
+
+        if text
+            .bytes()
+            .all(|b| b.is_ascii_digit() || b"-/.".contains(&b))
+        {
+            let parts: Vec<&str> = text.split(['-', '/', '.']).collect();
+            let number = |part: &str| part.parse::<u32>().map_err(|_| unrecognized());
+            return match parts.as_slice() {
+                [year] => Self::new(year_number(year).ok_or_else(unrecognized)?, None, None),
+                [year, month] if year.len() == 4 => {
+                    Self::new(number(year)? as i32, Some(number(month)?), None)
+                }
+                [month, year] if year.len() == 4 && month.len() <= 2 => {
+                    Self::new(number(year)? as i32, Some(number(month)?), None)
+                }
+                [year, month, day] if year.len() == 4 => Self::new(
+                    number(year)? as i32,
+                    Some(number(month)?),
+                    Some(number(day)?),
+                ),
+                // 03/04/2024 reads differently in the US and in Europe
+                [_, _, year] if year.len() == 4 => {
+                    Err(format!("'{}' is ambiguous, write it as YYYY-MM-DD", text))
+                }
+                _ => Err(unrecognized()),
+            };
+        }
+
+        let words: Vec<&str> = text
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|word| !word.is_empty())
+            .collect();
+        let (year, month, day) = match words.as_slice() {
+            [month, year] => (year_number(year), month_number(month), None),
+            [day, month, year] if day_number(day).is_some() => {
+                (year_number(year), month_number(month), day_number(day))
+            }
+            [month, day, year] => (year_number(year), month_number(month), day_number(day)),
+            _ => return Err(unrecognized()),
+        };
+        match (year, month) {
+            (Some(year), Some(month)) if words.len() == 2 || day.is_some() => {
+                Self::new(year, Some(month), day)
+            }
+            _ => Err(unrecognized()),
+        }
+    }
+}
+
+impl fmt::Display for PartialDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let month = |month: u32| MONTHS[month as usize - 1];
+        match (self.precision(), self.month, self.day) {
+            (DatePrecision::Day, Some(m), Some(d)) => write!(f, "{} {} {}", d, month(m), self.year),
+            (DatePrecision::Month, Some(m), _) => write!(f, "{} {}", month(m), self.year),
+            _ => write!(f, "{}", self.year),
+        }
+    }
+}
+
+/// When a project was built or a certificate issued: a single date,
+/// a finished range, or a range that is still ongoing
+/// Accepts free-form text (`"Jan 2023 - Present"`) wherever it is deserialized
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct ContentDate {
+    pub start: PartialDate,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<PartialDate>,
+    /// Still running, the range has no end yet
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub ongoing: bool,
+}
+
+impl ContentDate {
+    /// Checked constructor: an ongoing range has no end, an end is not before the start
+    pub fn new(
+        start: PartialDate,
+        end: Option<PartialDate>,
+        ongoing: bool,
+    ) -> Result<Self, String> {
+        match end {
+            Some(_) if ongoing => Err("An ongoing range has no end".to_string()),
+            Some(end) if end.last_day() < start.first_day() => {
+                Err(format!("{} ends before it starts", Self::range(start, end)))
+            }
+            _ => Ok(Self {
+                start,
+                end,
+                ongoing,
+            }),
+        }
+    }
+
+    fn range(start: PartialDate, end: PartialDate) -> String {
+        format!("{} – {}", start, end)
+    }
+
+    /// Whether any part of the date falls in `year`, ongoing ranges run until today
+    pub fn covers_year(&self, year: i32) -> bool {
+        let last = match self.end {
+            Some(end) => end.year,
+            None if self.ongoing => Utc::now().year().max(self.start.year),
+            None => self.start.year,
+        };
+        (self.start.year..=last).contains(&year)
+    }
+}
+
+impl FromStr for ContentDate {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err("Date is empty".to_string());
+        }
+        let single_error = match text.parse::<PartialDate>() {
+            Ok(date) => return Self::new(date, None, false),
+            Err(e) => e,
+        };
+
+        for separator in RANGE_SEPARATORS {
+            let Some((start, end)) = text.split_once(separator) else {
+                continue;
+            };
+            let Ok(start) = start.parse::<PartialDate>() else {
+                continue;
+            };
+            let end = end.trim();
+            if end.is_empty() || ONGOING_WORDS.contains(&end.to_ascii_lowercase().as_str()) {
+                return Self::new(start, None, true);
+            }
+            if let Ok(end) = end.parse::<PartialDate>() {
+                return Self::new(start, Some(end), false);
+            }
+        }
+        Err(single_error)
+    }
+}
+
+impl fmt::Display for ContentDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.end {
+            Some(end) => write!(f, "{}", Self::range(self.start, end)),
+            None if self.ongoing => write!(f, "{} – Present", self.start),
+            None => write!(f, "{}", self.start),
+        }
+    }
+}
+
+/// Fields of the structured form, validated on conversion
+#[derive(Deserialize)]
+struct ContentDateParts {
+    start: PartialDate,
+    #[serde(default)]
+    end: Option<PartialDate>,
+    #[serde(default)]
+    ongoing: bool,
+}
+
+/// Reads either the structured form or text, empty text reads as no date
+struct DateVisitor;
+
+impl<'de> Visitor<'de> for DateVisitor {
+    type Value = Option<ContentDate>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(
+            "a date such as \"2024\", \"May 2024 - Present\" or { \"start\": { \"year\": 2024 } }",
+        )
+    }
+
+    fn visit_str<E: de::Error>(self, text: &str) -> Result<Self::Value, E> {
+        if text.trim().is_empty() {
+            return Ok(None);
+        }
+        text.parse().map(Some).map_err(E::custom)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        let parts = ContentDateParts::deserialize(de::value::MapAccessDeserializer::new(map))?;
+        ContentDate::new(parts.start, parts.end, parts.ongoing)
+            .map(Some)
+            .map_err(de::Error::custom)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ContentDate {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer
+            .deserialize_any(DateVisitor)?
+            .ok_or_else(|| de::Error::custom("Date is empty"))
+    }
+}
+
+/// `deserialize_with` for optional dates: null, a missing field and "" are no date
+pub fn optional<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<ContentDate>, D::Error> {
+    deserializer.deserialize_option(DateVisitor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn date(year: i32, month: Option<u32>, day: Option<u32>) -> PartialDate {
+        PartialDate::new(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_free_form_text_is_parsed_with_its_precision() {
+        let cases = [
+            ("2024", date(2024, None, None)),
+            ("2024-05", date(2024, Some(5), None)),
+            ("2024-05-14", date(2024, Some(5), Some(14))),
+            ("5/2024", date(2024, Some(5), None)),
+            ("May 2024", date(2024, Some(5), None)),
+            ("Sept. 2023", date(2023, Some(9), None)),
+            ("14th May 2024", date(2024, Some(5), Some(14))),
+            ("May 14, 2024", date(2024, Some(5), Some(14))),
+        ];
+        for (text, expected) in cases {
+            let parsed: ContentDate = text.parse().unwrap();
+            assert_eq!(parsed.start, expected, "{}", text);
+            assert_eq!((parsed.end, parsed.ongoing), (None, false), "{}", text);
+        }
+        assert_eq!(
+            date(2024, Some(5), Some(14)).precision(),
+            DatePrecision::Day
+        );
+        assert_eq!(date(2024, Some(5), None).to_string(), "May 2024");
+
+        let range: ContentDate = "Jan 2023 - Present".parse().unwrap();
+        assert_eq!(range.start, date(2023, Some(1), None));
+        assert!(range.ongoing);
+        assert_eq!(range.to_string(), "January 2023 – Present");
+        assert!(range.covers_year(Utc::now().year()));
+        assert!(!range.covers_year(2022));
+
+        let range: ContentDate = "2021-2022".parse().unwrap();
+        assert_eq!(range.end, Some(date(2022, None, None)));
+        assert!(range.covers_year(2021) && range.covers_year(2022));
+        assert_eq!(
+            "Mar 2023 – Jun 2023".parse::<ContentDate>().unwrap().end,
+            Some(date(2023, Some(6), None))
+        );
+        assert!("2023 –".parse::<ContentDate>().unwrap().ongoing);
+    }
+
+    #[test]
+    fn test_invalid_dates_are_rejected() {
+        for text in [
+            "Summer 2023",
+            "2024-13",
+            "2023-02-30",
+            "03/04/2024",
+            "2024 - 2022",
+            "24",
+        ] {
+            assert!(text.parse::<ContentDate>().is_err(), "{}", text);
+        }
+        assert!("03/04/2024"
+            .parse::<ContentDate>()
+            .unwrap_err()
+            .contains("ambiguous"));
+        // Contained months are not "before" a year range
+        assert!("May 2023 - 2023".parse::<ContentDate>().is_ok());
+    }
+
+    #[test]
+    fn test_structured_and_text_forms_deserialize_alike() {
+        let structured = json!({ "start": { "year": 2023, "month": 1 }, "ongoing": true });
+        let from_json: ContentDate = serde_json::from_value(structured.clone()).unwrap();
+        let from_text: ContentDate = serde_json::from_value(json!("Jan 2023 - now")).unwrap();
+        assert_eq!(from_json, from_text);
+        assert_eq!(serde_json::to_value(&from_text).unwrap(), structured);
+
+        assert!(serde_json::from_value::<ContentDate>(
+            json!({ "start": { "year": 2023, "day": 2 } })
+        )
+        .is_err());
+        assert!(serde_json::from_value::<ContentDate>(json!("")).is_err());
+    }
+}
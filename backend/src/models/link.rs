@@ -0,0 +1,82 @@
+use super::ContentKind;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+/// Outcome of probing a link
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum LinkState {
+    /// Answered with a success status, after following redirects
+    Ok,
+    /// Answered with an error status, or is not an http(s) URL
+    Broken,
+    /// No answer: DNS failure, refused connection, TLS error or timeout
+    Unreachable,
+}
+
+/// Last check of one URL field of a project or certificate, stored in `link_checks`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkCheck {
+    pub kind: ContentKind,
+    pub slug: String,
+    /// Field holding the URL, named as in the public API (`demoUrl`, `link`)
+    pub field: String,
+    pub url: String,
+    pub state: LinkState,
+    pub http_status: Option<u16>,
+    pub error: Option<String>,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub checked_at: DateTime<Utc>,
+    /// First check of the current run of failures, None while the link works
+    #[serde(
+        default,
+        with = "bson::serde_helpers::chrono_datetime_as_bson_datetime_optional"
+    )]
+    pub failing_since: Option<DateTime<Utc>>,
+}
+
+impl LinkCheck {
+    /// Whether the link was found broken or unreachable
+    pub fn is_failing(&self) -> bool {
+        self.state != LinkState::Ok
+    }
+}
+
+/// Link check as listed by the admin report
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct LinkCheckResponse {
+    pub kind: ContentKind,
+    pub slug: String,
+    pub field: String,
+    pub url: String,
+    pub state: LinkState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub checked_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failing_since: Option<DateTime<Utc>>,
+}
+
+impl From<LinkCheck> for LinkCheckResponse {
+    fn from(check: LinkCheck) -> Self {
+        Self {
+            kind: check.kind,
+            slug: check.slug,
+            field: check.field,
+            url: check.url,
+            state: check.state,
+            http_status: check.http_status,
+            error: check.error,
+            checked_at: check.checked_at,
+            failing_since: check.failing_since,
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use super::ContentKind;
+use chrono::{DateTime, Utc};
+use mongodb::bson::{oid::ObjectId, Bson, Document};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+/// A project or certificate using an asset
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MediaReference {
+    pub kind: ContentKind,
+    /// Id of the referencing document, stable across slug renames
+    pub content_id: ObjectId,
+    /// Slug the document had at its last write
+    pub slug: String,
+}
+
+/// Stored rendition of an asset other than the original
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct MediaThumbnail {
+    pub key: String,
+    pub url: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Uploaded image tracked in the `media` collection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediaAsset {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    /// Storage key of the original, thumbnails keep their own keys
+    pub key: String,
+    pub url: String,
+    /// `MediaStore` backend holding the files
+    pub backend: String,
+    pub file_name: Option<String>,
+    pub content_type: String,
+    pub bytes: i64,
+    pub width: u32,
+    pub height: u32,
+    pub thumbnails: Vec<MediaThumbnail>,
+    /// Every URL content may use for this asset: the original and its thumbnails
+    pub urls: Vec<String>,
+    pub uploaded_by: String,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub uploaded_at: DateTime<Utc>,
+    pub references: Vec<MediaReference>,
+    /// Length of `references`, kept in the document so orphans can be queried
+    pub reference_count: i64,
+}
+
+impl MediaAsset {
+    /// Storage keys of the original and every thumbnail
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.key.as_str()).chain(
+            self.thumbnails
+                .iter()
+                .map(|thumbnail| thumbnail.key.as_str()),
+        )
+    }
+}
+
+/// Media URLs a stored project or certificate document points at
+/// Projects list them in `images`, certificates hold one `image_url`
+pub fn referenced_urls(kind: ContentKind, document: &Document) -> Vec<String> {
+    let field = match kind {
+        ContentKind::Project => "images",
+        ContentKind::Certificate => "image_url",
+    };
+    let mut urls: Vec<String> = match document.get(field) {
+        Some(Bson::Array(values)) => values
+            .iter()
+            .filter_map(|value| value.as_str())
+            .map(str::to_string)
+            .collect(),
+        Some(Bson::String(url)) => vec![url.clone()],
+        _ => Vec::new(),
+    };
+    urls.retain(|url| !url.trim().is_empty());
+    urls.sort();
+    urls.dedup();
+    urls
+}
+
+/// Document using an asset, as listed by the admin API
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct MediaReferenceResponse {
+    pub kind: ContentKind,
+    pub content_id: String,
+    pub slug: String,
+}
+
+/// Asset with its size, dimensions, uploader and referencing documents
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct MediaAssetResponse {
+    pub id: String,
+    pub key: String,
+    pub url: String,
+    pub backend: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+    pub content_type: String,
+    pub bytes: i64,
+    pub width: u32,
+    pub height: u32,
+    pub thumbnails: Vec<MediaThumbnail>,
+    pub uploaded_by: String,
+    pub uploaded_at: DateTime<Utc>,
+    pub reference_count: i64,
+    pub references: Vec<MediaReferenceResponse>,
+}
+
+impl From<MediaAsset> for MediaAssetResponse {
+    fn from(asset: MediaAsset) -> Self {
+        Self {
+            id: asset.id.map(|id| id.to_hex()).unwrap_or_default(),
+            key: asset.key,
+            url: asset.url,
+            backend: asset.backend,
+            file_name: asset.file_name,
+            content_type: asset.content_type,
+            bytes: asset.bytes,
+            width: asset.width,
+            height: asset.height,
+            thumbnails: asset.thumbnails,
+            uploaded_by: asset.uploaded_by,
+            uploaded_at: asset.uploaded_at,
+            reference_count: asset.reference_count,
+            references: asset
+                .references
+                .into_iter()
+                .map(|reference| MediaReferenceResponse {
+                    kind: reference.kind,
+                    content_id: reference.content_id.to_hex(),
+                    slug: reference.slug,
+                })
+                .collect(),
+        }
+    }
+}
@@ -1,7 +1,18 @@
+pub mod bundle;
 pub mod certificate;
 pub mod chat;
+pub mod content;
+pub mod date;
+pub mod link;
+pub mod media;
 pub mod project;
+pub mod publication;
+pub mod revision;
 
-pub use certificate::Certificate;
+pub use certificate::{Certificate, CertificateResponse, CertificateStatus};
 pub use chat::{ChatMessage, ChatRequest, ChatResponse};
-pub use project::Project;
+pub use content::ContentKind;
+pub use date::ContentDate;
+pub use project::{Project, ProjectResponse, ProjectVisibility};
+pub use publication::PublicationStatus;
+pub use revision::Revision;
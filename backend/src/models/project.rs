@@ -1,21 +1,46 @@
+use super::{date, publication, ContentDate, PublicationStatus};
+use chrono::{DateTime, Utc};
 use mongodb::bson::oid::ObjectId;
 use serde::{Deserialize, Serialize};
-use validator::Validate;
+use validator::{Validate, ValidationError};
 
 #[cfg(feature = "swagger")]
 use utoipa::ToSchema;
 
-#[derive(Debug, Serialize, Deserialize, Validate)]
+/// Who can see a published project
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "swagger", derive(ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectVisibility {
+    /// Listed, searchable and known to the chat assistant
+    #[default]
+    Public,
+    /// Reachable by its URL only, left out of listings, search and chat
+    Unlisted,
+    /// Legacy value older clients still send, stored as a public project in `draft`
+    /// publication by `retire_draft_visibility`
+    Draft,
+}
+
+fn validate_project_schedule(project: &Project) -> Result<(), ValidationError> {
+    publication::check_schedule(project.publication, project.publish_at)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+#[validate(schema(function = "validate_project_schedule"))]
 pub struct Project {
     #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
     #[cfg_attr(feature = "swagger", schema(value_type = Option<String>))]
     pub id: Option<ObjectId>,
 
-    #[validate(length(min = 1))]
+    /// Assigned by the server on create - derived from this value, or the title when empty
+    #[serde(default)]
     pub slug: String,
 
-    pub date: String,
+    /// When the project was built, also accepted as text ("Jan 2023 - Present")
+    #[serde(default, deserialize_with = "date::optional")]
+    pub date: Option<ContentDate>,
 
     #[validate(length(min = 1))]
     pub title: String,
@@ -41,10 +66,50 @@ pub struct Project {
     pub youtube_url: Option<String>,
 
     pub embedding: Option<Vec<f64>>,
+
+    /// Model and vector size that produced `embedding`, used to detect stale vectors
+    pub embedding_model: Option<String>,
+    pub embedding_dimensions: Option<i32>,
     pub images: Option<Vec<String>>,
+
+    /// Listed ahead of every other project
+    #[serde(default)]
+    pub featured: bool,
+    /// Position within its featured or regular group, lowest first
+    #[serde(default)]
+    pub order: i32,
+    #[serde(default)]
+    pub visibility: ProjectVisibility,
+
+    /// Publishing workflow state, changed through the admin publication endpoint
+    #[serde(default)]
+    pub publication: PublicationStatus,
+    /// When a scheduled project goes live
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "publication::publish_at"
+    )]
+    #[cfg_attr(feature = "swagger", schema(value_type = Option<String>))]
+    pub publish_at: Option<DateTime<Utc>>,
+
+    /// Set when the item is moved to the trash, hidden from public reads until restored
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "bson::serde_helpers::chrono_datetime_as_bson_datetime_optional"
+    )]
+    #[cfg_attr(feature = "swagger", schema(value_type = Option<String>))]
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// Admin who moved the item to the trash
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_by: Option<String>,
+    /// Incremented on every content write, exposed as the document's `ETag`
+    #[serde(default)]
+    pub version: i64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "swagger", derive(ToSchema))]
 pub struct Description {
     pub title: Option<String>,
@@ -58,14 +123,158 @@ pub struct Description {
     pub dashboard_info: Option<String>,
 }
 
-/// DTO for updating projects - excludes _id and embedding (auto-managed)
+/// Build the canonical text embedded for vector search from a project's content
+fn embedding_text(
+    title: &str,
+    description: Option<&Description>,
+    technologies: &[String],
+) -> String {
+    let mut parts = vec![title.to_string()];
+
+    if let Some(desc) = description {
+        parts.extend(
+            [
+                &desc.title,
+                &desc.overview,
+                &desc.problem,
+                &desc.solution,
+                &desc.impact,
+            ]
+            .into_iter()
+            .flatten()
+            .filter(|text| !text.trim().is_empty())
+            .cloned(),
+        );
+    }
+
+    if !technologies.is_empty() {
+        parts.push(format!("Technologies: {}", technologies.join(", ")));
+    }
+
+    parts.join("\n")
+}
+
+impl Project {
+    /// Store an embedding along with the model that produced it
+    pub fn set_embedding(&mut self, values: Vec<f64>, model: String) {
+        self.embedding_dimensions = Some(values.len() as i32);
+        self.embedding_model = Some(model);
+        self.embedding = Some(values);
+    }
+
+    /// Drop any embedding, e.g. a client-supplied one that could not be regenerated
+    pub fn clear_embedding(&mut self) {
+        self.embedding = None;
+        self.embedding_model = None;
+        self.embedding_dimensions = None;
+    }
+
+    /// True while the item is in the trash
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    /// Canonical text used to generate this project's embedding
+    pub fn embedding_text(&self) -> String {
+        embedding_text(&self.title, self.description.as_ref(), &self.technologies)
+    }
+
+    /// Move to `status` in the publishing workflow, `publish_at` only matters while scheduled
+    pub fn set_publication(
+        &mut self,
+        status: PublicationStatus,
+        publish_at: Option<DateTime<Utc>>,
+    ) {
+        self.publication = status;
+        self.publish_at = publish_at;
+    }
+
+    /// Turn the legacy `draft` visibility into a public draft, before storing the project
+    pub fn retire_draft_visibility(&mut self) {
+        if self.visibility == ProjectVisibility::Draft {
+            self.visibility = ProjectVisibility::Public;
+            self.set_publication(PublicationStatus::Draft, None);
+        }
+    }
+
+    /// True if the project is served publicly
+    pub fn is_published(&self) -> bool {
+        self.publication == PublicationStatus::Published
+    }
+
+    /// True if the project appears in public listings, search and chat
+    pub fn is_listed(&self) -> bool {
+        self.is_published() && self.visibility == ProjectVisibility::Public
+    }
+}
+
+/// Public API representation of a project - string id, no embedding, camelCase fields
+/// `_id` and `ReportUrl` keep the names the frontend and dashboard already read
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectResponse {
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub slug: String,
+    /// Date as shown on the site ("January 2023 – Present")
+    pub date: Option<String>,
+    /// Structured form of `date`, with its precision and range
+    pub date_detail: Option<ContentDate>,
+    pub title: String,
+    pub description: Option<Description>,
+    pub technologies: Vec<String>,
+    pub features: Vec<String>,
+    pub github_url: String,
+    #[serde(rename = "ReportUrl")]
+    pub report_url: Option<String>,
+    pub demo_url: Option<String>,
+    pub youtube_url: Option<String>,
+    pub images: Option<Vec<String>>,
+    pub featured: bool,
+    pub order: i32,
+    pub visibility: ProjectVisibility,
+    pub publication: PublicationStatus,
+    pub publish_at: Option<DateTime<Utc>>,
+}
+
+impl From<Project> for ProjectResponse {
+    fn from(project: Project) -> Self {
+        Self {
+            id: project.id.map(|id| id.to_hex()).unwrap_or_default(),
+            slug: project.slug,
+            date: project.date.as_ref().map(ToString::to_string),
+            date_detail: project.date,
+            title: project.title,
+            description: project.description,
+            technologies: project.technologies,
+            features: project.features,
+            github_url: project.github_url,
+            report_url: project.report_url,
+            demo_url: project.demo_url,
+            youtube_url: project.youtube_url,
+            images: project.images,
+            featured: project.featured,
+            order: project.order,
+            visibility: project.visibility,
+            publication: project.publication,
+            publish_at: project.publish_at,
+        }
+    }
+}
+
+/// DTO for updating projects - excludes _id, embedding and publication (auto-managed)
+/// and order, which only the reorder endpoint sets
 #[derive(Debug, Serialize, Deserialize, Validate)]
 #[cfg_attr(feature = "swagger", derive(ToSchema))]
 pub struct ProjectUpdate {
-    #[validate(length(min = 1))]
+    /// New slug for a rename, empty keeps the current one
+    #[serde(default)]
     pub slug: String,
 
-    pub date: String,
+    /// When the project was built, also accepted as text ("Jan 2023 - Present")
+    #[serde(default, deserialize_with = "date::optional")]
+    pub date: Option<ContentDate>,
 
     #[validate(length(min = 1))]
     pub title: String,
@@ -91,4 +300,75 @@ pub struct ProjectUpdate {
     pub youtube_url: Option<String>,
 
     pub images: Option<Vec<String>>,
+
+    /// Left out keeps the current value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub featured: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<ProjectVisibility>,
+
+    /// Only set by `retire_draft_visibility`, publication is changed through its endpoint
+    #[serde(skip_deserializing, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "swagger", schema(ignore))]
+    pub publication: Option<PublicationStatus>,
+}
+
+impl From<Project> for ProjectUpdate {
+    fn from(project: Project) -> Self {
+        Self {
+            slug: project.slug,
+            date: project.date,
+            title: project.title,
+            description: project.description,
+            technologies: project.technologies,
+            features: project.features,
+            github_url: project.github_url,
+            report_url: project.report_url,
+            demo_url: project.demo_url,
+            youtube_url: project.youtube_url,
+            images: project.images,
+            featured: Some(project.featured),
+            visibility: Some(project.visibility),
+            publication: None,
+        }
+    }
+}
+
+impl ProjectUpdate {
+    /// Canonical text used to generate the updated project's embedding
+    pub fn embedding_text(&self) -> String {
+        embedding_text(&self.title, self.description.as_ref(), &self.technologies)
+    }
+
+    /// Turn the legacy `draft` visibility into a public draft, before storing the update
+    pub fn retire_draft_visibility(&mut self) {
+        if self.visibility == Some(ProjectVisibility::Draft) {
+            self.visibility = Some(ProjectVisibility::Public);
+            self.publication = Some(PublicationStatus::Draft);
+        }
+    }
+
+    /// Apply the update onto an existing project, keeping auto-managed fields
+    pub fn apply_to(self, project: &mut Project) {
+        project.slug = self.slug;
+        project.date = self.date;
+        project.title = self.title;
+        project.description = self.description;
+        project.technologies = self.technologies;
+        project.features = self.features;
+        project.github_url = self.github_url;
+        project.report_url = self.report_url;
+        project.demo_url = self.demo_url;
+        project.youtube_url = self.youtube_url;
+        project.images = self.images;
+        if let Some(featured) = self.featured {
+            project.featured = featured;
+        }
+        if let Some(visibility) = self.visibility {
+            project.visibility = visibility;
+        }
+        if let Some(publication) = self.publication {
+            project.publication = publication;
+        }
+    }
 }
@@ -0,0 +1,145 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::ValidationError;
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+/// Where a project or certificate is in its publishing workflow
+/// Only published content is served publicly and known to the chat assistant
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum PublicationStatus {
+    /// Being written, visible to admins only
+    Draft,
+    /// Goes live by itself at `publish_at`
+    Scheduled,
+    /// Live - content stored before the workflow existed is published
+    #[default]
+    Published,
+    /// Taken down but kept, e.g. an outdated project
+    Archived,
+}
+
+impl PublicationStatus {
+    /// Lowercase name, as stored
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Draft => "draft",
+            Self::Scheduled => "scheduled",
+            Self::Published => "published",
+            Self::Archived => "archived",
+        }
+    }
+
+    /// Whether the workflow allows moving from this status to `next`
+    /// Rescheduling moves the date, published or archived content is scheduled via draft
+    pub fn can_become(self, next: Self) -> bool {
+        match self {
+            Self::Draft => next != Self::Draft,
+            Self::Scheduled => true,
+            Self::Published => matches!(next, Self::Draft | Self::Archived),
+            Self::Archived => matches!(next, Self::Draft | Self::Published),
+        }
+    }
+}
+
+/// Scheduled content needs a `publish_at`, which no other status keeps
+pub fn check_schedule(
+    status: PublicationStatus,
+    publish_at: Option<DateTime<Utc>>,
+) -> Result<(), ValidationError> {
+    match (status, publish_at) {
+        (PublicationStatus::Scheduled, None) => Err(ValidationError::new("publish_at_required")
+            .with_message("scheduled content needs a publish_at".into())),
+        (PublicationStatus::Scheduled, Some(_)) | (_, None) => Ok(()),
+        (_, Some(_)) => Err(ValidationError::new("publish_at_unscheduled")
+            .with_message("publish_at is only kept while scheduled".into())),
+    }
+}
+
+/// Serde helper for `publish_at`: stored as a BSON date, sent by clients as RFC 3339 text
+pub mod publish_at {
+    use bson::Bson;
+    use chrono::{DateTime, Utc};
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<DateTime<Utc>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        bson::serde_helpers::chrono_datetime_as_bson_datetime_optional::serialize(value, serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<DateTime<Utc>>, D::Error> {
+        match Option::<Bson>::deserialize(deserializer)? {
+            None | Some(Bson::Null) => Ok(None),
+            Some(Bson::DateTime(value)) => Ok(Some(value.to_chrono())),
+            Some(Bson::String(text)) => DateTime::parse_from_rfc3339(&text)
+                .map(|value| Some(value.with_timezone(&Utc)))
+                .map_err(|e| D::Error::custom(format!("publish_at: {}", e))),
+            Some(other) => Err(D::Error::custom(format!(
+                "publish_at must be a date, got {}",
+                other
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use PublicationStatus::*;
+
+    #[test]
+    fn test_workflow_transitions() {
+        assert!(Draft.can_become(Scheduled));
+        assert!(Scheduled.can_become(Scheduled));
+        assert!(Scheduled.can_become(Published));
+        assert!(Published.can_become(Archived));
+        assert!(Archived.can_become(Published));
+        assert!(!Published.can_become(Scheduled));
+        assert!(!Archived.can_become(Scheduled));
+        assert!(!Draft.can_become(Draft));
+        assert!(!Published.can_become(Published));
+
+        assert!(check_schedule(Scheduled, Some(Utc::now())).is_ok());
+        assert!(check_schedule(Scheduled, None).is_err());
+        assert!(check_schedule(Published, Some(Utc::now())).is_err());
+        assert!(check_schedule(Draft, None).is_ok());
+    }
+
+    #[test]
+    fn test_publish_at_reads_text_and_bson_dates() {
+        #[derive(Debug, Serialize, Deserialize)]
+        struct Scheduled {
+            #[serde(default, with = "publish_at")]
+            publish_at: Option<DateTime<Utc>>,
+        }
+        let at = DateTime::parse_from_rfc3339("2026-11-01T09:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let sent: Scheduled = serde_json::from_value(
+            serde_json::json!({ "publish_at": "2026-11-01T10:00:00+01:00" }),
+        )
+        .unwrap();
+        assert_eq!(sent.publish_at, Some(at));
+
+        let stored = bson::to_document(&sent).unwrap();
+        assert!(matches!(
+            stored.get("publish_at"),
+            Some(bson::Bson::DateTime(_))
+        ));
+        let read: Scheduled = bson::from_document(stored).unwrap();
+        assert_eq!(read.publish_at, Some(at));
+
+        assert!(
+            serde_json::from_value::<Scheduled>(serde_json::json!({ "publish_at": "soon" }))
+                .is_err()
+        );
+    }
+}
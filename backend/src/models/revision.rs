@@ -0,0 +1,190 @@
+use super::ContentKind;
+use crate::error::{ApiError, ApiResult};
+use chrono::{DateTime, Utc};
+use mongodb::bson::{oid::ObjectId, Document};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+/// Fields left out of diffs - bookkeeping, and embeddings derived from the content
+const DIFF_IGNORED: [&str; 3] = ["_id", "embedding", "version"];
+
+/// Immutable copy of a project or certificate as it was before an edit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Revision {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub kind: ContentKind,
+    /// Id of the edited document, stable across slug renames
+    pub content_id: ObjectId,
+    /// Slug the document had when this revision was taken
+    pub slug: String,
+    /// Full prior document
+    pub document: Document,
+    pub edited_by: String,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl Revision {
+    /// Snapshot `document` before `edited_by` changes it
+    pub fn capture<T: Serialize>(
+        kind: ContentKind,
+        document: &T,
+        edited_by: &str,
+    ) -> ApiResult<Self> {
+        let document = mongodb::bson::to_document(document)?;
+        let content_id = document
+            .get_object_id("_id")
+            .map_err(|_| ApiError::InternalError("Revision source has no ObjectId".to_string()))?;
+        let slug = document.get_str("slug").unwrap_or_default().to_string();
+
+        Ok(Self {
+            id: None,
+            kind,
+            content_id,
+            slug,
+            document,
+            edited_by: edited_by.to_string(),
+            created_at: Utc::now(),
+        })
+    }
+}
+
+/// Revision metadata as listed by the admin API
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct RevisionSummary {
+    pub id: String,
+    pub kind: ContentKind,
+    pub slug: String,
+    pub edited_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<&Revision> for RevisionSummary {
+    fn from(revision: &Revision) -> Self {
+        Self {
+            id: revision.id.map(|id| id.to_hex()).unwrap_or_default(),
+            kind: revision.kind,
+            slug: revision.slug.clone(),
+            edited_by: revision.edited_by.clone(),
+            created_at: revision.created_at,
+        }
+    }
+}
+
+/// One field that differs between two versions of a document
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct FieldChange {
+    /// Dotted path of the field, e.g. `description.overview`
+    pub path: String,
+    /// Old value, null when the field was absent
+    #[cfg_attr(feature = "swagger", schema(value_type = Object))]
+    pub before: Value,
+    /// New value, null when the field was removed
+    #[cfg_attr(feature = "swagger", schema(value_type = Object))]
+    pub after: Value,
+}
+
+/// Leaf values of a document keyed by dotted path, arrays are compared as a whole
+fn flatten(document: &Document) -> BTreeMap<String, Value> {
+    fn walk(prefix: &str, value: Value, out: &mut BTreeMap<String, Value>) {
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map {
+                    walk(&format!("{}{}.", prefix, key), value, out);
+                }
+            }
+            value => {
+                out.insert(prefix.trim_end_matches('.').to_string(), value);
+            }
+        }
+    }
+
+    let mut out = BTreeMap::new();
+    for (key, value) in document {
+        if !DIFF_IGNORED.contains(&key.as_str()) {
+            walk(
+                &format!("{}.", key),
+                value.clone().into_relaxed_extjson(),
+                &mut out,
+            );
+        }
+    }
+    out
+}
+
+/// Field-level changes from `before` to `after`, sorted by path
+pub fn diff(before: &Document, after: &Document) -> Vec<FieldChange> {
+    let mut before = flatten(before);
+    let after = flatten(after);
+
+    let mut changes: Vec<FieldChange> = after
+        .into_iter()
+        .filter_map(|(path, after)| {
+            let before = before.remove(&path).unwrap_or(Value::Null);
+            (before != after).then_some(FieldChange {
+                path,
+                before,
+                after,
+            })
+        })
+        .collect();
+    changes.extend(
+        before
+            .into_iter()
+            .filter(|(_, value)| !value.is_null())
+            .map(|(path, before)| FieldChange {
+                path,
+                before,
+                after: Value::Null,
+            }),
+    );
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mongodb::bson::doc;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_reports_changed_added_and_removed_paths() {
+        let before = doc! {
+            "_id": ObjectId::new(),
+            "title": "Rust API",
+            "description": { "overview": "Old", "impact": "Fast" },
+            "technologies": ["Rust"],
+            "embedding": [0.1, 0.2],
+            "demoUrl": "https://demo.example.com",
+        };
+        let after = doc! {
+            "_id": ObjectId::new(),
+            "title": "Rust API",
+            "description": { "overview": "New", "impact": "Fast" },
+            "technologies": ["Rust", "Axum"],
+            "embedding": [0.3, 0.4],
+            "demoUrl": null,
+            "images": ["a.png"],
+        };
+
+        let changes = diff(&before, &after);
+        let paths: Vec<&str> = changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec!["demoUrl", "description.overview", "images", "technologies"]
+        );
+        assert_eq!(changes[1].before, json!("Old"));
+        assert_eq!(changes[1].after, json!("New"));
+        assert_eq!(changes[2].before, Value::Null);
+        assert!(diff(&before, &before).is_empty());
+    }
+}
@@ -0,0 +1,567 @@
+use super::{
+    CertificateRepository, FieldChanges, ListQuery, Page, ProjectRepository, VersionCheck,
+};
+use crate::{
+    error::ApiResult,
+    models::{
+        certificate::CertificateUpdate, project::ProjectUpdate, Certificate, ContentKind, Project,
+        PublicationStatus,
+    },
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use serde::Serialize;
+use std::{
+    any::Any,
+    collections::{HashMap, VecDeque},
+    future::Future,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+/// Lifetime of cached reads unless the `READ_CACHE_TTL_SECS` secret overrides it
+pub const DEFAULT_READ_CACHE_TTL: Duration = Duration::from_secs(300);
+/// Entries kept per collection unless the `READ_CACHE_MAX_ENTRIES` secret overrides it
+pub const DEFAULT_READ_CACHE_ENTRIES: usize = 1000;
+
+struct Entry {
+    value: Arc<dyn Any + Send + Sync>,
+    expires_at: Instant,
+}
+
+/// Cached reads of one collection
+#[derive(Default)]
+struct Bucket {
+    /// Bumped by every write, so loads that raced a write are not stored
+    generation: u64,
+    entries: HashMap<String, Entry>,
+    /// Stored keys with their expiry, oldest first - all entries share one TTL,
+    /// so this is also expiry order
+    order: VecDeque<(String, Instant)>,
+}
+
+impl Bucket {
+    /// Drop expired entries, then the oldest ones until at most `keep` are left
+    /// Only looks at the front of the queue, so a store costs O(1) amortized
+    fn evict(&mut self, now: Instant, keep: usize) {
+        while let Some((key, expires_at)) = self.order.front() {
+            // A key stored again since has a newer place further back
+            let superseded = self
+                .entries
+                .get(key)
+                .is_none_or(|entry| entry.expires_at != *expires_at);
+            let expired = *expires_at <= now;
+            if !superseded && !expired && self.entries.len() <= keep {
+                break;
+            }
+            if !superseded {
+                self.entries.remove(key);
+            }
+            self.order.pop_front();
+        }
+    }
+
+    fn clear(&mut self) -> usize {
+        self.generation += 1;
+        self.order.clear();
+        let removed = self.entries.len();
+        self.entries.clear();
+        removed
+    }
+}
+
+/// Hit/miss counters and size of the read cache
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CacheStats {
+    pub enabled: bool,
+    pub ttl_secs: u64,
+    /// Most entries kept per collection
+    pub max_entries: usize,
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// In-process read-through cache of content reads, keyed per collection
+/// Entries expire after the TTL and are dropped as soon as their collection is written
+/// Each collection keeps at most `max_entries`, the oldest go first
+pub struct ReadCache {
+    ttl: Duration,
+    max_entries: usize,
+    buckets: Mutex<HashMap<ContentKind, Bucket>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl Default for ReadCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_READ_CACHE_TTL, DEFAULT_READ_CACHE_ENTRIES)
+    }
+}
+
+impl ReadCache {
+    /// A zero TTL or size disables caching, every read goes to the store
+    pub fn new(ttl: Duration, max_entries: usize) -> Self {
+        Self {
+            ttl,
+            max_entries,
+            buckets: Mutex::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Cached value of `key`, or the result of `load` which is cached on success
+    pub async fn get_or_load<T, E, F, Fut>(
+        &self,
+        kind: ContentKind,
+        key: &str,
+        load: F,
+    ) -> Result<T, E>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        if !self.enabled() {
+            return load().await;
+        }
+
+        let generation = {
+            let mut buckets = self.buckets.lock().unwrap();
+            let bucket = buckets.entry(kind).or_default();
+            let cached = bucket
+                .entries
+                .get(key)
+                .filter(|entry| entry.expires_at > Instant::now())
+                .and_then(|entry| entry.value.downcast_ref::<T>())
+                .cloned();
+            if let Some(value) = cached {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return Ok(value);
+            }
+            bucket.generation
+        };
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let value = load().await?;
+
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = buckets.entry(kind).or_default();
+        if bucket.generation == generation {
+            let now = Instant::now();
+            bucket.evict(now, self.max_entries - 1);
+            let expires_at = now + self.ttl;
+            bucket.entries.insert(
+                key.to_string(),
+                Entry {
+                    value: Arc::new(value.clone()),
+                    expires_at,
+                },
+            );
+            bucket.order.push_back((key.to_string(), expires_at));
+        }
+        Ok(value)
+    }
+
+    fn enabled(&self) -> bool {
+        !self.ttl.is_zero() && self.max_entries > 0
+    }
+
+    /// Drop every cached read of a collection after a write to it
+    pub fn invalidate(&self, kind: ContentKind) {
+        let mut buckets = self.buckets.lock().unwrap();
+        buckets.entry(kind).or_default().clear();
+    }
+
+    /// Drop everything, returns how many entries were removed
+    pub fn flush(&self) -> usize {
+        let mut buckets = self.buckets.lock().unwrap();
+        buckets.values_mut().map(Bucket::clear).sum()
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let buckets = self.buckets.lock().unwrap();
+        CacheStats {
+            enabled: self.enabled(),
+            ttl_secs: self.ttl.as_secs(),
+            max_entries: self.max_entries,
+            entries: buckets.values().map(|bucket| bucket.entries.len()).sum(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Project repository answering reads from a `ReadCache`
+pub struct CachedProjectRepository {
+    inner: Arc<dyn ProjectRepository>,
+    cache: Arc<ReadCache>,
+}
+
+impl CachedProjectRepository {
+    pub fn new(inner: Arc<dyn ProjectRepository>, cache: Arc<ReadCache>) -> Self {
+        Self { inner, cache }
+    }
+
+    fn written<T>(&self, result: ApiResult<T>) -> ApiResult<T> {
+        self.cache.invalidate(ContentKind::Project);
+        result
+    }
+}
+
+#[async_trait]
+impl ProjectRepository for CachedProjectRepository {
+    async fn list(&self) -> ApiResult<Vec<Project>> {
+        self.cache
+            .get_or_load(ContentKind::Project, "list", || self.inner.list())
+            .await
+    }
+
+    async fn searchable(&self) -> ApiResult<Vec<Project>> {
+        self.cache
+            .get_or_load(ContentKind::Project, "searchable", || {
+                self.inner.searchable()
+            })
+            .await
+    }
+
+    async fn find(&self, query: &ListQuery) -> ApiResult<Page<Project>> {
+        let key = format!("find:{:?}", query);
+        self.cache
+            .get_or_load(ContentKind::Project, &key, || self.inner.find(query))
+            .await
+    }
+
+    async fn get_by_slug(&self, slug: &str) -> ApiResult<Option<Project>> {
+        let key = format!("slug:{}", slug);
+        self.cache
+            .get_or_load(ContentKind::Project, &key, || self.inner.get_by_slug(slug))
+            .await
+    }
+
+    async fn create(&self, project: Project) -> ApiResult<ObjectId> {
+        let result = self.inner.create(project).await;
+        self.written(result)
+    }
+
+    async fn update(
+        &self,
+        slug: &str,
+        update: ProjectUpdate,
+        check: &VersionCheck,
+    ) -> ApiResult<Option<Project>> {
+        let result = self.inner.update(slug, update, check).await;
+        self.written(result)
+    }
+
+    async fn patch(
+        &self,
+        slug: &str,
+        changes: &FieldChanges,
+        check: &VersionCheck,
+    ) -> ApiResult<Option<Project>> {
+        let result = self.inner.patch(slug, changes, check).await;
+        self.written(result)
+    }
+
+    async fn trash(&self, slug: &str, deleted_by: &str, check: &VersionCheck) -> ApiResult<bool> {
+        let result = self.inner.trash(slug, deleted_by, check).await;
+        self.written(result)
+    }
+
+    async fn list_trash(&self) -> ApiResult<Vec<Project>> {
+        self.inner.list_trash().await
+    }
+
+    async fn restore(&self, slug: &str) -> ApiResult<bool> {
+        let result = self.inner.restore(slug).await;
+        self.written(result)
+    }
+
+    async fn purge_trash(&self, cutoff: DateTime<Utc>) -> ApiResult<u64> {
+        let result = self.inner.purge_trash(cutoff).await;
+        self.written(result)
+    }
+
+    async fn slug_in_use(&self, slug: &str) -> ApiResult<bool> {
+        self.inner.slug_in_use(slug).await
+    }
+
+    async fn set_embedding(&self, slug: &str, values: Vec<f64>, model: &str) -> ApiResult<bool> {
+        let result = self.inner.set_embedding(slug, values, model).await;
+        self.written(result)
+    }
+
+    async fn clear_embedding(&self, slug: &str) -> ApiResult<bool> {
+        let result = self.inner.clear_embedding(slug).await;
+        self.written(result)
+    }
+
+    async fn reorder(&self, slugs: &[String]) -> ApiResult<()> {
+        let result = self.inner.reorder(slugs).await;
+        self.written(result)
+    }
+
+    async fn set_publication(
+        &self,
+        slug: &str,
+        status: PublicationStatus,
+        publish_at: Option<DateTime<Utc>>,
+    ) -> ApiResult<bool> {
+        let result = self.inner.set_publication(slug, status, publish_at).await;
+        self.written(result)
+    }
+
+    /// Runs on every publisher tick, cached reads only go stale when something was published
+    async fn publish_due(&self, now: DateTime<Utc>) -> ApiResult<Vec<String>> {
+        let slugs = self.inner.publish_due(now).await?;
+        if !slugs.is_empty() {
+            self.cache.invalidate(ContentKind::Project);
+        }
+        Ok(slugs)
+    }
+}
+
+/// Certificate repository answering reads from a `ReadCache`
+pub struct CachedCertificateRepository {
+    inner: Arc<dyn CertificateRepository>,
+    cache: Arc<ReadCache>,
+}
+
+impl CachedCertificateRepository {
+    pub fn new(inner: Arc<dyn CertificateRepository>, cache: Arc<ReadCache>) -> Self {
+        Self { inner, cache }
+    }
+
+    fn written<T>(&self, result: ApiResult<T>) -> ApiResult<T> {
+        self.cache.invalidate(ContentKind::Certificate);
+        result
+    }
+}
+
+#[async_trait]
+impl CertificateRepository for CachedCertificateRepository {
+    async fn list(&self) -> ApiResult<Vec<Certificate>> {
+        self.cache
+            .get_or_load(ContentKind::Certificate, "list", || self.inner.list())
+            .await
+    }
+
+    async fn searchable(&self) -> ApiResult<Vec<Certificate>> {
+        self.cache
+            .get_or_load(ContentKind::Certificate, "searchable", || {
+                self.inner.searchable()
+            })
+            .await
+    }
+
+    async fn find(&self, query: &ListQuery) -> ApiResult<Page<Certificate>> {
+        let key = format!("find:{:?}", query);
+        self.cache
+            .get_or_load(ContentKind::Certificate, &key, || self.inner.find(query))
+            .await
+    }
+
+    async fn get_by_slug(&self, slug: &str) -> ApiResult<Option<Certificate>> {
+        let key = format!("slug:{}", slug);
+        self.cache
+            .get_or_load(ContentKind::Certificate, &key, || {
+                self.inner.get_by_slug(slug)
+            })
+            .await
+    }
+
+    async fn create(&self, certificate: Certificate) -> ApiResult<ObjectId> {
+        let result = self.inner.create(certificate).await;
+        self.written(result)
+    }
+
+    async fn update(
+        &self,
+        slug: &str,
+        update: CertificateUpdate,
+        check: &VersionCheck,
+    ) -> ApiResult<Option<Certificate>> {
+        let result = self.inner.update(slug, update, check).await;
+        self.written(result)
+    }
+
+    async fn patch(
+        &self,
+        slug: &str,
+        changes: &FieldChanges,
+        check: &VersionCheck,
+    ) -> ApiResult<Option<Certificate>> {
+        let result = self.inner.patch(slug, changes, check).await;
+        self.written(result)
+    }
+
+    async fn trash(&self, slug: &str, deleted_by: &str, check: &VersionCheck) -> ApiResult<bool> {
+        let result = self.inner.trash(slug, deleted_by, check).await;
+        self.written(result)
+    }
+
+    async fn list_trash(&self) -> ApiResult<Vec<Certificate>> {
+        self.inner.list_trash().await
+    }
+
+    async fn restore(&self, slug: &str) -> ApiResult<bool> {
+        let result = self.inner.restore(slug).await;
+        self.written(result)
+    }
+
+    async fn purge_trash(&self, cutoff: DateTime<Utc>) -> ApiResult<u64> {
+        let result = self.inner.purge_trash(cutoff).await;
+        self.written(result)
+    }
+
+    async fn slug_in_use(&self, slug: &str) -> ApiResult<bool> {
+        self.inner.slug_in_use(slug).await
+    }
+
+    async fn set_embedding(&self, slug: &str, values: Vec<f64>, model: &str) -> ApiResult<bool> {
+        let result = self.inner.set_embedding(slug, values, model).await;
+        self.written(result)
+    }
+
+    async fn clear_embedding(&self, slug: &str) -> ApiResult<bool> {
+        let result = self.inner.clear_embedding(slug).await;
+        self.written(result)
+    }
+
+    async fn set_verification(
+        &self,
+        slug: &str,
+        verified_at: Option<DateTime<Utc>>,
+    ) -> ApiResult<bool> {
+        let result = self.inner.set_verification(slug, verified_at).await;
+        self.written(result)
+    }
+
+    async fn set_publication(
+        &self,
+        slug: &str,
+        status: PublicationStatus,
+        publish_at: Option<DateTime<Utc>>,
+    ) -> ApiResult<bool> {
+        let result = self.inner.set_publication(slug, status, publish_at).await;
+        self.written(result)
+    }
+
+    /// Runs on every publisher tick, cached reads only go stale when something was published
+    async fn publish_due(&self, now: DateTime<Utc>) -> ApiResult<Vec<String>> {
+        let slugs = self.inner.publish_due(now).await?;
+        if !slugs.is_empty() {
+            self.cache.invalidate(ContentKind::Certificate);
+        }
+        Ok(slugs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::InMemoryProjectRepository;
+    use serde_json::json;
+
+    fn project(slug: &str) -> Project {
+        serde_json::from_value(json!({
+            "slug": slug,
+            "date": "2024",
+            "title": "Rust API",
+            "description": null,
+            "technologies": ["Rust"],
+            "features": [],
+            "githubUrl": "https://github.com/example/rust-api",
+            "embedding": null,
+            "images": null
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_reads_are_cached_until_a_write() {
+        let cache = Arc::new(ReadCache::default());
+        let inner = Arc::new(InMemoryProjectRepository::default());
+        let repo = CachedProjectRepository::new(inner.clone(), cache.clone());
+        repo.create(project("rust-api")).await.unwrap();
+
+        assert_eq!(repo.list().await.unwrap().len(), 1);
+        // Written behind the cache's back, so only visible once the entry goes
+        inner.create(project("go-api")).await.unwrap();
+        assert_eq!(repo.list().await.unwrap().len(), 1);
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 1, 1));
+
+        repo.trash("rust-api", "admin@example.com", &VersionCheck::Any)
+            .await
+            .unwrap();
+        let slugs: Vec<String> = repo
+            .list()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|p| p.slug)
+            .collect();
+        assert_eq!(slugs, vec!["go-api"]);
+        assert!(repo.get_by_slug("rust-api").await.unwrap().is_none());
+
+        assert_eq!(cache.flush(), 2);
+        assert_eq!(cache.stats().entries, 0);
+    }
+
+    #[tokio::test]
+    async fn test_entries_expire_and_zero_ttl_disables() {
+        let cache = ReadCache::new(Duration::from_millis(20), DEFAULT_READ_CACHE_ENTRIES);
+        let kind = ContentKind::Certificate;
+        let load = |n: u32| move || async move { Ok::<_, ()>(n) };
+
+        assert_eq!(cache.get_or_load(kind, "k", load(1)).await, Ok(1));
+        assert_eq!(cache.get_or_load(kind, "k", load(2)).await, Ok(1));
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(cache.get_or_load(kind, "k", load(3)).await, Ok(3));
+        // Failed loads are not cached
+        assert_eq!(
+            cache.get_or_load(kind, "e", || async { Err(()) }).await,
+            Err::<u32, _>(())
+        );
+        assert_eq!(cache.get_or_load(kind, "e", load(4)).await, Ok(4));
+
+        let disabled = ReadCache::new(Duration::ZERO, DEFAULT_READ_CACHE_ENTRIES);
+        assert_eq!(disabled.get_or_load(kind, "k", load(1)).await, Ok(1));
+        assert_eq!(disabled.get_or_load(kind, "k", load(2)).await, Ok(2));
+        assert!(!disabled.stats().enabled);
+    }
+
+    #[tokio::test]
+    async fn test_each_collection_keeps_its_newest_entries() {
+        let cache = ReadCache::new(Duration::from_secs(60), 2);
+        let kind = ContentKind::Project;
+        let load = |n: u32| move || async move { Ok::<_, ()>(n) };
+
+        for (key, n) in [("a", 1), ("b", 2), ("c", 3)] {
+            cache.get_or_load(kind, key, load(n)).await.unwrap();
+        }
+        cache
+            .get_or_load(ContentKind::Certificate, "a", load(1))
+            .await
+            .unwrap();
+        assert_eq!(cache.stats().entries, 3);
+
+        // "a" was the oldest project read and made room for "c"
+        assert_eq!(cache.get_or_load(kind, "c", load(30)).await, Ok(3));
+        assert_eq!(cache.get_or_load(kind, "a", load(10)).await, Ok(10));
+        assert_eq!(cache.get_or_load(kind, "b", load(20)).await, Ok(20));
+        assert_eq!(cache.stats().entries, 3);
+    }
+}
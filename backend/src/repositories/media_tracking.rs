@@ -0,0 +1,424 @@
+use super::{
+    CertificateRepository, FieldChanges, ListQuery, MediaRepository, Page, ProjectRepository,
+    VersionCheck,
+};
+use crate::{
+    error::ApiResult,
+    models::{
+        certificate::CertificateUpdate,
+        media::{referenced_urls, MediaReference},
+        project::ProjectUpdate,
+        Certificate, ContentKind, Project, PublicationStatus,
+    },
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mongodb::bson::{oid::ObjectId, Document};
+use serde::Serialize;
+use std::sync::Arc;
+
+/// Point the media library at the URLs a document holds after a write
+/// The content write already happened, so a failure is logged rather than returned;
+/// the reference counts of the document catch up on its next write
+async fn track(media: &dyn MediaRepository, kind: ContentKind, id: ObjectId, document: &Document) {
+    let reference = MediaReference {
+        kind,
+        content_id: id,
+        slug: document.get_str("slug").unwrap_or_default().to_string(),
+    };
+    let urls = referenced_urls(kind, document);
+    if let Err(e) = media.set_references(reference, &urls).await {
+        tracing::error!(
+            "Failed to update media references of {} {}: {}",
+            kind.as_str(),
+            id,
+            e
+        );
+    }
+}
+
+/// Document as written by a patch: the prior document with the changes applied
+fn after_patch<T: Serialize>(before: &T, changes: &FieldChanges) -> ApiResult<Document> {
+    let mut document = mongodb::bson::to_document(before)?;
+    changes.apply(&mut document);
+    Ok(document)
+}
+
+/// Project repository keeping media reference counts in step with every write
+/// Trashed projects keep their references until purged, so a restore never finds
+/// its images deleted as orphans
+pub struct MediaTrackingProjectRepository {
+    inner: Arc<dyn ProjectRepository>,
+    media: Arc<dyn MediaRepository>,
+}
+
+impl MediaTrackingProjectRepository {
+    pub fn new(inner: Arc<dyn ProjectRepository>, media: Arc<dyn MediaRepository>) -> Self {
+        Self { inner, media }
+    }
+}
+
+#[async_trait]
+impl ProjectRepository for MediaTrackingProjectRepository {
+    async fn list(&self) -> ApiResult<Vec<Project>> {
+        self.inner.list().await
+    }
+
+    async fn searchable(&self) -> ApiResult<Vec<Project>> {
+        self.inner.searchable().await
+    }
+
+    async fn find(&self, query: &ListQuery) -> ApiResult<Page<Project>> {
+        self.inner.find(query).await
+    }
+
+    async fn get_by_slug(&self, slug: &str) -> ApiResult<Option<Project>> {
+        self.inner.get_by_slug(slug).await
+    }
+
+    async fn create(&self, project: Project) -> ApiResult<ObjectId> {
+        let document = mongodb::bson::to_document(&project)?;
+        let id = self.inner.create(project).await?;
+        track(self.media.as_ref(), ContentKind::Project, id, &document).await;
+        Ok(id)
+    }
+
+    async fn update(
+        &self,
+        slug: &str,
+        update: ProjectUpdate,
+        check: &VersionCheck,
+    ) -> ApiResult<Option<Project>> {
+        let mut document = mongodb::bson::to_document(&update)?;
+        if update.slug.is_empty() {
+            document.insert("slug", slug);
+        }
+        let before = self.inner.update(slug, update, check).await?;
+        if let Some(id) = before.as_ref().and_then(|project| project.id) {
+            track(self.media.as_ref(), ContentKind::Project, id, &document).await;
+        }
+        Ok(before)
+    }
+
+    async fn patch(
+        &self,
+        slug: &str,
+        changes: &FieldChanges,
+        check: &VersionCheck,
+    ) -> ApiResult<Option<Project>> {
+        let before = self.inner.patch(slug, changes, check).await?;
+        if let Some((project, Some(id))) = before.as_ref().map(|project| (project, project.id)) {
+            let document = after_patch(project, changes)?;
+            track(self.media.as_ref(), ContentKind::Project, id, &document).await;
+        }
+        Ok(before)
+    }
+
+    async fn trash(&self, slug: &str, deleted_by: &str, check: &VersionCheck) -> ApiResult<bool> {
+        self.inner.trash(slug, deleted_by, check).await
+    }
+
+    async fn list_trash(&self) -> ApiResult<Vec<Project>> {
+        self.inner.list_trash().await
+    }
+
+    async fn restore(&self, slug: &str) -> ApiResult<bool> {
+        self.inner.restore(slug).await
+    }
+
+    async fn purge_trash(&self, cutoff: DateTime<Utc>) -> ApiResult<u64> {
+        let expired: Vec<Project> = self
+            .inner
+            .list_trash()
+            .await?
+            .into_iter()
+            .filter(|project| project.deleted_at.is_some_and(|at| at < cutoff))
+            .collect();
+        let purged = self.inner.purge_trash(cutoff).await?;
+
+        for project in expired {
+            // Restored between listing and purging, still holding its images
+            if self.inner.slug_in_use(&project.slug).await? {
+                continue;
+            }
+            if let Some(id) = project.id {
+                track(
+                    self.media.as_ref(),
+                    ContentKind::Project,
+                    id,
+                    &Document::new(),
+                )
+                .await;
+            }
+        }
+        Ok(purged)
+    }
+
+    async fn slug_in_use(&self, slug: &str) -> ApiResult<bool> {
+        self.inner.slug_in_use(slug).await
+    }
+
+    async fn set_embedding(&self, slug: &str, values: Vec<f64>, model: &str) -> ApiResult<bool> {
+        self.inner.set_embedding(slug, values, model).await
+    }
+
+    async fn clear_embedding(&self, slug: &str) -> ApiResult<bool> {
+        self.inner.clear_embedding(slug).await
+    }
+
+    async fn reorder(&self, slugs: &[String]) -> ApiResult<()> {
+        self.inner.reorder(slugs).await
+    }
+
+    async fn set_publication(
+        &self,
+        slug: &str,
+        status: PublicationStatus,
+        publish_at: Option<DateTime<Utc>>,
+    ) -> ApiResult<bool> {
+        self.inner.set_publication(slug, status, publish_at).await
+    }
+
+    async fn publish_due(&self, now: DateTime<Utc>) -> ApiResult<Vec<String>> {
+        self.inner.publish_due(now).await
+    }
+}
+
+/// Certificate repository keeping media reference counts in step with every write
+pub struct MediaTrackingCertificateRepository {
+    inner: Arc<dyn CertificateRepository>,
+    media: Arc<dyn MediaRepository>,
+}
+
+impl MediaTrackingCertificateRepository {
+    pub fn new(inner: Arc<dyn CertificateRepository>, media: Arc<dyn MediaRepository>) -> Self {
+        Self { inner, media }
+    }
+}
+
+#[async_trait]
+impl CertificateRepository for MediaTrackingCertificateRepository {
+    async fn list(&self) -> ApiResult<Vec<Certificate>> {
+        self.inner.list().await
+    }
+
+    async fn searchable(&self) -> ApiResult<Vec<Certificate>> {
+        self.inner.searchable().await
+    }
+
+    async fn find(&self, query: &ListQuery) -> ApiResult<Page<Certificate>> {
+        self.inner.find(query).await
+    }
+
+    async fn get_by_slug(&self, slug: &str) -> ApiResult<Option<Certificate>> {
+        self.inner.get_by_slug(slug).await
+    }
+
+    async fn create(&self, certificate: Certificate) -> ApiResult<ObjectId> {
+        let document = mongodb::bson::to_document(&certificate)?;
+        let id = self.inner.create(certificate).await?;
+        track(self.media.as_ref(), ContentKind::Certificate, id, &document).await;
+        Ok(id)
+    }
+
+    async fn update(
+        &self,
+        slug: &str,
+        update: CertificateUpdate,
+        check: &VersionCheck,
+    ) -> ApiResult<Option<Certificate>> {
+        let mut document = mongodb::bson::to_document(&update)?;
+        document.insert("slug", slug);
+        let before = self.inner.update(slug, update, check).await?;
+        if let Some(id) = before.as_ref().and_then(|certificate| certificate.id) {
+            track(self.media.as_ref(), ContentKind::Certificate, id, &document).await;
+        }
+        Ok(before)
+    }
+
+    async fn patch(
+        &self,
+        slug: &str,
+        changes: &FieldChanges,
+        check: &VersionCheck,
+    ) -> ApiResult<Option<Certificate>> {
+        let before = self.inner.patch(slug, changes, check).await?;
+        if let Some((certificate, Some(id))) = before
+            .as_ref()
+            .map(|certificate| (certificate, certificate.id))
+        {
+            let document = after_patch(certificate, changes)?;
+            track(self.media.as_ref(), ContentKind::Certificate, id, &document).await;
+        }
+        Ok(before)
+    }
+
+    async fn trash(&self, slug: &str, deleted_by: &str, check: &VersionCheck) -> ApiResult<bool> {
+        self.inner.trash(slug, deleted_by, check).await
+    }
+
+    async fn list_trash(&self) -> ApiResult<Vec<Certificate>> {
+        self.inner.list_trash().await
+    }
+
+    async fn restore(&self, slug: &str) -> ApiResult<bool> {
+        self.inner.restore(slug).await
+    }
+
+    async fn purge_trash(&self, cutoff: DateTime<Utc>) -> ApiResult<u64> {
+        let expired: Vec<Certificate> = self
+            .inner
+            .list_trash()
+            .await?
+            .into_iter()
+            .filter(|certificate| certificate.deleted_at.is_some_and(|at| at < cutoff))
+            .collect();
+        let purged = self.inner.purge_trash(cutoff).await?;
+
+        for certificate in expired {
+            if self.inner.slug_in_use(&certificate.slug).await? {
+                continue;
+            }
+            if let Some(id) = certificate.id {
+                track(
+                    self.media.as_ref(),
+                    ContentKind::Certificate,
+                    id,
+                    &Document::new(),
+                )
+                .await;
+            }
+        }
+        Ok(purged)
+    }
+
+    async fn slug_in_use(&self, slug: &str) -> ApiResult<bool> {
+        self.inner.slug_in_use(slug).await
+    }
+
+    async fn set_embedding(&self, slug: &str, values: Vec<f64>, model: &str) -> ApiResult<bool> {
+        self.inner.set_embedding(slug, values, model).await
+    }
+
+    async fn clear_embedding(&self, slug: &str) -> ApiResult<bool> {
+        self.inner.clear_embedding(slug).await
+    }
+
+    async fn set_verification(
+        &self,
+        slug: &str,
+        verified_at: Option<DateTime<Utc>>,
+    ) -> ApiResult<bool> {
+        self.inner.set_verification(slug, verified_at).await
+    }
+
+    async fn set_publication(
+        &self,
+        slug: &str,
+        status: PublicationStatus,
+        publish_at: Option<DateTime<Utc>>,
+    ) -> ApiResult<bool> {
+        self.inner.set_publication(slug, status, publish_at).await
+    }
+
+    async fn publish_due(&self, now: DateTime<Utc>) -> ApiResult<Vec<String>> {
+        self.inner.publish_due(now).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::media::MediaAsset,
+        repositories::{InMemoryMediaRepository, InMemoryProjectRepository},
+    };
+    use chrono::Duration;
+    use mongodb::bson::doc;
+    use serde_json::json;
+
+    fn asset(key: &str) -> MediaAsset {
+        let url = format!("https://media.example.com/{}", key);
+        MediaAsset {
+            id: None,
+            key: key.to_string(),
+            url: url.clone(),
+            backend: "stub".to_string(),
+            file_name: None,
+            content_type: "image/png".to_string(),
+            bytes: 10,
+            width: 1,
+            height: 1,
+            thumbnails: Vec::new(),
+            urls: vec![url],
+            uploaded_by: "admin@example.com".to_string(),
+            uploaded_at: Utc::now() - Duration::days(2),
+            references: Vec::new(),
+            reference_count: 0,
+        }
+    }
+
+    fn counts(assets: &[MediaAsset]) -> Vec<(&str, i64)> {
+        let mut counts: Vec<_> = assets
+            .iter()
+            .map(|a| (a.key.as_str(), a.reference_count))
+            .collect();
+        counts.sort();
+        counts
+    }
+
+    #[tokio::test]
+    async fn test_writes_update_reference_counts() {
+        let media = Arc::new(InMemoryMediaRepository::default());
+        media.create(asset("a.png")).await.unwrap();
+        media.create(asset("b.png")).await.unwrap();
+        let projects = MediaTrackingProjectRepository::new(
+            Arc::new(InMemoryProjectRepository::default()),
+            media.clone(),
+        );
+
+        let project: Project = serde_json::from_value(json!({
+            "slug": "rust-api",
+            "date": "2024",
+            "title": "Rust API",
+            "technologies": ["Rust"],
+            "features": [],
+            "githubUrl": "https://github.com/example/rust-api",
+            "images": ["https://media.example.com/a.png", "https://elsewhere.example.com/c.png"]
+        }))
+        .unwrap();
+        projects.create(project).await.unwrap();
+        let assets = media.list().await.unwrap();
+        assert_eq!(counts(&assets), vec![("a.png", 1), ("b.png", 0)]);
+        let reference = &assets.iter().find(|a| a.key == "a.png").unwrap().references[0];
+        assert_eq!(reference.slug, "rust-api");
+
+        // Renaming and switching images moves the reference
+        let changes = FieldChanges {
+            set: doc! { "slug": "rust-service", "images": ["https://media.example.com/b.png"] },
+            unset: Vec::new(),
+        };
+        projects
+            .patch("rust-api", &changes, &VersionCheck::Any)
+            .await
+            .unwrap();
+        let assets = media.list().await.unwrap();
+        assert_eq!(counts(&assets), vec![("a.png", 0), ("b.png", 1)]);
+        assert_eq!(assets[0].references[0].slug, "rust-service");
+
+        // Trashed content keeps its images, purging it releases them
+        projects
+            .trash("rust-service", "admin@example.com", &VersionCheck::Any)
+            .await
+            .unwrap();
+        let orphans = media.list_orphans(Utc::now()).await.unwrap();
+        assert_eq!(counts(&orphans), vec![("a.png", 0)]);
+
+        projects
+            .purge_trash(Utc::now() + Duration::seconds(1))
+            .await
+            .unwrap();
+        let orphans = media.list_orphans(Utc::now()).await.unwrap();
+        assert_eq!(counts(&orphans), vec![("a.png", 0), ("b.png", 0)]);
+    }
+}
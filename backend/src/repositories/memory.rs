@@ -0,0 +1,945 @@
+use super::{
+    query::{
+        modified_since_read, projects_not_found, reordered, FieldChanges, ListQuery, ListSort,
+        Page, VersionCheck,
+    },
+    CertificateRepository, LinkCheckRepository, MediaRepository, ProjectRepository,
+    RevisionRepository, SlugRedirectRepository,
+};
+use crate::{
+    error::{ApiError, ApiResult},
+    models::{
+        certificate::{CertificateUpdate, VERIFIED_FIELDS},
+        date::PartialDate,
+        link::LinkCheck,
+        media::{MediaAsset, MediaReference},
+        project::ProjectUpdate,
+        Certificate, ContentDate, ContentKind, Project, PublicationStatus, Revision,
+    },
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{cmp::Ordering, collections::HashMap, sync::RwLock};
+
+/// True if the optional date covers the requested year, mirroring `year_filter`
+fn matches_year(date: Option<&ContentDate>, year: Option<i32>) -> bool {
+    year.is_none_or(|year| date.is_some_and(|date| date.covers_year(year)))
+}
+
+/// Mirror of the unique slug index on the MongoDB collections
+fn duplicate_slug(slug: &str) -> ApiError {
+    ApiError::Conflict(format!("Slug '{}' is already in use", slug))
+}
+
+/// Copy of `item` with a partial update applied through its BSON form
+fn patched<T: Serialize + DeserializeOwned>(item: &T, changes: &FieldChanges) -> ApiResult<T> {
+    let mut document = mongodb::bson::to_document(item)?;
+    changes.apply(&mut document);
+    mongodb::bson::from_document(document)
+        .map_err(|e| ApiError::BadRequest(format!("Patch does not fit the model: {}", e)))
+}
+
+/// New slug of a patch that renames
+fn renamed_to(changes: &FieldChanges) -> Option<&str> {
+    changes.set.get_str("slug").ok()
+}
+
+/// True for scheduled content whose publication time has come
+fn is_due(
+    status: PublicationStatus,
+    publish_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> bool {
+    status == PublicationStatus::Scheduled && publish_at.is_some_and(|at| at <= now)
+}
+
+/// Sort by (date, title) keys, then stably by `rank`, and slice out the requested page
+fn paginate<T, R: Ord>(
+    mut items: Vec<T>,
+    query: &ListQuery,
+    keys: impl Fn(&T) -> (Option<PartialDate>, String),
+    rank: impl Fn(&T) -> R,
+) -> Page<T> {
+    let compare = |a: &T, b: &T, by_date: bool| -> Ordering {
+        let (a_date, a_title) = keys(a);
+        let (b_date, b_title) = keys(b);
+        if by_date {
+            a_date.cmp(&b_date)
+        } else {
+            a_title.to_lowercase().cmp(&b_title.to_lowercase())
+        }
+    };
+
+    match query.sort {
+        ListSort::Inserted => {}
+        ListSort::DateAsc => items.sort_by(|a, b| compare(a, b, true)),
+        ListSort::DateDesc => items.sort_by(|a, b| compare(b, a, true)),
+        ListSort::TitleAsc => items.sort_by(|a, b| compare(a, b, false)),
+        ListSort::TitleDesc => items.sort_by(|a, b| compare(b, a, false)),
+    }
+    items.sort_by_key(rank);
+
+    let total = items.len() as u64;
+    let page: Vec<T> = items
+        .into_iter()
+        .skip(query.offset as usize)
+        .take(query.limit.map_or(usize::MAX, |limit| limit as usize))
+        .collect();
+    let next_offset = query.next_offset(page.len(), total);
+
+    Page {
+        items: page,
+        total,
+        next_offset,
+    }
+}
+
+/// In-memory project repository for tests and local development without MongoDB
+#[derive(Default)]
+pub struct InMemoryProjectRepository {
+    projects: RwLock<Vec<Project>>,
+}
+
+#[async_trait]
+impl ProjectRepository for InMemoryProjectRepository {
+    async fn list(&self) -> ApiResult<Vec<Project>> {
+        let projects = self.projects.read().unwrap();
+        Ok(projects
+            .iter()
+            .filter(|p| !p.is_deleted())
+            .cloned()
+            .collect())
+    }
+
+    async fn searchable(&self) -> ApiResult<Vec<Project>> {
+        let projects = self.projects.read().unwrap();
+        Ok(projects
+            .iter()
+            .filter(|p| !p.is_deleted() && p.is_listed())
+            .map(|p| Project {
+                embedding: None,
+                ..p.clone()
+            })
+            .collect())
+    }
+
+    async fn find(&self, query: &ListQuery) -> ApiResult<Page<Project>> {
+        let technology = query.technology.as_deref().map(str::trim);
+        let projects: Vec<Project> = self
+            .projects
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|p| !p.is_deleted() && p.is_listed())
+            .filter(|p| {
+                technology
+                    .is_none_or(|tech| p.technologies.iter().any(|t| t.eq_ignore_ascii_case(tech)))
+            })
+            .filter(|p| matches_year(p.date.as_ref(), query.year))
+            .cloned()
+            .collect();
+
+        // Featured first, then the admin's order unless another sort was asked for
+        let by_order = query.sort == ListSort::Inserted;
+        Ok(paginate(
+            projects,
+            query,
+            |p| (p.date.as_ref().map(|date| date.start), p.title.clone()),
+            |p| (!p.featured, if by_order { p.order } else { 0 }),
+        ))
+    }
+
+    async fn get_by_slug(&self, slug: &str) -> ApiResult<Option<Project>> {
+        let projects = self.projects.read().unwrap();
+        Ok(projects
+            .iter()
+            .find(|p| p.slug == slug && !p.is_deleted())
+            .cloned())
+    }
+
+    async fn create(&self, mut project: Project) -> ApiResult<ObjectId> {
+        let mut projects = self.projects.write().unwrap();
+        if projects.iter().any(|p| p.slug == project.slug) {
+            return Err(duplicate_slug(&project.slug));
+        }
+        let id = *project.id.get_or_insert_with(ObjectId::new);
+        projects.push(project);
+        Ok(id)
+    }
+
+    async fn update(
+        &self,
+        slug: &str,
+        update: ProjectUpdate,
+        check: &VersionCheck,
+    ) -> ApiResult<Option<Project>> {
+        let mut projects = self.projects.write().unwrap();
+        if update.slug != slug && projects.iter().any(|p| p.slug == update.slug) {
+            return Err(duplicate_slug(&update.slug));
+        }
+        match projects
+            .iter_mut()
+            .find(|p| p.slug == slug && !p.is_deleted())
+        {
+            Some(project) => {
+                if !check.allows(project.id, project.version) {
+                    return Err(modified_since_read(slug));
+                }
+                let before = project.clone();
+                update.apply_to(project);
+                project.version += 1;
+                Ok(Some(before))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn patch(
+        &self,
+        slug: &str,
+        changes: &FieldChanges,
+        check: &VersionCheck,
+    ) -> ApiResult<Option<Project>> {
+        let mut projects = self.projects.write().unwrap();
+        if let Some(new_slug) = renamed_to(changes) {
+            if new_slug != slug && projects.iter().any(|p| p.slug == new_slug) {
+                return Err(duplicate_slug(new_slug));
+            }
+        }
+        match projects
+            .iter_mut()
+            .find(|p| p.slug == slug && !p.is_deleted())
+        {
+            Some(item) => {
+                if !check.allows(item.id, item.version) {
+                    return Err(modified_since_read(slug));
+                }
+                let before = item.clone();
+                *item = patched(item, changes)?;
+                item.version += 1;
+                Ok(Some(before))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn trash(&self, slug: &str, deleted_by: &str, check: &VersionCheck) -> ApiResult<bool> {
+        let mut projects = self.projects.write().unwrap();
+        match projects
+            .iter_mut()
+            .find(|p| p.slug == slug && !p.is_deleted())
+        {
+            Some(item) => {
+                if !check.allows(item.id, item.version) {
+                    return Err(modified_since_read(slug));
+                }
+                item.deleted_at = Some(Utc::now());
+                item.deleted_by = Some(deleted_by.to_string());
+                item.version += 1;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn list_trash(&self) -> ApiResult<Vec<Project>> {
+        let mut trashed: Vec<Project> = self
+            .projects
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|p| p.is_deleted())
+            .cloned()
+            .collect();
+        trashed.sort_by_key(|item| std::cmp::Reverse(item.deleted_at));
+        Ok(trashed)
+    }
+
+    async fn restore(&self, slug: &str) -> ApiResult<bool> {
+        let mut projects = self.projects.write().unwrap();
+        match projects
+            .iter_mut()
+            .find(|p| p.slug == slug && p.is_deleted())
+        {
+            Some(item) => {
+                item.deleted_at = None;
+                item.deleted_by = None;
+                item.version += 1;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn purge_trash(&self, cutoff: DateTime<Utc>) -> ApiResult<u64> {
+        let mut projects = self.projects.write().unwrap();
+        let before = projects.len();
+        projects.retain(|p| p.deleted_at.is_none_or(|deleted_at| deleted_at >= cutoff));
+        Ok((before - projects.len()) as u64)
+    }
+
+    async fn slug_in_use(&self, slug: &str) -> ApiResult<bool> {
+        let projects = self.projects.read().unwrap();
+        Ok(projects.iter().any(|p| p.slug == slug))
+    }
+
+    async fn set_embedding(&self, slug: &str, values: Vec<f64>, model: &str) -> ApiResult<bool> {
+        let mut projects = self.projects.write().unwrap();
+        match projects.iter_mut().find(|p| p.slug == slug) {
+            Some(item) => {
+                item.set_embedding(values, model.to_string());
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn clear_embedding(&self, slug: &str) -> ApiResult<bool> {
+        let mut projects = self.projects.write().unwrap();
+        match projects
+            .iter_mut()
+            .find(|p| p.slug == slug && !p.is_deleted())
+        {
+            Some(item) => {
+                item.clear_embedding();
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn reorder(&self, slugs: &[String]) -> ApiResult<()> {
+        let mut projects = self.projects.write().unwrap();
+        let missing: Vec<&str> = slugs
+            .iter()
+            .filter(|slug| !projects.iter().any(|p| &p.slug == *slug && !p.is_deleted()))
+            .map(String::as_str)
+            .collect();
+        if !missing.is_empty() {
+            return Err(projects_not_found(&missing));
+        }
+        // Sorting is stable, so projects sharing an order stay in insertion order
+        let mut live: Vec<&Project> = projects.iter().filter(|p| !p.is_deleted()).collect();
+        live.sort_by_key(|p| p.order);
+        let current: Vec<&str> = live.iter().map(|p| p.slug.as_str()).collect();
+        let positions: HashMap<String, i32> = reordered(&current, slugs)
+            .into_iter()
+            .enumerate()
+            .map(|(position, slug)| (slug.to_string(), position as i32))
+            .collect();
+        for project in projects.iter_mut().filter(|p| !p.is_deleted()) {
+            let position = positions[&project.slug];
+            if project.order != position {
+                project.order = position;
+                project.version += 1;
+            }
+        }
+        Ok(())
+    }
+
+    async fn set_publication(
+        &self,
+        slug: &str,
+        status: PublicationStatus,
+        publish_at: Option<DateTime<Utc>>,
+    ) -> ApiResult<bool> {
+        let mut projects = self.projects.write().unwrap();
+        match projects
+            .iter_mut()
+            .find(|p| p.slug == slug && !p.is_deleted())
+        {
+            Some(item) => {
+                item.set_publication(status, publish_at);
+                item.version += 1;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn publish_due(&self, now: DateTime<Utc>) -> ApiResult<Vec<String>> {
+        let mut projects = self.projects.write().unwrap();
+        Ok(projects
+            .iter_mut()
+            .filter(|p| !p.is_deleted() && is_due(p.publication, p.publish_at, now))
+            .map(|item| {
+                item.set_publication(PublicationStatus::Published, None);
+                item.version += 1;
+                item.slug.clone()
+            })
+            .collect())
+    }
+}
+
+/// In-memory certificate repository for tests and local development without MongoDB
+#[derive(Default)]
+pub struct InMemoryCertificateRepository {
+    certificates: RwLock<Vec<Certificate>>,
+}
+
+#[async_trait]
+impl CertificateRepository for InMemoryCertificateRepository {
+    async fn list(&self) -> ApiResult<Vec<Certificate>> {
+        let certificates = self.certificates.read().unwrap();
+        Ok(certificates
+            .iter()
+            .filter(|c| !c.is_deleted())
+            .cloned()
+            .collect())
+    }
+
+    async fn searchable(&self) -> ApiResult<Vec<Certificate>> {
+        let certificates = self.certificates.read().unwrap();
+        Ok(certificates
+            .iter()
+            .filter(|c| !c.is_deleted() && c.is_published())
+            .map(|c| Certificate {
+                embedding: None,
+                ..c.clone()
+            })
+            .collect())
+    }
+
+    async fn find(&self, query: &ListQuery) -> ApiResult<Page<Certificate>> {
+        let issuer = query.issuer.as_deref().map(str::trim);
+        let certificates: Vec<Certificate> = self
+            .certificates
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|c| !c.is_deleted() && c.is_published())
+            .filter(|c| issuer.is_none_or(|issuer| c.issuer.eq_ignore_ascii_case(issuer)))
+            .filter(|c| matches_year(c.issue_date.as_ref(), query.year))
+            .cloned()
+            .collect();
+
+        Ok(paginate(
+            certificates,
+            query,
+            |c| (c.issue_date.as_ref().map(|date| date.start), c.name.clone()),
+            |_| (),
+        ))
+    }
+
+    async fn get_by_slug(&self, slug: &str) -> ApiResult<Option<Certificate>> {
+        let certificates = self.certificates.read().unwrap();
+        Ok(certificates
+            .iter()
+            .find(|c| c.slug == slug && !c.is_deleted())
+            .cloned())
+    }
+
+    async fn create(&self, mut certificate: Certificate) -> ApiResult<ObjectId> {
+        let mut certificates = self.certificates.write().unwrap();
+        if certificates.iter().any(|c| c.slug == certificate.slug) {
+            return Err(duplicate_slug(&certificate.slug));
+        }
+        let id = *certificate.id.get_or_insert_with(ObjectId::new);
+        certificates.push(certificate);
+        Ok(id)
+    }
+
+    async fn update(
+        &self,
+        slug: &str,
+        update: CertificateUpdate,
+        check: &VersionCheck,
+    ) -> ApiResult<Option<Certificate>> {
+        let mut certificates = self.certificates.write().unwrap();
+        match certificates
+            .iter_mut()
+            .find(|c| c.slug == slug && !c.is_deleted())
+        {
+            Some(certificate) => {
+                if !check.allows(certificate.id, certificate.version) {
+                    return Err(modified_since_read(slug));
+                }
+                let before = certificate.clone();
+                update.apply_to(certificate);
+                certificate.version += 1;
+                Ok(Some(before))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn patch(
+        &self,
+        slug: &str,
+        changes: &FieldChanges,
+        check: &VersionCheck,
+    ) -> ApiResult<Option<Certificate>> {
+        let mut certificates = self.certificates.write().unwrap();
+        if let Some(new_slug) = renamed_to(changes) {
+            if new_slug != slug && certificates.iter().any(|c| c.slug == new_slug) {
+                return Err(duplicate_slug(new_slug));
+            }
+        }
+        match certificates
+            .iter_mut()
+            .find(|c| c.slug == slug && !c.is_deleted())
+        {
+            Some(item) => {
+                if !check.allows(item.id, item.version) {
+                    return Err(modified_since_read(slug));
+                }
+                let before = item.clone();
+                *item = patched(item, &changes.voiding_verification(&VERIFIED_FIELDS))?;
+                item.version += 1;
+                Ok(Some(before))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn trash(&self, slug: &str, deleted_by: &str, check: &VersionCheck) -> ApiResult<bool> {
+        let mut certificates = self.certificates.write().unwrap();
+        match certificates
+            .iter_mut()
+            .find(|c| c.slug == slug && !c.is_deleted())
+        {
+            Some(item) => {
+                if !check.allows(item.id, item.version) {
+                    return Err(modified_since_read(slug));
+                }
+                item.deleted_at = Some(Utc::now());
+                item.deleted_by = Some(deleted_by.to_string());
+                item.version += 1;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn list_trash(&self) -> ApiResult<Vec<Certificate>> {
+        let mut trashed: Vec<Certificate> = self
+            .certificates
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|c| c.is_deleted())
+            .cloned()
+            .collect();
+        trashed.sort_by_key(|item| std::cmp::Reverse(item.deleted_at));
+        Ok(trashed)
+    }
+
+    async fn restore(&self, slug: &str) -> ApiResult<bool> {
+        let mut certificates = self.certificates.write().unwrap();
+        match certificates
+            .iter_mut()
+            .find(|c| c.slug == slug && c.is_deleted())
+        {
+            Some(item) => {
+                item.deleted_at = None;
+                item.deleted_by = None;
+                item.version += 1;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn purge_trash(&self, cutoff: DateTime<Utc>) -> ApiResult<u64> {
+        let mut certificates = self.certificates.write().unwrap();
+        let before = certificates.len();
+        certificates.retain(|c| c.deleted_at.is_none_or(|deleted_at| deleted_at >= cutoff));
+        Ok((before - certificates.len()) as u64)
+    }
+
+    async fn slug_in_use(&self, slug: &str) -> ApiResult<bool> {
+        let certificates = self.certificates.read().unwrap();
+        Ok(certificates.iter().any(|c| c.slug == slug))
+    }
+
+    async fn set_embedding(&self, slug: &str, values: Vec<f64>, model: &str) -> ApiResult<bool> {
+        let mut certificates = self.certificates.write().unwrap();
+        match certificates.iter_mut().find(|c| c.slug == slug) {
+            Some(item) => {
+                item.set_embedding(values, model.to_string());
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn clear_embedding(&self, slug: &str) -> ApiResult<bool> {
+        let mut certificates = self.certificates.write().unwrap();
+        match certificates
+            .iter_mut()
+            .find(|c| c.slug == slug && !c.is_deleted())
+        {
+            Some(item) => {
+                item.clear_embedding();
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn set_verification(
+        &self,
+        slug: &str,
+        verified_at: Option<DateTime<Utc>>,
+    ) -> ApiResult<bool> {
+        let mut certificates = self.certificates.write().unwrap();
+        match certificates
+            .iter_mut()
+            .find(|c| c.slug == slug && !c.is_deleted())
+        {
+            Some(item) => {
+                item.set_verification(verified_at);
+                item.version += 1;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn set_publication(
+        &self,
+        slug: &str,
+        status: PublicationStatus,
+        publish_at: Option<DateTime<Utc>>,
+    ) -> ApiResult<bool> {
+        let mut certificates = self.certificates.write().unwrap();
+        match certificates
+            .iter_mut()
+            .find(|c| c.slug == slug && !c.is_deleted())
+        {
+            Some(item) => {
+                item.set_publication(status, publish_at);
+                item.version += 1;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn publish_due(&self, now: DateTime<Utc>) -> ApiResult<Vec<String>> {
+        let mut certificates = self.certificates.write().unwrap();
+        Ok(certificates
+            .iter_mut()
+            .filter(|c| !c.is_deleted() && is_due(c.publication, c.publish_at, now))
+            .map(|item| {
+                item.set_publication(PublicationStatus::Published, None);
+                item.version += 1;
+                item.slug.clone()
+            })
+            .collect())
+    }
+}
+
+/// In-memory slug redirects, keyed by kind and retired slug
+#[derive(Default)]
+pub struct InMemorySlugRedirectRepository {
+    redirects: RwLock<HashMap<(ContentKind, String), String>>,
+}
+
+#[async_trait]
+impl SlugRedirectRepository for InMemorySlugRedirectRepository {
+    async fn resolve(&self, kind: ContentKind, slug: &str) -> ApiResult<Option<String>> {
+        let redirects = self.redirects.read().unwrap();
+        Ok(redirects.get(&(kind, slug.to_string())).cloned())
+    }
+
+    async fn record(&self, kind: ContentKind, from: &str, to: &str) -> ApiResult<()> {
+        let mut redirects = self.redirects.write().unwrap();
+        for ((redirect_kind, _), target) in redirects.iter_mut() {
+            if *redirect_kind == kind && target == from {
+                *target = to.to_string();
+            }
+        }
+        redirects.remove(&(kind, to.to_string()));
+        redirects.insert((kind, from.to_string()), to.to_string());
+        Ok(())
+    }
+}
+
+/// In-memory revision history in insertion order
+#[derive(Default)]
+pub struct InMemoryRevisionRepository {
+    revisions: RwLock<Vec<Revision>>,
+}
+
+#[async_trait]
+impl RevisionRepository for InMemoryRevisionRepository {
+    async fn record(&self, mut revision: Revision) -> ApiResult<ObjectId> {
+        let id = *revision.id.get_or_insert_with(ObjectId::new);
+        self.revisions.write().unwrap().push(revision);
+        Ok(id)
+    }
+
+    async fn list(&self, kind: ContentKind, content_id: ObjectId) -> ApiResult<Vec<Revision>> {
+        let revisions = self.revisions.read().unwrap();
+        Ok(revisions
+            .iter()
+            .rev()
+            .filter(|r| r.kind == kind && r.content_id == content_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn get(
+        &self,
+        kind: ContentKind,
+        content_id: ObjectId,
+        id: ObjectId,
+    ) -> ApiResult<Option<Revision>> {
+        let revisions = self.revisions.read().unwrap();
+        Ok(revisions
+            .iter()
+            .find(|r| r.id == Some(id) && r.kind == kind && r.content_id == content_id)
+            .cloned())
+    }
+}
+
+/// In-memory media library in upload order
+#[derive(Default)]
+pub struct InMemoryMediaRepository {
+    assets: RwLock<Vec<MediaAsset>>,
+}
+
+#[async_trait]
+impl MediaRepository for InMemoryMediaRepository {
+    async fn create(&self, mut asset: MediaAsset) -> ApiResult<ObjectId> {
+        let id = *asset.id.get_or_insert_with(ObjectId::new);
+        self.assets.write().unwrap().push(asset);
+        Ok(id)
+    }
+
+    async fn list(&self) -> ApiResult<Vec<MediaAsset>> {
+        let assets = self.assets.read().unwrap();
+        Ok(assets.iter().rev().cloned().collect())
+    }
+
+    async fn list_orphans(&self, uploaded_before: DateTime<Utc>) -> ApiResult<Vec<MediaAsset>> {
+        let assets = self.assets.read().unwrap();
+        Ok(assets
+            .iter()
+            .filter(|a| a.reference_count == 0 && a.uploaded_at < uploaded_before)
+            .cloned()
+            .collect())
+    }
+
+    async fn set_references(&self, reference: MediaReference, urls: &[String]) -> ApiResult<()> {
+        let mut assets = self.assets.write().unwrap();
+        for asset in assets.iter_mut() {
+            asset
+                .references
+                .retain(|r| !(r.kind == reference.kind && r.content_id == reference.content_id));
+            if asset.urls.iter().any(|url| urls.contains(url)) {
+                asset.references.push(reference.clone());
+            }
+            asset.reference_count = asset.references.len() as i64;
+        }
+        Ok(())
+    }
+
+    async fn delete_orphan(&self, id: ObjectId) -> ApiResult<bool> {
+        let mut assets = self.assets.write().unwrap();
+        let before = assets.len();
+        assets.retain(|a| !(a.id == Some(id) && a.reference_count == 0));
+        Ok(assets.len() < before)
+    }
+}
+
+/// In-memory link checks, one per content field
+#[derive(Default)]
+pub struct InMemoryLinkCheckRepository {
+    checks: RwLock<Vec<LinkCheck>>,
+}
+
+impl InMemoryLinkCheckRepository {
+    fn sorted(&self, filter: impl Fn(&LinkCheck) -> bool) -> Vec<LinkCheck> {
+        let mut checks: Vec<LinkCheck> = self
+            .checks
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|c| filter(c))
+            .cloned()
+            .collect();
+        checks.sort_by(|a, b| {
+            (a.kind.as_str(), &a.slug, &a.field).cmp(&(b.kind.as_str(), &b.slug, &b.field))
+        });
+        checks
+    }
+}
+
+#[async_trait]
+impl LinkCheckRepository for InMemoryLinkCheckRepository {
+    async fn list(&self) -> ApiResult<Vec<LinkCheck>> {
+        Ok(self.sorted(|_| true))
+    }
+
+    async fn list_failing(&self) -> ApiResult<Vec<LinkCheck>> {
+        Ok(self.sorted(LinkCheck::is_failing))
+    }
+
+    async fn upsert(&self, check: LinkCheck) -> ApiResult<()> {
+        let mut checks = self.checks.write().unwrap();
+        checks
+            .retain(|c| !(c.kind == check.kind && c.slug == check.slug && c.field == check.field));
+        checks.push(check);
+        Ok(())
+    }
+
+    async fn prune(&self, checked_before: DateTime<Utc>) -> ApiResult<u64> {
+        let mut checks = self.checks.write().unwrap();
+        let before = checks.len();
+        checks.retain(|c| c.checked_at >= checked_before);
+        Ok((before - checks.len()) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_certificate(slug: &str) -> Certificate {
+        Certificate {
+            id: None,
+            name: "Rust Fundamentals".to_string(),
+            issuer: "Coursera".to_string(),
+            link: "https://coursera.org/verify/abc".to_string(),
+            slug: slug.to_string(),
+            issue_date: Some("2024".parse().unwrap()),
+            embedding: None,
+            embedding_model: None,
+            embedding_dimensions: None,
+            expires_at: None,
+            credential_id: None,
+            image_url: None,
+            verified: false,
+            verified_at: None,
+            publication: Default::default(),
+            publish_at: None,
+            deleted_at: None,
+            deleted_by: None,
+            version: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_assigns_id_and_lists() {
+        let repo = InMemoryCertificateRepository::default();
+        let id = repo.create(sample_certificate("rust")).await.unwrap();
+
+        let all = repo.list().await.unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].id, Some(id));
+    }
+
+    #[tokio::test]
+    async fn test_searchable_is_published_and_without_embeddings() {
+        let repo = InMemoryCertificateRepository::default();
+        let mut published = sample_certificate("rust");
+        published.embedding = Some(vec![0.5; 4]);
+        repo.create(published).await.unwrap();
+        let mut draft = sample_certificate("rust-draft");
+        draft.publication = PublicationStatus::Draft;
+        repo.create(draft).await.unwrap();
+
+        let searchable = repo.searchable().await.unwrap();
+        assert_eq!(searchable.len(), 1);
+        assert_eq!(searchable[0].slug, "rust");
+        assert!(searchable[0].embedding.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_update_trash_and_restore_by_slug() {
+        let repo = InMemoryCertificateRepository::default();
+        let id = repo.create(sample_certificate("rust")).await.unwrap();
+
+        let update = CertificateUpdate {
+            name: "Advanced Rust".to_string(),
+            issuer: "Coursera".to_string(),
+            link: "https://coursera.org/verify/abc".to_string(),
+            issue_date: None,
+            expires_at: None,
+            credential_id: None,
+            image_url: None,
+        };
+        let read_at = VersionCheck::OneOf(vec![(id, 0)]);
+        let before = repo
+            .update("rust", update, &read_at)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(before.name, "Rust Fundamentals");
+        let stored = repo.get_by_slug("rust").await.unwrap().unwrap();
+        assert_eq!(stored.name, "Advanced Rust");
+        assert_eq!(stored.version, 1);
+
+        // A second write based on the same read is stale
+        assert!(matches!(
+            repo.trash("rust", "admin@example.com", &read_at).await,
+            Err(ApiError::PreconditionFailed(_))
+        ));
+
+        let any = VersionCheck::Any;
+        assert!(repo.trash("rust", "admin@example.com", &any).await.unwrap());
+        assert!(!repo.trash("rust", "admin@example.com", &any).await.unwrap());
+        assert!(repo.get_by_slug("rust").await.unwrap().is_none());
+        assert!(repo.list().await.unwrap().is_empty());
+        assert!(repo.slug_in_use("rust").await.unwrap());
+
+        let trashed = repo.list_trash().await.unwrap();
+        assert_eq!(trashed[0].deleted_by.as_deref(), Some("admin@example.com"));
+
+        assert!(repo.restore("rust").await.unwrap());
+        assert!(!repo.restore("rust").await.unwrap());
+        assert!(repo.get_by_slug("rust").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_purge_only_removes_old_trash() {
+        let repo = InMemoryCertificateRepository::default();
+        for slug in ["old", "recent", "live"] {
+            repo.create(sample_certificate(slug)).await.unwrap();
+        }
+        for slug in ["old", "recent"] {
+            repo.trash(slug, "admin@example.com", &VersionCheck::Any)
+                .await
+                .unwrap();
+        }
+        repo.certificates.write().unwrap()[0].deleted_at =
+            Some(Utc::now() - chrono::Duration::days(40));
+
+        let cutoff = Utc::now() - chrono::Duration::days(30);
+        assert_eq!(repo.purge_trash(cutoff).await.unwrap(), 1);
+        assert!(!repo.slug_in_use("old").await.unwrap());
+        assert!(repo.slug_in_use("recent").await.unwrap());
+        assert!(repo.slug_in_use("live").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_redirects_collapse_chains() {
+        let redirects = InMemorySlugRedirectRepository::default();
+        let kind = ContentKind::Project;
+        redirects.record(kind, "a", "b").await.unwrap();
+        redirects.record(kind, "b", "c").await.unwrap();
+        assert_eq!(
+            redirects.resolve(kind, "a").await.unwrap().as_deref(),
+            Some("c")
+        );
+
+        // Renaming back to a retired slug makes it live again
+        redirects.record(kind, "c", "a").await.unwrap();
+        assert_eq!(redirects.resolve(kind, "a").await.unwrap(), None);
+        assert_eq!(
+            redirects.resolve(kind, "b").await.unwrap().as_deref(),
+            Some("a")
+        );
+        assert_eq!(
+            redirects
+                .resolve(ContentKind::Certificate, "b")
+                .await
+                .unwrap(),
+            None
+        );
+    }
+}
@@ -1 +1,330 @@
-// All repository base functions removed - handlers use direct MongoDB operations
+mod cached;
+mod media_tracking;
+mod memory;
+mod mongo;
+pub mod query;
+
+pub use cached::{
+    CacheStats, CachedCertificateRepository, CachedProjectRepository, ReadCache,
+    DEFAULT_READ_CACHE_ENTRIES, DEFAULT_READ_CACHE_TTL,
+};
+pub use media_tracking::{MediaTrackingCertificateRepository, MediaTrackingProjectRepository};
+pub use memory::{
+    InMemoryCertificateRepository, InMemoryLinkCheckRepository, InMemoryMediaRepository,
+    InMemoryProjectRepository, InMemoryRevisionRepository, InMemorySlugRedirectRepository,
+};
+pub use mongo::{
+    MongoCertificateRepository, MongoLinkCheckRepository, MongoMediaRepository,
+    MongoProjectRepository, MongoRevisionRepository, MongoSlugRedirectRepository,
+};
+pub use query::{FieldChanges, ListQuery, ListSort, Page, VersionCheck};
+
+use crate::{
+    database::MongoClient,
+    error::ApiResult,
+    models::{
+        certificate::CertificateUpdate,
+        link::LinkCheck,
+        media::{MediaAsset, MediaReference},
+        project::ProjectUpdate,
+        Certificate, ContentKind, Project, PublicationStatus, Revision,
+    },
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use std::sync::Arc;
+
+/// Storage operations for portfolio projects
+#[async_trait]
+pub trait ProjectRepository: Send + Sync {
+    /// List all live (not trashed) projects, published or not
+    async fn list(&self) -> ApiResult<Vec<Project>>;
+
+    /// Every published public project without its embedding, as ranked by search
+    async fn searchable(&self) -> ApiResult<Vec<Project>>;
+
+    /// Filtered, sorted page of published public projects, featured ones first
+    /// The default sort follows `order` within the featured and regular groups
+    async fn find(&self, query: &ListQuery) -> ApiResult<Page<Project>>;
+
+    /// Find a single live project by slug
+    async fn get_by_slug(&self, slug: &str) -> ApiResult<Option<Project>>;
+
+    /// Insert a new project and return its id
+    async fn create(&self, project: Project) -> ApiResult<ObjectId>;
+
+    /// Replace the editable fields of a live project and bump its version
+    /// Returns the project as it was before the update, or None if no project matched
+    /// Fails with `PreconditionFailed` if the project exists at a version `check` rejects
+    async fn update(
+        &self,
+        slug: &str,
+        update: ProjectUpdate,
+        check: &VersionCheck,
+    ) -> ApiResult<Option<Project>>;
+
+    /// Write only the changed paths of a live project and bump its version
+    /// Returns the project as it was before the patch, or None if no project matched
+    /// Fails with `PreconditionFailed` if the project exists at a version `check` rejects
+    async fn patch(
+        &self,
+        slug: &str,
+        changes: &FieldChanges,
+        check: &VersionCheck,
+    ) -> ApiResult<Option<Project>>;
+
+    /// Move a project to the trash, returns false if no live project matched
+    /// Fails with `PreconditionFailed` if the project exists at a version `check` rejects
+    async fn trash(&self, slug: &str, deleted_by: &str, check: &VersionCheck) -> ApiResult<bool>;
+
+    /// Trashed projects, most recently deleted first
+    async fn list_trash(&self) -> ApiResult<Vec<Project>>;
+
+    /// Bring a trashed project back and bump its version
+    /// Returns false if no trashed project matched
+    async fn restore(&self, slug: &str) -> ApiResult<bool>;
+
+    /// Permanently delete projects trashed before `cutoff`, returns how many were removed
+    async fn purge_trash(&self, cutoff: DateTime<Utc>) -> ApiResult<u64>;
+
+    /// True if any project, trashed or not, holds this slug
+    async fn slug_in_use(&self, slug: &str) -> ApiResult<bool>;
+
+    /// Store a freshly generated embedding, returns false if no project matched
+    async fn set_embedding(&self, slug: &str, values: Vec<f64>, model: &str) -> ApiResult<bool>;
+
+    /// Drop the stored embedding of a project whose text changed but could not be re-embedded,
+    /// so chat stops matching it on old content and reindex regenerates it
+    async fn clear_embedding(&self, slug: &str) -> ApiResult<bool>;
+
+    /// Move the projects in `slugs` into that sequence among the positions they held, then
+    /// number every live project from 0 and bump the versions of those whose `order` changed
+    /// All or nothing: fails with `NotFound` and writes nothing if any slug has no live project
+    async fn reorder(&self, slugs: &[String]) -> ApiResult<()>;
+
+    /// Move a live project to `status` in the publishing workflow and bump its version
+    /// Returns false if no live project matched
+    async fn set_publication(
+        &self,
+        slug: &str,
+        status: PublicationStatus,
+        publish_at: Option<DateTime<Utc>>,
+    ) -> ApiResult<bool>;
+
+    /// Publish live scheduled projects whose `publish_at` has passed, returns their slugs
+    async fn publish_due(&self, now: DateTime<Utc>) -> ApiResult<Vec<String>>;
+}
+
+/// Storage operations for certificates
+#[async_trait]
+pub trait CertificateRepository: Send + Sync {
+    /// List all live (not trashed) certificates, published or not
+    async fn list(&self) -> ApiResult<Vec<Certificate>>;
+
+    /// Every published certificate without its embedding, as ranked by search
+    async fn searchable(&self) -> ApiResult<Vec<Certificate>>;
+
+    /// Filtered, sorted page of published certificates
+    async fn find(&self, query: &ListQuery) -> ApiResult<Page<Certificate>>;
+
+    /// Find a single live certificate by slug
+    async fn get_by_slug(&self, slug: &str) -> ApiResult<Option<Certificate>>;
+
+    /// Insert a new certificate and return its id
+    async fn create(&self, certificate: Certificate) -> ApiResult<ObjectId>;
+
+    /// Replace the editable fields of a live certificate and bump its version
+    /// Returns the certificate as it was before the update, or None if no certificate matched
+    /// Fails with `PreconditionFailed` if the certificate exists at a version `check` rejects
+    async fn update(
+        &self,
+        slug: &str,
+        update: CertificateUpdate,
+        check: &VersionCheck,
+    ) -> ApiResult<Option<Certificate>>;
+
+    /// Write only the changed paths of a live certificate and bump its version
+    /// Returns the certificate as it was before the patch, or None if no certificate matched
+    /// Fails with `PreconditionFailed` if the certificate exists at a version `check` rejects
+    async fn patch(
+        &self,
+        slug: &str,
+        changes: &FieldChanges,
+        check: &VersionCheck,
+    ) -> ApiResult<Option<Certificate>>;
+
+    /// Move a certificate to the trash, returns false if no live certificate matched
+    /// Fails with `PreconditionFailed` if the certificate exists at a version `check` rejects
+    async fn trash(&self, slug: &str, deleted_by: &str, check: &VersionCheck) -> ApiResult<bool>;
+
+    /// Trashed certificates, most recently deleted first
+    async fn list_trash(&self) -> ApiResult<Vec<Certificate>>;
+
+    /// Bring a trashed certificate back and bump its version
+    /// Returns false if no trashed certificate matched
+    async fn restore(&self, slug: &str) -> ApiResult<bool>;
+
+    /// Permanently delete certificates trashed before `cutoff`, returns how many were removed
+    async fn purge_trash(&self, cutoff: DateTime<Utc>) -> ApiResult<u64>;
+
+    /// True if any certificate, trashed or not, holds this slug
+    async fn slug_in_use(&self, slug: &str) -> ApiResult<bool>;
+
+    /// Store a freshly generated embedding, returns false if no certificate matched
+    async fn set_embedding(&self, slug: &str, values: Vec<f64>, model: &str) -> ApiResult<bool>;
+
+    /// Drop the stored embedding of a certificate whose text changed but could not be re-embedded,
+    /// so chat stops matching it on old content and reindex regenerates it
+    async fn clear_embedding(&self, slug: &str) -> ApiResult<bool>;
+
+    /// Store the outcome of checking the issuer page and bump the version,
+    /// `verified_at` is None when it failed
+    /// Returns false if no live certificate matched
+    async fn set_verification(
+        &self,
+        slug: &str,
+        verified_at: Option<DateTime<Utc>>,
+    ) -> ApiResult<bool>;
+
+    /// Move a live certificate to `status` in the publishing workflow and bump its version
+    /// Returns false if no live certificate matched
+    async fn set_publication(
+        &self,
+        slug: &str,
+        status: PublicationStatus,
+        publish_at: Option<DateTime<Utc>>,
+    ) -> ApiResult<bool>;
+
+    /// Publish live scheduled certificates whose `publish_at` has passed, returns their slugs
+    async fn publish_due(&self, now: DateTime<Utc>) -> ApiResult<Vec<String>>;
+}
+
+/// Retired slugs of renamed content, so existing links keep resolving
+#[async_trait]
+pub trait SlugRedirectRepository: Send + Sync {
+    /// Current slug of content that used to live at `slug`
+    async fn resolve(&self, kind: ContentKind, slug: &str) -> ApiResult<Option<String>>;
+
+    /// Redirect `from` to `to`, repointing older redirects to `from` so chains never form
+    async fn record(&self, kind: ContentKind, from: &str, to: &str) -> ApiResult<()>;
+}
+
+/// Append-only history of content edits (`revisions` collection)
+#[async_trait]
+pub trait RevisionRepository: Send + Sync {
+    /// Store a revision and return its id
+    async fn record(&self, revision: Revision) -> ApiResult<ObjectId>;
+
+    /// Revisions of one document, newest first
+    async fn list(&self, kind: ContentKind, content_id: ObjectId) -> ApiResult<Vec<Revision>>;
+
+    /// A single revision of one document
+    async fn get(
+        &self,
+        kind: ContentKind,
+        content_id: ObjectId,
+        id: ObjectId,
+    ) -> ApiResult<Option<Revision>>;
+}
+
+/// Uploaded assets and the content referencing them (`media` collection)
+#[async_trait]
+pub trait MediaRepository: Send + Sync {
+    /// Store a new asset and return its id
+    async fn create(&self, asset: MediaAsset) -> ApiResult<ObjectId>;
+
+    /// All assets, most recently uploaded first
+    async fn list(&self) -> ApiResult<Vec<MediaAsset>>;
+
+    /// Unreferenced assets uploaded before `uploaded_before`, oldest first
+    async fn list_orphans(&self, uploaded_before: DateTime<Utc>) -> ApiResult<Vec<MediaAsset>>;
+
+    /// Make `urls` the complete set of media used by one document, updating
+    /// reference counts of assets it started or stopped using; an empty list releases all
+    async fn set_references(&self, reference: MediaReference, urls: &[String]) -> ApiResult<()>;
+
+    /// Remove an asset record if it is still unreferenced, returns false otherwise
+    async fn delete_orphan(&self, id: ObjectId) -> ApiResult<bool>;
+}
+
+/// Latest health check of every stored link (`link_checks` collection)
+#[async_trait]
+pub trait LinkCheckRepository: Send + Sync {
+    /// Every checked link, ordered by kind, slug and field
+    async fn list(&self) -> ApiResult<Vec<LinkCheck>>;
+
+    /// Links found broken or unreachable, ordered by kind, slug and field
+    async fn list_failing(&self) -> ApiResult<Vec<LinkCheck>>;
+
+    /// Store the check of one field, replacing its previous check
+    async fn upsert(&self, check: LinkCheck) -> ApiResult<()>;
+
+    /// Drop checks older than `checked_before`, i.e. links no run has seen since;
+    /// returns the number removed
+    async fn prune(&self, checked_before: DateTime<Utc>) -> ApiResult<u64>;
+}
+
+/// Repository handles shared by the API routers
+#[derive(Clone)]
+pub struct Repositories {
+    pub projects: Arc<dyn ProjectRepository>,
+    pub certificates: Arc<dyn CertificateRepository>,
+    pub redirects: Arc<dyn SlugRedirectRepository>,
+    pub revisions: Arc<dyn RevisionRepository>,
+    pub media: Arc<dyn MediaRepository>,
+    pub links: Arc<dyn LinkCheckRepository>,
+    /// Read cache in front of projects and certificates, flushed by admins
+    pub read_cache: Arc<ReadCache>,
+}
+
+impl Repositories {
+    /// Repositories backed by MongoDB collections, reads of content go through `read_cache`
+    /// Content writes keep media reference counts current
+    pub fn mongo(db_client: Arc<MongoClient>, read_cache: Arc<ReadCache>) -> Self {
+        let media: Arc<dyn MediaRepository> =
+            Arc::new(MongoMediaRepository::new(db_client.clone()));
+        Self {
+            projects: Arc::new(CachedProjectRepository::new(
+                Arc::new(MediaTrackingProjectRepository::new(
+                    Arc::new(MongoProjectRepository::new(db_client.clone())),
+                    media.clone(),
+                )),
+                read_cache.clone(),
+            )),
+            certificates: Arc::new(CachedCertificateRepository::new(
+                Arc::new(MediaTrackingCertificateRepository::new(
+                    Arc::new(MongoCertificateRepository::new(db_client.clone())),
+                    media.clone(),
+                )),
+                read_cache.clone(),
+            )),
+            redirects: Arc::new(MongoSlugRedirectRepository::new(db_client.clone())),
+            revisions: Arc::new(MongoRevisionRepository::new(db_client.clone())),
+            media,
+            links: Arc::new(MongoLinkCheckRepository::new(db_client)),
+            read_cache,
+        }
+    }
+
+    /// Repositories kept in process memory (tests and local development)
+    pub fn in_memory() -> Self {
+        let media: Arc<dyn MediaRepository> = Arc::new(InMemoryMediaRepository::default());
+        Self {
+            projects: Arc::new(MediaTrackingProjectRepository::new(
+                Arc::new(InMemoryProjectRepository::default()),
+                media.clone(),
+            )),
+            certificates: Arc::new(MediaTrackingCertificateRepository::new(
+                Arc::new(InMemoryCertificateRepository::default()),
+                media.clone(),
+            )),
+            redirects: Arc::new(InMemorySlugRedirectRepository::default()),
+            revisions: Arc::new(InMemoryRevisionRepository::default()),
+            media,
+            links: Arc::new(InMemoryLinkCheckRepository::default()),
+            // Nothing to save over an in-memory store, kept for the admin cache endpoints
+            read_cache: Arc::new(ReadCache::default()),
+        }
+    }
+}
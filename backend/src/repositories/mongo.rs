@@ -0,0 +1,1032 @@
+use super::{
+    query::{
+        exact_match_pattern, modified_since_read, projects_not_found, reordered, FieldChanges,
+        ListQuery, ListSort, Page, VersionCheck,
+    },
+    CertificateRepository, LinkCheckRepository, MediaRepository, ProjectRepository,
+    RevisionRepository, SlugRedirectRepository,
+};
+use crate::{
+    database::MongoClient,
+    error::{ApiError, ApiResult},
+    models::{
+        certificate::{CertificateUpdate, VERIFIED_FIELDS},
+        link::LinkCheck,
+        media::{MediaAsset, MediaReference},
+        project::ProjectUpdate,
+        Certificate, ContentKind, Project, PublicationStatus, Revision,
+    },
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, Utc};
+use futures::stream::TryStreamExt;
+use mongodb::{
+    bson::{doc, oid::ObjectId, Bson, DateTime as BsonDateTime, Document},
+    options::{Collation, CollationStrength, ReturnDocument},
+    Collection,
+};
+use serde::de::DeserializeOwned;
+use std::sync::Arc;
+
+/// Filter matching documents that are not in the trash
+fn live() -> Document {
+    doc! { "deleted_at": null }
+}
+
+/// Visibility condition of projects shown in public listings
+/// Documents from before the visibility field count as public
+fn listed() -> Document {
+    doc! { "$ne": "unlisted" }
+}
+
+/// Publication condition of content served publicly
+/// Documents from before the publishing workflow count as published
+fn published() -> Document {
+    doc! { "$in": [PublicationStatus::Published.as_str(), Bson::Null] }
+}
+
+/// Filter matching trashed documents
+fn trashed() -> Document {
+    doc! { "deleted_at": { "$ne": null } }
+}
+
+/// Filter matching the live document with `slug`, at a version `check` accepts
+fn writable(slug: &str, check: &VersionCheck) -> Document {
+    let mut filter = live();
+    filter.insert("slug", slug);
+    if let VersionCheck::OneOf(accepted) = check {
+        let accepted: Vec<Document> = accepted
+            .iter()
+            .map(|(id, version)| {
+                // Documents written before versioning have no counter and count as version 0
+                let versions = match version {
+                    0 => vec![Bson::Int64(0), Bson::Null],
+                    version => vec![Bson::Int64(*version)],
+                };
+                doc! { "_id": id, "version": { "$in": versions } }
+            })
+            .collect();
+        if accepted.is_empty() {
+            // `$or` needs at least one clause, no tag at all matches nothing
+            filter.insert("_id", doc! { "$in": [] });
+        } else {
+            filter.insert("$or", accepted);
+        }
+    }
+    filter
+}
+
+/// Explain a write that matched nothing: a stale `If-Match` fails, a missing document does not
+async fn check_missed_write(
+    collection: &Collection<Document>,
+    slug: &str,
+    check: &VersionCheck,
+) -> ApiResult<()> {
+    if *check == VersionCheck::Any {
+        return Ok(());
+    }
+    let mut filter = live();
+    filter.insert("slug", slug);
+    match collection.count_documents(filter).await? {
+        0 => Ok(()),
+        _ => Err(modified_since_read(slug)),
+    }
+}
+
+/// Read every live document of a collection, skipping documents that don't match the model
+async fn find_all<T: DeserializeOwned>(
+    collection: &Collection<Document>,
+    label: &str,
+) -> ApiResult<Vec<T>> {
+    find_matching(collection, live(), doc! {}, label).await
+}
+
+/// Read every document matching `filter`, shaped by `projection` (empty for whole documents)
+async fn find_matching<T: DeserializeOwned>(
+    collection: &Collection<Document>,
+    filter: Document,
+    projection: Document,
+    label: &str,
+) -> ApiResult<Vec<T>> {
+    let mut cursor = collection.find(filter).projection(projection).await?;
+    let mut items = Vec::new();
+    let mut error_count = 0;
+
+    while let Some(document) = cursor.try_next().await? {
+        match mongodb::bson::from_document::<T>(document) {
+            Ok(item) => items.push(item),
+            Err(e) => {
+                error_count += 1;
+                tracing::warn!(
+                    "Failed to deserialize {} document {}: {}",
+                    label,
+                    error_count,
+                    e
+                );
+            }
+        }
+    }
+
+    tracing::info!(
+        "{} retrieval: {} successful, {} failed",
+        label,
+        items.len(),
+        error_count
+    );
+
+    Ok(items)
+}
+
+/// Run a filtered, sorted, paginated query and deserialize the matching documents
+/// `leading` sort keys take precedence over the requested sort
+async fn find_page<T: DeserializeOwned>(
+    collection: &Collection<Document>,
+    filter: Document,
+    query: &ListQuery,
+    leading: Document,
+    date_field: &str,
+    title_field: &str,
+) -> ApiResult<Page<T>> {
+    let total = collection.count_documents(filter.clone()).await?;
+
+    let mut sort = leading;
+    sort.extend(match query.sort {
+        ListSort::Inserted => doc! { "_id": 1 },
+        ListSort::DateAsc => doc! { date_field: 1, "_id": 1 },
+        ListSort::DateDesc => doc! { date_field: -1, "_id": 1 },
+        ListSort::TitleAsc => doc! { title_field: 1, "_id": 1 },
+        ListSort::TitleDesc => doc! { title_field: -1, "_id": 1 },
+    });
+
+    // Case-insensitive ordering for titles, matching the in-memory backend
+    let collation = Collation::builder()
+        .locale("en")
+        .strength(CollationStrength::Secondary)
+        .build();
+
+    let mut find = collection
+        .find(filter)
+        .sort(sort)
+        .collation(collation)
+        .skip(query.offset);
+    if let Some(limit) = query.limit {
+        find = find.limit(limit as i64);
+    }
+
+    let mut cursor = find.await?;
+    let mut items = Vec::new();
+    let mut returned = 0;
+    while let Some(document) = cursor.try_next().await? {
+        returned += 1;
+        match mongodb::bson::from_document::<T>(document) {
+            Ok(item) => items.push(item),
+            Err(e) => tracing::warn!("Skipping malformed document in page: {}", e),
+        }
+    }
+
+    Ok(Page {
+        items,
+        total,
+        next_offset: query.next_offset(returned, total),
+    })
+}
+
+/// Case-insensitive exact match on a string (or any element of a string array)
+fn exact_match(value: &str) -> Document {
+    doc! { "$regex": exact_match_pattern(value), "$options": "i" }
+}
+
+/// Filter on a `ContentDate` field matching dates that cover `year`,
+/// mirroring `ContentDate::covers_year`: ongoing ranges run until the current year
+fn year_filter(field: &str, year: i32) -> Document {
+    let start = format!("{}.start.year", field);
+    let end = format!("{}.end.year", field);
+    let ongoing = format!("{}.ongoing", field);
+
+    let mut last_year = vec![
+        doc! { &end: { "$gte": year } },
+        doc! { &end: null, &ongoing: { "$ne": true }, &start: year },
+    ];
+    if year <= Utc::now().year() {
+        last_year.push(doc! { &ongoing: true });
+    } else {
+        last_year.push(doc! { &ongoing: true, &start: year });
+    }
+    doc! { &start: { "$lte": year }, "$or": last_year }
+}
+
+/// Find one live document by slug and deserialize it into the model
+async fn find_by_slug<T: DeserializeOwned>(
+    collection: &Collection<Document>,
+    slug: &str,
+) -> ApiResult<Option<T>> {
+    let mut filter = live();
+    filter.insert("slug", slug);
+
+    match collection.find_one(filter).await? {
+        Some(document) => mongodb::bson::from_document(document)
+            .map(Some)
+            .map_err(|e| {
+                tracing::error!("Stored document '{}' does not match model: {}", slug, e);
+                ApiError::InternalError("Stored document is malformed".to_string())
+            }),
+        None => Ok(None),
+    }
+}
+
+/// Trashed documents, most recently deleted first
+async fn find_trash<T: DeserializeOwned>(
+    collection: &Collection<Document>,
+    label: &str,
+) -> ApiResult<Vec<T>> {
+    let mut cursor = collection
+        .find(trashed())
+        .sort(doc! { "deleted_at": -1 })
+        .await?;
+    let mut items = Vec::new();
+    while let Some(document) = cursor.try_next().await? {
+        match mongodb::bson::from_document::<T>(document) {
+            Ok(item) => items.push(item),
+            Err(e) => tracing::warn!("Skipping malformed document in {}: {}", label, e),
+        }
+    }
+    Ok(items)
+}
+
+/// Apply update operators to a live document and bump its version in the same atomic write
+/// Returns the document as it was before the update
+async fn update_returning_before<T: DeserializeOwned>(
+    collection: &Collection<Document>,
+    slug: &str,
+    mut update: Document,
+    check: &VersionCheck,
+) -> ApiResult<Option<T>> {
+    update.insert("$inc", doc! { "version": 1_i64 });
+    let before = collection
+        .find_one_and_update(writable(slug, check), update)
+        .return_document(ReturnDocument::Before)
+        .await?;
+    match before {
+        Some(document) => Ok(Some(mongodb::bson::from_document(document).map_err(
+            |e| {
+                tracing::error!("Stored document '{}' does not match model: {}", slug, e);
+                ApiError::InternalError("Stored document is malformed".to_string())
+            },
+        )?)),
+        None => {
+            check_missed_write(collection, slug, check).await?;
+            Ok(None)
+        }
+    }
+}
+
+/// Mark a live document as trashed by an admin
+async fn trash(
+    collection: &Collection<Document>,
+    slug: &str,
+    deleted_by: &str,
+    check: &VersionCheck,
+) -> ApiResult<bool> {
+    let result = collection
+        .update_one(
+            writable(slug, check),
+            doc! {
+                "$set": { "deleted_at": BsonDateTime::now(), "deleted_by": deleted_by },
+                "$inc": { "version": 1_i64 },
+            },
+        )
+        .await?;
+    if result.matched_count == 0 {
+        check_missed_write(collection, slug, check).await?;
+    }
+    Ok(result.matched_count > 0)
+}
+
+/// Clear the trash markers of a trashed document
+async fn restore(collection: &Collection<Document>, slug: &str) -> ApiResult<bool> {
+    let mut filter = trashed();
+    filter.insert("slug", slug);
+
+    let result = collection
+        .update_one(
+            filter,
+            doc! {
+                "$unset": { "deleted_at": "", "deleted_by": "" },
+                "$inc": { "version": 1_i64 },
+            },
+        )
+        .await?;
+    Ok(result.matched_count > 0)
+}
+
+/// Hard-delete documents trashed before the cutoff
+async fn purge_trash(collection: &Collection<Document>, cutoff: DateTime<Utc>) -> ApiResult<u64> {
+    let result = collection
+        .delete_many(doc! { "deleted_at": { "$lt": BsonDateTime::from_chrono(cutoff) } })
+        .await?;
+    Ok(result.deleted_count)
+}
+
+/// True if any document, trashed or not, holds the slug
+async fn slug_in_use(collection: &Collection<Document>, slug: &str) -> ApiResult<bool> {
+    Ok(collection.count_documents(doc! { "slug": slug }).await? > 0)
+}
+
+/// Insert a serialized model and return the generated ObjectId
+async fn insert(collection: &Collection<Document>, document: Document) -> ApiResult<ObjectId> {
+    let result = collection.insert_one(document).await?;
+    result
+        .inserted_id
+        .as_object_id()
+        .ok_or_else(|| ApiError::InternalError("Inserted id is not an ObjectId".to_string()))
+}
+
+/// Move a live document to `status` and bump its version
+async fn set_publication(
+    collection: &Collection<Document>,
+    slug: &str,
+    status: PublicationStatus,
+    publish_at: Option<DateTime<Utc>>,
+) -> ApiResult<bool> {
+    let mut filter = live();
+    filter.insert("slug", slug);
+    let result = collection
+        .update_one(
+            filter,
+            doc! {
+                "$set": {
+                    "publication": status.as_str(),
+                    "publish_at": publish_at.map(BsonDateTime::from_chrono),
+                },
+                "$inc": { "version": 1_i64 },
+            },
+        )
+        .await?;
+    Ok(result.matched_count > 0)
+}
+
+/// Publish due scheduled documents one at a time, so each returned slug was really flipped
+async fn publish_due(
+    collection: &Collection<Document>,
+    now: DateTime<Utc>,
+) -> ApiResult<Vec<String>> {
+    let mut filter = live();
+    filter.insert("publication", PublicationStatus::Scheduled.as_str());
+    filter.insert(
+        "publish_at",
+        doc! { "$lte": BsonDateTime::from_chrono(now) },
+    );
+    let update = doc! {
+        "$set": { "publication": PublicationStatus::Published.as_str(), "publish_at": Bson::Null },
+        "$inc": { "version": 1_i64 },
+    };
+
+    let mut slugs = Vec::new();
+    while let Some(document) = collection
+        .find_one_and_update(filter.clone(), update.clone())
+        .projection(doc! { "slug": 1 })
+        .await?
+    {
+        slugs.push(document.get_str("slug").unwrap_or_default().to_string());
+    }
+    Ok(slugs)
+}
+
+/// `$set` fields storing an embedding with its model and dimensions
+fn embedding_fields(values: Vec<f64>, model: &str) -> Document {
+    doc! {
+        "embedding_dimensions": values.len() as i32,
+        "embedding_model": model,
+        "embedding": values,
+    }
+}
+
+/// Remove the embedding of a live document along with its model fields
+async fn clear_embedding(collection: &Collection<Document>, slug: &str) -> ApiResult<bool> {
+    let mut filter = live();
+    filter.insert("slug", slug);
+    let result = collection
+        .update_one(
+            filter,
+            doc! { "$unset": { "embedding": "", "embedding_model": "", "embedding_dimensions": "" } },
+        )
+        .await?;
+    Ok(result.matched_count > 0)
+}
+
+/// MongoDB-backed project repository
+pub struct MongoProjectRepository {
+    db: Arc<MongoClient>,
+}
+
+impl MongoProjectRepository {
+    pub fn new(db: Arc<MongoClient>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl ProjectRepository for MongoProjectRepository {
+    async fn list(&self) -> ApiResult<Vec<Project>> {
+        find_all(&self.db.projects(), "Projects").await
+    }
+
+    async fn searchable(&self) -> ApiResult<Vec<Project>> {
+        let mut filter = live();
+        filter.insert("visibility", listed());
+        filter.insert("publication", published());
+        let projection = doc! { "embedding": 0 };
+        find_matching(&self.db.projects(), filter, projection, "Projects").await
+    }
+
+    async fn find(&self, query: &ListQuery) -> ApiResult<Page<Project>> {
+        let mut filter = live();
+        if let Some(technology) = &query.technology {
+            filter.insert("technologies", exact_match(technology));
+        }
+        if let Some(year) = query.year {
+            filter.extend(year_filter("date", year));
+        }
+        filter.insert("visibility", listed());
+        filter.insert("publication", published());
+
+        // Featured first, then the admin's order unless another sort was asked for
+        let mut leading = doc! { "featured": -1 };
+        if query.sort == ListSort::Inserted {
+            leading.insert("order", 1);
+        }
+        find_page(
+            &self.db.projects(),
+            filter,
+            query,
+            leading,
+            "date.start",
+            "title",
+        )
+        .await
+    }
+
+    async fn get_by_slug(&self, slug: &str) -> ApiResult<Option<Project>> {
+        find_by_slug(&self.db.projects(), slug).await
+    }
+
+    async fn create(&self, project: Project) -> ApiResult<ObjectId> {
+        let document = mongodb::bson::to_document(&project)?;
+        insert(&self.db.projects(), document).await
+    }
+
+    async fn update(
+        &self,
+        slug: &str,
+        update: ProjectUpdate,
+        check: &VersionCheck,
+    ) -> ApiResult<Option<Project>> {
+        let update_doc = mongodb::bson::to_document(&update)?;
+        update_returning_before(
+            &self.db.projects(),
+            slug,
+            doc! { "$set": update_doc },
+            check,
+        )
+        .await
+    }
+
+    async fn patch(
+        &self,
+        slug: &str,
+        changes: &FieldChanges,
+        check: &VersionCheck,
+    ) -> ApiResult<Option<Project>> {
+        update_returning_before(&self.db.projects(), slug, changes.to_update(), check).await
+    }
+
+    async fn trash(&self, slug: &str, deleted_by: &str, check: &VersionCheck) -> ApiResult<bool> {
+        trash(&self.db.projects(), slug, deleted_by, check).await
+    }
+
+    async fn list_trash(&self) -> ApiResult<Vec<Project>> {
+        find_trash(&self.db.projects(), "Trashed projects").await
+    }
+
+    async fn restore(&self, slug: &str) -> ApiResult<bool> {
+        restore(&self.db.projects(), slug).await
+    }
+
+    async fn purge_trash(&self, cutoff: DateTime<Utc>) -> ApiResult<u64> {
+        purge_trash(&self.db.projects(), cutoff).await
+    }
+
+    async fn slug_in_use(&self, slug: &str) -> ApiResult<bool> {
+        slug_in_use(&self.db.projects(), slug).await
+    }
+
+    async fn set_embedding(&self, slug: &str, values: Vec<f64>, model: &str) -> ApiResult<bool> {
+        let update = embedding_fields(values, model);
+        Ok(self.db.update_by_slug("projects", slug, update).await?)
+    }
+
+    async fn clear_embedding(&self, slug: &str) -> ApiResult<bool> {
+        clear_embedding(&self.db.projects(), slug).await
+    }
+
+    /// Runs in a transaction, which MongoDB only offers on replica sets (Atlas included)
+    async fn reorder(&self, slugs: &[String]) -> ApiResult<()> {
+        let collection = self.db.projects();
+        let mut session = self.db.start_session().await?;
+        session.start_transaction().await?;
+        let live_projects: Vec<Document> = collection
+            .find(live())
+            .projection(doc! { "slug": 1 })
+            .sort(doc! { "order": 1, "_id": 1 })
+            .session(&mut session)
+            .await?
+            .stream(&mut session)
+            .try_collect()
+            .await?;
+        let current: Vec<&str> = live_projects
+            .iter()
+            .filter_map(|project| project.get_str("slug").ok())
+            .collect();
+        let missing: Vec<&str> = slugs
+            .iter()
+            .map(String::as_str)
+            .filter(|slug| !current.contains(slug))
+            .collect();
+        if !missing.is_empty() {
+            session.abort_transaction().await?;
+            return Err(projects_not_found(&missing));
+        }
+
+        // Positions come from the new sequence itself, so a single update renumbers every
+        // live project, only those whose order changes get a new version
+        let sequence = reordered(&current, slugs);
+        let position = doc! { "$indexOfArray": [&sequence, "$slug"] };
+        let update = vec![doc! {
+            "$set": {
+                "order": position.clone(),
+                "version": {
+                    "$cond": [
+                        { "$eq": ["$order", position] },
+                        "$version",
+                        { "$add": [{ "$ifNull": ["$version", 0_i64] }, 1_i64] },
+                    ]
+                },
+            }
+        }];
+        let mut filter = live();
+        filter.insert("slug", doc! { "$in": &sequence });
+        collection
+            .update_many(filter, update)
+            .session(&mut session)
+            .await?;
+        session.commit_transaction().await?;
+        Ok(())
+    }
+
+    async fn set_publication(
+        &self,
+        slug: &str,
+        status: PublicationStatus,
+        publish_at: Option<DateTime<Utc>>,
+    ) -> ApiResult<bool> {
+        set_publication(&self.db.projects(), slug, status, publish_at).await
+    }
+
+    async fn publish_due(&self, now: DateTime<Utc>) -> ApiResult<Vec<String>> {
+        publish_due(&self.db.projects(), now).await
+    }
+}
+
+/// MongoDB-backed certificate repository
+pub struct MongoCertificateRepository {
+    db: Arc<MongoClient>,
+}
+
+impl MongoCertificateRepository {
+    pub fn new(db: Arc<MongoClient>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl CertificateRepository for MongoCertificateRepository {
+    async fn list(&self) -> ApiResult<Vec<Certificate>> {
+        find_all(&self.db.certificates(), "Certificates").await
+    }
+
+    async fn searchable(&self) -> ApiResult<Vec<Certificate>> {
+        let mut filter = live();
+        filter.insert("publication", published());
+        let projection = doc! { "embedding": 0 };
+        find_matching(&self.db.certificates(), filter, projection, "Certificates").await
+    }
+
+    async fn find(&self, query: &ListQuery) -> ApiResult<Page<Certificate>> {
+        let mut filter = live();
+        if let Some(issuer) = &query.issuer {
+            filter.insert("issuer", exact_match(issuer));
+        }
+        if let Some(year) = query.year {
+            filter.extend(year_filter("issue_date", year));
+        }
+        filter.insert("publication", published());
+
+        find_page(
+            &self.db.certificates(),
+            filter,
+            query,
+            Document::new(),
+            "issue_date.start",
+            "name",
+        )
+        .await
+    }
+
+    async fn get_by_slug(&self, slug: &str) -> ApiResult<Option<Certificate>> {
+        find_by_slug(&self.db.certificates(), slug).await
+    }
+
+    async fn create(&self, certificate: Certificate) -> ApiResult<ObjectId> {
+        let document = mongodb::bson::to_document(&certificate)?;
+        insert(&self.db.certificates(), document).await
+    }
+
+    async fn update(
+        &self,
+        slug: &str,
+        update: CertificateUpdate,
+        check: &VersionCheck,
+    ) -> ApiResult<Option<Certificate>> {
+        // Whether the verification survives depends on the stored name and link,
+        // so the write is pinned to the version they were read at
+        let mut update_doc = mongodb::bson::to_document(&update)?;
+        let check = match find_by_slug::<Certificate>(&self.db.certificates(), slug).await? {
+            Some(current) if check.allows(current.id, current.version) => {
+                if update.voids_verification(&current) {
+                    update_doc.insert("verified", false);
+                    update_doc.insert("verified_at", Bson::Null);
+                }
+                VersionCheck::OneOf(
+                    current
+                        .id
+                        .map(|id| (id, current.version))
+                        .into_iter()
+                        .collect(),
+                )
+            }
+            Some(_) => return Err(modified_since_read(slug)),
+            None => return Ok(None),
+        };
+        update_returning_before(
+            &self.db.certificates(),
+            slug,
+            doc! { "$set": update_doc },
+            &check,
+        )
+        .await
+    }
+
+    async fn patch(
+        &self,
+        slug: &str,
+        changes: &FieldChanges,
+        check: &VersionCheck,
+    ) -> ApiResult<Option<Certificate>> {
+        let changes = changes.voiding_verification(&VERIFIED_FIELDS);
+        update_returning_before(&self.db.certificates(), slug, changes.to_update(), check).await
+    }
+
+    async fn trash(&self, slug: &str, deleted_by: &str, check: &VersionCheck) -> ApiResult<bool> {
+        trash(&self.db.certificates(), slug, deleted_by, check).await
+    }
+
+    async fn list_trash(&self) -> ApiResult<Vec<Certificate>> {
+        find_trash(&self.db.certificates(), "Trashed certificates").await
+    }
+
+    async fn restore(&self, slug: &str) -> ApiResult<bool> {
+        restore(&self.db.certificates(), slug).await
+    }
+
+    async fn purge_trash(&self, cutoff: DateTime<Utc>) -> ApiResult<u64> {
+        purge_trash(&self.db.certificates(), cutoff).await
+    }
+
+    async fn slug_in_use(&self, slug: &str) -> ApiResult<bool> {
+        slug_in_use(&self.db.certificates(), slug).await
+    }
+
+    async fn set_embedding(&self, slug: &str, values: Vec<f64>, model: &str) -> ApiResult<bool> {
+        let update = embedding_fields(values, model);
+        Ok(self.db.update_by_slug("certificates", slug, update).await?)
+    }
+
+    async fn clear_embedding(&self, slug: &str) -> ApiResult<bool> {
+        clear_embedding(&self.db.certificates(), slug).await
+    }
+
+    async fn set_verification(
+        &self,
+        slug: &str,
+        verified_at: Option<DateTime<Utc>>,
+    ) -> ApiResult<bool> {
+        let update = doc! {
+            "$set": {
+                "verified": verified_at.is_some(),
+                "verified_at": verified_at.map(BsonDateTime::from_chrono),
+            },
+            "$inc": { "version": 1_i64 },
+        };
+        let result = self
+            .db
+            .certificates()
+            .update_one(writable(slug, &VersionCheck::Any), update)
+            .await?;
+        Ok(result.matched_count > 0)
+    }
+
+    async fn set_publication(
+        &self,
+        slug: &str,
+        status: PublicationStatus,
+        publish_at: Option<DateTime<Utc>>,
+    ) -> ApiResult<bool> {
+        set_publication(&self.db.certificates(), slug, status, publish_at).await
+    }
+
+    async fn publish_due(&self, now: DateTime<Utc>) -> ApiResult<Vec<String>> {
+        publish_due(&self.db.certificates(), now).await
+    }
+}
+
+/// MongoDB-backed slug redirects (`slug_redirects` collection)
+pub struct MongoSlugRedirectRepository {
+    db: Arc<MongoClient>,
+}
+
+impl MongoSlugRedirectRepository {
+    pub fn new(db: Arc<MongoClient>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl SlugRedirectRepository for MongoSlugRedirectRepository {
+    async fn resolve(&self, kind: ContentKind, slug: &str) -> ApiResult<Option<String>> {
+        let redirect = self
+            .db
+            .slug_redirects()
+            .find_one(doc! { "kind": kind.as_str(), "from": slug })
+            .await?;
+        Ok(redirect.and_then(|r| r.get_str("to").ok().map(str::to_string)))
+    }
+
+    async fn record(&self, kind: ContentKind, from: &str, to: &str) -> ApiResult<()> {
+        let redirects = self.db.slug_redirects();
+        let kind = kind.as_str();
+
+        redirects
+            .update_many(
+                doc! { "kind": kind, "to": from },
+                doc! { "$set": { "to": to } },
+            )
+            .await?;
+        redirects
+            .delete_many(doc! { "kind": kind, "from": to })
+            .await?;
+        redirects
+            .update_one(
+                doc! { "kind": kind, "from": from },
+                doc! { "$set": { "to": to, "created_at": mongodb::bson::DateTime::now() } },
+            )
+            .upsert(true)
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// MongoDB-backed revision history (`revisions` collection)
+pub struct MongoRevisionRepository {
+    db: Arc<MongoClient>,
+}
+
+impl MongoRevisionRepository {
+    pub fn new(db: Arc<MongoClient>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl RevisionRepository for MongoRevisionRepository {
+    async fn record(&self, revision: Revision) -> ApiResult<ObjectId> {
+        let document = mongodb::bson::to_document(&revision)?;
+        insert(&self.db.revisions(), document).await
+    }
+
+    async fn list(&self, kind: ContentKind, content_id: ObjectId) -> ApiResult<Vec<Revision>> {
+        let mut cursor = self
+            .db
+            .revisions()
+            .find(doc! { "kind": kind.as_str(), "content_id": content_id })
+            .sort(doc! { "created_at": -1, "_id": -1 })
+            .await?;
+        let mut revisions = Vec::new();
+        while let Some(document) = cursor.try_next().await? {
+            match mongodb::bson::from_document::<Revision>(document) {
+                Ok(revision) => revisions.push(revision),
+                Err(e) => tracing::warn!("Skipping malformed revision: {}", e),
+            }
+        }
+        Ok(revisions)
+    }
+
+    async fn get(
+        &self,
+        kind: ContentKind,
+        content_id: ObjectId,
+        id: ObjectId,
+    ) -> ApiResult<Option<Revision>> {
+        let filter = doc! { "_id": id, "kind": kind.as_str(), "content_id": content_id };
+        match self.db.revisions().find_one(filter).await? {
+            Some(document) => mongodb::bson::from_document(document)
+                .map(Some)
+                .map_err(|e| {
+                    tracing::error!("Stored revision {} is malformed: {}", id, e);
+                    ApiError::InternalError("Stored revision is malformed".to_string())
+                }),
+            None => Ok(None),
+        }
+    }
+}
+
+/// MongoDB-backed media library (`media` collection)
+pub struct MongoMediaRepository {
+    db: Arc<MongoClient>,
+}
+
+impl MongoMediaRepository {
+    pub fn new(db: Arc<MongoClient>) -> Self {
+        Self { db }
+    }
+
+    async fn find(&self, filter: Document, sort: Document) -> ApiResult<Vec<MediaAsset>> {
+        let mut cursor = self.db.media().find(filter).sort(sort).await?;
+        let mut assets = Vec::new();
+        while let Some(document) = cursor.try_next().await? {
+            match mongodb::bson::from_document::<MediaAsset>(document) {
+                Ok(asset) => assets.push(asset),
+                Err(e) => tracing::warn!("Skipping malformed media asset: {}", e),
+            }
+        }
+        Ok(assets)
+    }
+}
+
+/// Pipeline stage expression: `references` without the entries of one document
+fn references_without(kind: ContentKind, content_id: ObjectId) -> Document {
+    doc! {
+        "$filter": {
+            "input": "$references",
+            "cond": {
+                "$not": [{
+                    "$and": [
+                        { "$eq": ["$$this.kind", kind.as_str()] },
+                        { "$eq": ["$$this.content_id", content_id] },
+                    ]
+                }]
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl MediaRepository for MongoMediaRepository {
+    async fn create(&self, asset: MediaAsset) -> ApiResult<ObjectId> {
+        let document = mongodb::bson::to_document(&asset)?;
+        insert(&self.db.media(), document).await
+    }
+
+    async fn list(&self) -> ApiResult<Vec<MediaAsset>> {
+        self.find(doc! {}, doc! { "uploaded_at": -1 }).await
+    }
+
+    async fn list_orphans(&self, uploaded_before: DateTime<Utc>) -> ApiResult<Vec<MediaAsset>> {
+        let filter = doc! {
+            "reference_count": 0,
+            "uploaded_at": { "$lt": BsonDateTime::from_chrono(uploaded_before) },
+        };
+        self.find(filter, doc! { "uploaded_at": 1 }).await
+    }
+
+    async fn set_references(&self, reference: MediaReference, urls: &[String]) -> ApiResult<()> {
+        let kind = reference.kind;
+        let content_id = reference.content_id;
+        let count = doc! { "$set": { "reference_count": { "$size": "$references" } } };
+
+        // Assets the document no longer uses lose its reference
+        let released = doc! {
+            "references": { "$elemMatch": { "kind": kind.as_str(), "content_id": content_id } },
+            "urls": { "$nin": urls.to_vec() },
+        };
+        let release = vec![
+            doc! { "$set": { "references": references_without(kind, content_id) } },
+            count.clone(),
+        ];
+        self.db.media().update_many(released, release).await?;
+
+        // Assets it uses get exactly one, current reference
+        if !urls.is_empty() {
+            let entry = mongodb::bson::to_document(&reference)?;
+            let add = vec![
+                doc! {
+                    "$set": {
+                        "references": {
+                            "$concatArrays": [references_without(kind, content_id), [entry]]
+                        }
+                    }
+                },
+                count,
+            ];
+            self.db
+                .media()
+                .update_many(doc! { "urls": { "$in": urls.to_vec() } }, add)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn delete_orphan(&self, id: ObjectId) -> ApiResult<bool> {
+        let result = self
+            .db
+            .media()
+            .delete_one(doc! { "_id": id, "reference_count": 0 })
+            .await?;
+        Ok(result.deleted_count > 0)
+    }
+}
+
+/// MongoDB-backed link checks, one document per content field
+pub struct MongoLinkCheckRepository {
+    db: Arc<MongoClient>,
+}
+
+impl MongoLinkCheckRepository {
+    pub fn new(db: Arc<MongoClient>) -> Self {
+        Self { db }
+    }
+
+    async fn find(&self, filter: Document) -> ApiResult<Vec<LinkCheck>> {
+        let mut cursor = self
+            .db
+            .link_checks()
+            .find(filter)
+            .sort(doc! { "kind": 1, "slug": 1, "field": 1 })
+            .await?;
+        let mut checks = Vec::new();
+        while let Some(document) = cursor.try_next().await? {
+            match mongodb::bson::from_document::<LinkCheck>(document) {
+                Ok(check) => checks.push(check),
+                Err(e) => tracing::warn!("Skipping malformed link check: {}", e),
+            }
+        }
+        Ok(checks)
+    }
+}
+
+#[async_trait]
+impl LinkCheckRepository for MongoLinkCheckRepository {
+    async fn list(&self) -> ApiResult<Vec<LinkCheck>> {
+        self.find(doc! {}).await
+    }
+
+    async fn list_failing(&self) -> ApiResult<Vec<LinkCheck>> {
+        self.find(doc! { "state": { "$ne": "ok" } }).await
+    }
+
+    async fn upsert(&self, check: LinkCheck) -> ApiResult<()> {
+        let filter = doc! {
+            "kind": check.kind.as_str(),
+            "slug": &check.slug,
+            "field": &check.field,
+        };
+        let document = mongodb::bson::to_document(&check)?;
+        self.db
+            .link_checks()
+            .replace_one(filter, document)
+            .upsert(true)
+            .await?;
+        Ok(())
+    }
+
+    async fn prune(&self, checked_before: DateTime<Utc>) -> ApiResult<u64> {
+        let result = self
+            .db
+            .link_checks()
+            .delete_many(doc! {
+                "checked_at": { "$lt": BsonDateTime::from_chrono(checked_before) }
+            })
+            .await?;
+        Ok(result.deleted_count)
+    }
+}
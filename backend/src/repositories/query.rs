@@ -0,0 +1,273 @@
+use crate::error::{ApiError, ApiResult};
+use mongodb::bson::{oid::ObjectId, Bson, Document};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+/// Sort order for list queries - `date`/`title` ascending, `-` prefix for descending
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum ListSort {
+    /// Insertion order (MongoDB `_id`)
+    #[default]
+    #[serde(rename = "inserted")]
+    Inserted,
+    #[serde(rename = "date")]
+    DateAsc,
+    #[serde(rename = "-date")]
+    DateDesc,
+    #[serde(rename = "title")]
+    TitleAsc,
+    #[serde(rename = "-title")]
+    TitleDesc,
+}
+
+/// Optimistic concurrency precondition of a write, taken from `If-Match`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum VersionCheck {
+    /// Write whatever the stored version is
+    #[default]
+    Any,
+    /// Write only if the stored document is one of these, by id and version
+    OneOf(Vec<(ObjectId, i64)>),
+}
+
+impl VersionCheck {
+    /// True if the document `id` at `version` may be written
+    pub fn allows(&self, id: Option<ObjectId>, version: i64) -> bool {
+        match self {
+            Self::Any => true,
+            Self::OneOf(accepted) => id.is_some_and(|id| accepted.contains(&(id, version))),
+        }
+    }
+}
+
+/// Error for a write whose `If-Match` no longer matches the stored document
+pub fn modified_since_read(slug: &str) -> ApiError {
+    ApiError::PreconditionFailed(format!("'{}' has been modified since it was read", slug))
+}
+
+/// Error for a bulk write naming projects that do not exist or are in the trash
+pub fn projects_not_found(slugs: &[&str]) -> ApiError {
+    ApiError::NotFound(format!("Projects not found: {}", slugs.join(", ")))
+}
+
+/// Every slug of `current` after a reorder: the `slugs` trade places among the positions
+/// they held, in the requested sequence, and all other slugs keep theirs
+pub fn reordered<'a>(current: &[&'a str], slugs: &'a [String]) -> Vec<&'a str> {
+    let mut requested = slugs.iter().map(String::as_str);
+    current
+        .iter()
+        .map(|&slug| match slugs.iter().any(|listed| listed == slug) {
+            true => requested.next().unwrap_or(slug),
+            false => slug,
+        })
+        .collect()
+}
+
+/// Fields a partial update sets or removes, keyed by dotted BSON path
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FieldChanges {
+    pub set: Document,
+    pub unset: Vec<String>,
+}
+
+impl FieldChanges {
+    /// Smallest set of paths turning `before` into `after`
+    /// Subdocuments are descended into, anything else (arrays included) is replaced whole
+    pub fn between<T: Serialize>(before: &T, after: &T) -> ApiResult<Self> {
+        fn walk(prefix: &str, before: &Document, after: &Document, changes: &mut FieldChanges) {
+            for (key, value) in after {
+                let path = format!("{}{}", prefix, key);
+                match (before.get(key), value) {
+                    (Some(old), new) if old == new => {}
+                    (Some(Bson::Document(old)), Bson::Document(new)) => {
+                        walk(&format!("{}.", path), old, new, changes)
+                    }
+                    _ => {
+                        changes.set.insert(path, value.clone());
+                    }
+                }
+            }
+            for key in before.keys().filter(|key| !after.contains_key(key)) {
+                changes.unset.push(format!("{}{}", prefix, key));
+            }
+        }
+
+        let mut changes = Self::default();
+        walk(
+            "",
+            &mongodb::bson::to_document(before)?,
+            &mongodb::bson::to_document(after)?,
+            &mut changes,
+        );
+        Ok(changes)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty() && self.unset.is_empty()
+    }
+
+    /// Every changed path, set or removed
+    pub fn paths(&self) -> Vec<&str> {
+        self.set
+            .keys()
+            .chain(self.unset.iter())
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// The changes, also clearing the verification when one of `verified_fields` changes
+    pub fn voiding_verification(&self, verified_fields: &[&str]) -> Self {
+        let mut changes = self.clone();
+        if self
+            .paths()
+            .iter()
+            .any(|path| verified_fields.contains(path))
+        {
+            changes.set.insert("verified", false);
+            changes.set.insert("verified_at", Bson::Null);
+            changes.unset.retain(|path| path != "verified_at");
+        }
+        changes
+    }
+
+    /// MongoDB update operators applying the changes
+    pub fn to_update(&self) -> Document {
+        let mut update = Document::new();
+        if !self.set.is_empty() {
+            update.insert("$set", self.set.clone());
+        }
+        if !self.unset.is_empty() {
+            let unset: Document = self
+                .unset
+                .iter()
+                .map(|path| (path.clone(), Bson::from("")))
+                .collect();
+            update.insert("$unset", unset);
+        }
+        update
+    }
+
+    /// Apply the changes to a document in memory, as MongoDB would
+    pub fn apply(&self, document: &mut Document) {
+        /// Document holding the last segment of `path`, missing parents are created on `$set` only
+        fn parent<'a>(
+            document: &'a mut Document,
+            path: &'a str,
+            create: bool,
+        ) -> Option<(&'a mut Document, &'a str)> {
+            let (parents, leaf) = match path.rsplit_once('.') {
+                Some((parents, leaf)) => (Some(parents), leaf),
+                None => (None, path),
+            };
+            let mut current = document;
+            for segment in parents.into_iter().flat_map(|parents| parents.split('.')) {
+                if create && !matches!(current.get(segment), Some(Bson::Document(_))) {
+                    current.insert(segment, Document::new());
+                }
+                current = current.get_document_mut(segment).ok()?;
+            }
+            Some((current, leaf))
+        }
+
+        for (path, value) in &self.set {
+            if let Some((parent, leaf)) = parent(document, path, true) {
+                parent.insert(leaf, value.clone());
+            }
+        }
+        for path in &self.unset {
+            if let Some((parent, leaf)) = parent(document, path, false) {
+                parent.remove(leaf);
+            }
+        }
+    }
+}
+
+/// Filtering, sorting and pagination of a list request
+#[derive(Debug, Clone, Default)]
+pub struct ListQuery {
+    pub offset: u64,
+    /// Maximum number of items, `None` returns everything after `offset`
+    pub limit: Option<u64>,
+    pub sort: ListSort,
+    /// Projects only: case-insensitive match on one of the technologies
+    pub technology: Option<String>,
+    /// Certificates only: case-insensitive match on the issuer
+    pub issuer: Option<String>,
+    /// Year covered by the project date or certificate issue date, ranges included
+    pub year: Option<i32>,
+}
+
+impl ListQuery {
+    /// Offset of the next page, if any items remain after this one
+    pub fn next_offset(&self, returned: usize, total: u64) -> Option<u64> {
+        let next = self.offset + returned as u64;
+        (self.limit.is_some() && next < total).then_some(next)
+    }
+}
+
+/// One page of results together with the total number of matches
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: u64,
+    pub next_offset: Option<u64>,
+}
+
+impl<T> Page<T> {
+    /// Convert the items while keeping pagination metadata
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            total: self.total,
+            next_offset: self.next_offset,
+        }
+    }
+}
+
+/// Anchored case-insensitive regex matching exactly `value`
+pub fn exact_match_pattern(value: &str) -> String {
+    format!("^{}$", regex::escape(value.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mongodb::bson::doc;
+
+    #[test]
+    fn test_field_changes_cover_only_changed_paths() {
+        let before = doc! {
+            "title": "Rust API",
+            "description": { "overview": "Old", "impact": "Fast" },
+            "technologies": ["Rust"],
+            "demoUrl": "https://demo.example.com",
+        };
+        let after = doc! {
+            "title": "Rust API",
+            "description": { "overview": "New", "impact": "Fast" },
+            "technologies": ["Rust", "Axum"],
+        };
+
+        let changes = FieldChanges::between(&before, &after).unwrap();
+        assert_eq!(
+            changes.set,
+            doc! { "description.overview": "New", "technologies": ["Rust", "Axum"] }
+        );
+        assert_eq!(changes.unset, vec!["demoUrl"]);
+        assert_eq!(
+            changes.to_update(),
+            doc! {
+                "$set": { "description.overview": "New", "technologies": ["Rust", "Axum"] },
+                "$unset": { "demoUrl": "" }
+            }
+        );
+
+        let mut applied = before.clone();
+        changes.apply(&mut applied);
+        assert_eq!(applied, after);
+        assert!(FieldChanges::between(&after, &after).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,440 @@
+use crate::{
+    error::ApiResult,
+    models::{
+        link::{LinkCheck, LinkState},
+        ContentKind,
+    },
+    repositories::Repositories,
+};
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+use reqwest::{Client, Method, Url};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+/// Per-request timeout when LINK_CHECK_TIMEOUT_SECS is not configured
+pub const DEFAULT_LINK_TIMEOUT: Duration = Duration::from_secs(10);
+/// Pause between scheduled runs when LINK_CHECK_INTERVAL_HOURS is not configured
+pub const DEFAULT_LINK_CHECK_INTERVAL: Duration = Duration::from_secs(24 * 3600);
+/// Links probed at once
+const CONCURRENCY: usize = 8;
+/// Redirects followed before a link counts as broken
+const MAX_REDIRECTS: usize = 10;
+
+/// Result of one probe
+#[derive(Debug, Clone, PartialEq)]
+pub struct Probe {
+    pub state: LinkState,
+    pub http_status: Option<u16>,
+    pub error: Option<String>,
+}
+
+/// Summary of a finished run
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "swagger", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct LinkCheckRun {
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub checked: usize,
+    pub failing: usize,
+    /// Set when content could not be loaded or results not stored
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Whether a run is in progress, and how the last one went
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "swagger", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct LinkCheckStatus {
+    pub running: bool,
+    pub last_run: Option<LinkCheckRun>,
+}
+
+/// A URL field of a stored project or certificate
+struct Link {
+    kind: ContentKind,
+    slug: String,
+    field: &'static str,
+    url: String,
+}
+
+/// Probes every URL stored on projects and certificates and records the outcome
+/// Runs on a schedule and on admin request, never two at once
+pub struct LinkChecker {
+    repositories: Repositories,
+    client: Client,
+    timeout: Duration,
+    running: AtomicBool,
+    last_run: Mutex<Option<LinkCheckRun>>,
+}
+
+impl LinkChecker {
+    pub fn new(repositories: Repositories, timeout: Duration) -> Self {
+        let client = Client::builder()
+            .timeout(timeout)
+            .redirect(reqwest::redirect::Policy::limited(MAX_REDIRECTS))
+            .user_agent(concat!(
+                "portfolio-link-checker/",
+                env!("CARGO_PKG_VERSION")
+            ))
+            .build()
+            .expect("link checker HTTP client");
+        Self {
+            repositories,
+            client,
+            timeout,
+            running: AtomicBool::new(false),
+            last_run: Mutex::new(None),
+        }
+    }
+
+    pub fn status(&self) -> LinkCheckStatus {
+        LinkCheckStatus {
+            running: self.running.load(Ordering::SeqCst),
+            last_run: self.last_run.lock().unwrap().clone(),
+        }
+    }
+
+    /// Claim the checker for a run, returns false if one is already in progress
+    pub fn try_start(&self) -> bool {
+        self.running
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok()
+    }
+
+    /// Check every link once, after `try_start` succeeded
+    pub async fn run_started(&self) -> LinkCheckRun {
+        let started_at = Utc::now();
+        let (checked, failing, error) = match self.check_all(started_at).await {
+            Ok((checked, failing)) => (checked, failing, None),
+            Err(e) => {
+                tracing::error!("Link check aborted: {}", e);
+                (0, 0, Some(e.to_string()))
+            }
+        };
+        let run = LinkCheckRun {
+            started_at,
+            finished_at: Utc::now(),
+            checked,
+            failing,
+            error,
+        };
+        tracing::info!(
+            "Link check finished: {} checked, {} failing",
+            run.checked,
+            run.failing
+        );
+
+        *self.last_run.lock().unwrap() = Some(run.clone());
+        self.running.store(false, Ordering::SeqCst);
+        run
+    }
+
+    /// Check every link once, None if a run is already in progress
+    pub async fn run(&self) -> Option<LinkCheckRun> {
+        if !self.try_start() {
+            return None;
+        }
+        Some(self.run_started().await)
+    }
+
+    /// Run now and then every `interval`, skipping ticks that find a run in progress
+    pub async fn run_every(&self, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            ticker.tick().await;
+            if self.run().await.is_none() {
+                tracing::info!("Scheduled link check skipped, a run is in progress");
+            }
+        }
+    }
+
+    /// Probe and store every link, then drop checks of links no longer stored
+    async fn check_all(&self, started_at: DateTime<Utc>) -> ApiResult<(usize, usize)> {
+        let links = self.collect_links().await?;
+        let previous: HashMap<(ContentKind, String, String), LinkCheck> = self
+            .repositories
+            .links
+            .list()
+            .await?
+            .into_iter()
+            .map(|check| ((check.kind, check.slug.clone(), check.field.clone()), check))
+            .collect();
+        let previous = &previous;
+
+        let checks: Vec<LinkCheck> = stream::iter(links)
+            .map(|link| async move {
+                let probe = self.probe(&link.url).await;
+                let checked_at = Utc::now();
+                let key = (link.kind, link.slug.clone(), link.field.to_string());
+                // A failure keeps the start of its streak while the URL stays the same
+                let failing_since = (probe.state != LinkState::Ok).then(|| {
+                    previous
+                        .get(&key)
+                        .filter(|check| check.url == link.url)
+                        .and_then(|check| check.failing_since)
+                        .unwrap_or(checked_at)
+                });
+                LinkCheck {
+                    kind: link.kind,
+                    slug: link.slug,
+                    field: link.field.to_string(),
+                    url: link.url,
+                    state: probe.state,
+                    http_status: probe.http_status,
+                    error: probe.error,
+                    checked_at,
+                    failing_since,
+                }
+            })
+            .buffer_unordered(CONCURRENCY)
+            .collect()
+            .await;
+
+        let checked = checks.len();
+        let failing = checks.iter().filter(|check| check.is_failing()).count();
+        for check in checks {
+            self.repositories.links.upsert(check).await?;
+        }
+        self.repositories.links.prune(started_at).await?;
+        Ok((checked, failing))
+    }
+
+    /// URL fields of every live project and certificate, empty fields skipped
+    async fn collect_links(&self) -> ApiResult<Vec<Link>> {
+        let mut links = Vec::new();
+        let mut push = |kind, slug: &str, field, url: Option<&String>| {
+            if let Some(url) = url.map(|url| url.trim()).filter(|url| !url.is_empty()) {
+                links.push(Link {
+                    kind,
+                    slug: slug.to_string(),
+                    field,
+                    url: url.to_string(),
+                });
+            }
+        };
+
+        for project in self.repositories.projects.list().await? {
+            let kind = ContentKind::Project;
+            push(kind, &project.slug, "githubUrl", Some(&project.github_url));
+            push(kind, &project.slug, "demoUrl", project.demo_url.as_ref());
+            push(
+                kind,
+                &project.slug,
+                "ReportUrl",
+                project.report_url.as_ref(),
+            );
+            push(
+                kind,
+                &project.slug,
+                "youtubeUrl",
+                project.youtube_url.as_ref(),
+            );
+        }
+        for certificate in self.repositories.certificates.list().await? {
+            let kind = ContentKind::Certificate;
+            push(kind, &certificate.slug, "link", Some(&certificate.link));
+        }
+        Ok(links)
+    }
+
+    /// HEAD the URL, falling back to GET when HEAD fails: plenty of servers
+    /// answer HEAD with 403, 404 or 405 while serving the page just fine
+    pub async fn probe(&self, url: &str) -> Probe {
+        let url = match Url::parse(url) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => url,
+            _ => {
+                return Probe {
+                    state: LinkState::Broken,
+                    http_status: None,
+                    error: Some("Not an http(s) URL".to_string()),
+                }
+            }
+        };
+
+        let head = self.request(Method::HEAD, url.clone()).await;
+        if head.state == LinkState::Ok {
+            return head;
+        }
+        self.request(Method::GET, url).await
+    }
+
+    async fn request(&self, method: Method, url: Url) -> Probe {
+        // Only the status line matters, the body of a GET is never read
+        match self.client.request(method, url).send().await {
+            Ok(response) => {
+                let status = response.status();
+                Probe {
+                    state: if status.is_success() {
+                        LinkState::Ok
+                    } else {
+                        LinkState::Broken
+                    },
+                    http_status: Some(status.as_u16()),
+                    error: (!status.is_success()).then(|| status.to_string()),
+                }
+            }
+            Err(e) if e.is_redirect() => Probe {
+                state: LinkState::Broken,
+                http_status: None,
+                error: Some(format!("More than {} redirects", MAX_REDIRECTS)),
+            },
+            Err(e) => Probe {
+                state: LinkState::Unreachable,
+                http_status: None,
+                error: Some(if e.is_timeout() {
+                    format!("No response within {}s", self.timeout.as_secs_f32())
+                } else {
+                    e.to_string()
+                }),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::Certificate, repositories::VersionCheck, services::slug::slugify};
+    use axum::{http::StatusCode, routing::get, Router};
+
+    /// Local server standing in for the sites content links to
+    async fn stub_server() -> String {
+        let app = Router::new()
+            .route("/ok", get(|| async { "ok" }))
+            .route(
+                "/no-head",
+                get(|| async { "ok" }).head(|| async { StatusCode::METHOD_NOT_ALLOWED }),
+            )
+            .route(
+                "/gone",
+                get(|| async { StatusCode::GONE }).head(|| async { StatusCode::GONE }),
+            )
+            .route(
+                "/slow",
+                get(|| async {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    "late"
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}", address)
+    }
+
+    fn certificate(name: &str, link: &str) -> Certificate {
+        Certificate {
+            id: None,
+            name: name.to_string(),
+            issuer: "Coursera".to_string(),
+            link: link.to_string(),
+            slug: slugify(name),
+            issue_date: None,
+            embedding: None,
+            embedding_model: None,
+            embedding_dimensions: None,
+            expires_at: None,
+            credential_id: None,
+            image_url: None,
+            verified: false,
+            verified_at: None,
+            publication: Default::default(),
+            publish_at: None,
+            deleted_at: None,
+            deleted_by: None,
+            version: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_records_failing_links_and_drops_removed_ones() {
+        let base = stub_server().await;
+        let repositories = Repositories::in_memory();
+        let project = serde_json::from_value(serde_json::json!({
+            "slug": "rust-api",
+            "date": "2024",
+            "title": "Rust API",
+            "technologies": ["Rust"],
+            "features": [],
+            "githubUrl": format!("{}/ok", base),
+            "demoUrl": format!("{}/gone", base),
+            "youtubeUrl": format!("{}/no-head", base),
+            "ReportUrl": ""
+        }))
+        .unwrap();
+        repositories.projects.create(project).await.unwrap();
+        for (name, link) in [
+            ("Slow", format!("{}/slow", base)),
+            ("Not A Url", "coursera certificate".to_string()),
+        ] {
+            repositories
+                .certificates
+                .create(certificate(name, &link))
+                .await
+                .unwrap();
+        }
+
+        let checker = LinkChecker::new(repositories.clone(), Duration::from_millis(300));
+        let run = checker.run().await.unwrap();
+        assert_eq!((run.checked, run.failing), (5, 3));
+        assert!(!checker.status().running);
+
+        let states: Vec<_> = repositories
+            .links
+            .list()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|check| (check.slug, check.field, check.state, check.http_status))
+            .collect();
+        let expected = [
+            ("not-a-url", "link", LinkState::Broken, None),
+            ("slow", "link", LinkState::Unreachable, None),
+            ("rust-api", "demoUrl", LinkState::Broken, Some(410)),
+            ("rust-api", "githubUrl", LinkState::Ok, Some(200)),
+            ("rust-api", "youtubeUrl", LinkState::Ok, Some(200)),
+        ];
+        assert_eq!(
+            states,
+            expected
+                .map(|(slug, field, state, status)| (
+                    slug.to_string(),
+                    field.to_string(),
+                    state,
+                    status
+                ))
+                .to_vec()
+        );
+
+        // Failure streaks survive later runs, checks of deleted content are dropped
+        let first = repositories.links.list_failing().await.unwrap();
+        let since = first
+            .iter()
+            .find(|c| c.slug == "rust-api")
+            .unwrap()
+            .failing_since;
+        repositories
+            .certificates
+            .trash("slow", "admin@example.com", &VersionCheck::Any)
+            .await
+            .unwrap();
+        checker.run().await.unwrap();
+
+        let failing = repositories.links.list_failing().await.unwrap();
+        assert_eq!(failing.len(), 2);
+        assert_eq!(failing[1].slug, "rust-api");
+        assert_eq!(failing[1].failing_since, since);
+        assert!(failing[1].checked_at > since.unwrap());
+    }
+}
@@ -0,0 +1,349 @@
+use crate::{
+    error::{ApiError, ApiResult},
+    models::{project::Description, publication, Project, ProjectVisibility, PublicationStatus},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer};
+
+/// Project fields taken from the YAML front matter
+/// Keys follow the project JSON (`githubUrl`), snake_case spellings are accepted too
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct FrontMatter {
+    slug: String,
+    title: Option<String>,
+    #[serde(deserialize_with = "scalar_string")]
+    date: String,
+    technologies: Vec<String>,
+    features: Vec<String>,
+    #[serde(rename = "githubUrl", alias = "github_url", alias = "github")]
+    github_url: Option<String>,
+    #[serde(rename = "demoUrl", alias = "demo_url", alias = "demo")]
+    demo_url: Option<String>,
+    #[serde(rename = "ReportUrl", alias = "reportUrl", alias = "report_url")]
+    report_url: Option<String>,
+    #[serde(rename = "youtubeUrl", alias = "youtube_url", alias = "youtube")]
+    youtube_url: Option<String>,
+    images: Option<Vec<String>>,
+    featured: bool,
+    order: i32,
+    visibility: ProjectVisibility,
+    publication: PublicationStatus,
+    #[serde(alias = "publishAt", with = "publication::publish_at")]
+    publish_at: Option<DateTime<Utc>>,
+}
+
+/// Dates are parsed from text, but YAML reads `date: 2024` as a number
+fn scalar_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    match serde_yaml::Value::deserialize(deserializer)? {
+        serde_yaml::Value::Null => Ok(String::new()),
+        serde_yaml::Value::String(text) => Ok(text),
+        serde_yaml::Value::Number(number) => Ok(number.to_string()),
+        serde_yaml::Value::Bool(flag) => Ok(flag.to_string()),
+        _ => Err(serde::de::Error::custom("expected a single value")),
+    }
+}
+
+/// Where the content of a `## Heading` section goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Overview,
+    Problem,
+    Solution,
+    Impact,
+    Dashboard,
+    Features,
+    Technologies,
+}
+
+impl Section {
+    fn from_heading(heading: &str) -> Option<Self> {
+        let heading = heading.trim().trim_end_matches(':').to_lowercase();
+        let heading = heading.strip_prefix("the ").unwrap_or(&heading);
+        match heading {
+            "overview" | "summary" | "about" => Some(Self::Overview),
+            "problem" | "challenge" | "challenges" => Some(Self::Problem),
+            "solution" | "approach" => Some(Self::Solution),
+            "impact" | "results" | "outcome" | "outcomes" => Some(Self::Impact),
+            "dashboard" => Some(Self::Dashboard),
+            "features" | "key features" => Some(Self::Features),
+            "technologies" | "tech stack" | "stack" => Some(Self::Technologies),
+            _ => None,
+        }
+    }
+}
+
+/// A project read from a Markdown case study, with notes on ignored content
+#[derive(Debug)]
+pub struct MarkdownProject {
+    pub project: Project,
+    pub warnings: Vec<String>,
+}
+
+/// Split a leading `---` fenced YAML block from the Markdown body
+fn split_front_matter(text: &str) -> (Option<&str>, &str) {
+    let text = text.trim_start_matches('\u{feff}');
+    let Some(rest) = text
+        .strip_prefix("---\n")
+        .or_else(|| text.strip_prefix("---\r\n"))
+    else {
+        return (None, text);
+    };
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return (Some(&rest[..offset]), &rest[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+    (None, text)
+}
+
+/// Items of a Markdown bullet or numbered list
+fn list_items(text: &str) -> Vec<String> {
+    text.lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let item = line
+                .strip_prefix("- ")
+                .or_else(|| line.strip_prefix("* "))
+                .or_else(|| {
+                    let (number, item) = line.split_once(". ")?;
+                    number.chars().all(|c| c.is_ascii_digit()).then_some(item)
+                })?;
+            Some(item.trim().to_string()).filter(|item| !item.is_empty())
+        })
+        .collect()
+}
+
+/// Parse a Markdown case study into a project
+/// Front matter holds slug, date, technologies and URLs; `# Title` names the project
+/// unless the front matter does; `## Problem`-style sections fill the description
+/// The result is not validated here, importing it applies the `Project` rules
+pub fn parse_project(text: &str) -> ApiResult<MarkdownProject> {
+    let (front_matter, body) = split_front_matter(text);
+    let front: FrontMatter = match front_matter {
+        Some(yaml) if !yaml.trim().is_empty() => serde_yaml::from_str(yaml)
+            .map_err(|e| ApiError::BadRequest(format!("Invalid front matter: {}", e)))?,
+        _ => FrontMatter::default(),
+    };
+
+    let mut warnings = Vec::new();
+    let mut heading_title = None;
+    let mut intro = String::new();
+    let mut sections: Vec<(Option<Section>, String)> = Vec::new();
+    let mut fence: Option<&str> = None;
+
+    for line in body.lines() {
+        let trimmed = line.trim_start();
+        // Headings inside fenced code blocks are code, not structure
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+        } else if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            fence = Some(&trimmed[..3]);
+        } else if let Some(heading) = trimmed.strip_prefix("# ") {
+            if heading_title.is_none() {
+                heading_title = Some(heading.trim().to_string());
+                continue;
+            }
+        } else if let Some(heading) = trimmed.strip_prefix("## ") {
+            let section = Section::from_heading(heading);
+            if section.is_none() {
+                warnings.push(format!(
+                    "Section '{}' has no matching field and was ignored",
+                    heading.trim()
+                ));
+            }
+            sections.push((section, String::new()));
+            continue;
+        }
+
+        let target = match sections.last_mut() {
+            Some((_, content)) => content,
+            None => &mut intro,
+        };
+        target.push_str(line);
+        target.push('\n');
+    }
+
+    let title = front
+        .title
+        .filter(|title| !title.trim().is_empty())
+        .or(heading_title)
+        .ok_or_else(|| {
+            ApiError::BadRequest(
+                "No title: add `title` to the front matter or a `# Heading`".to_string(),
+            )
+        })?;
+
+    let mut description = Description {
+        title: None,
+        overview: None,
+        problem: None,
+        solution: None,
+        impact: None,
+        dataset_description: None,
+        dashboard_info: None,
+    };
+    let mut features = front.features;
+    let mut technologies = front.technologies;
+    for (section, content) in sections {
+        let content = content.trim().to_string();
+        if content.is_empty() {
+            continue;
+        }
+        let field = match section {
+            Some(Section::Overview) => &mut description.overview,
+            Some(Section::Problem) => &mut description.problem,
+            Some(Section::Solution) => &mut description.solution,
+            Some(Section::Impact) => &mut description.impact,
+            Some(Section::Dashboard) => &mut description.dashboard_info,
+            Some(Section::Features) => {
+                features.extend(list_items(&content));
+                continue;
+            }
+            // Front matter wins, the section only fills a missing list
+            Some(Section::Technologies) => {
+                if technologies.is_empty() {
+                    technologies = list_items(&content);
+                }
+                continue;
+            }
+            None => continue,
+        };
+        match field {
+            Some(existing) => {
+                existing.push_str("\n\n");
+                existing.push_str(&content);
+            }
+            None => *field = Some(content),
+        }
+    }
+    // Text between the title and the first section is the overview when there is none
+    let intro = intro.trim();
+    if description.overview.is_none() && !intro.is_empty() {
+        description.overview = Some(intro.to_string());
+    }
+
+    let has_description = [
+        &description.overview,
+        &description.problem,
+        &description.solution,
+        &description.impact,
+        &description.dashboard_info,
+    ]
+    .iter()
+    .any(|field| field.is_some());
+
+    let date = match front.date.trim() {
+        "" => None,
+        text => Some(
+            text.parse()
+                .map_err(|e| ApiError::BadRequest(format!("Invalid date: {}", e)))?,
+        ),
+    };
+
+    let project = Project {
+        id: None,
+        slug: front.slug,
+        date,
+        title,
+        description: has_description.then_some(description),
+        technologies,
+        features,
+        github_url: front.github_url.unwrap_or_default(),
+        report_url: front.report_url,
+        demo_url: front.demo_url,
+        youtube_url: front.youtube_url,
+        embedding: None,
+        embedding_model: None,
+        embedding_dimensions: None,
+        images: front.images,
+        featured: front.featured,
+        order: front.order,
+        visibility: front.visibility,
+        publication: front.publication,
+        publish_at: front.publish_at,
+        deleted_at: None,
+        deleted_by: None,
+        version: 0,
+    };
+    Ok(MarkdownProject { project, warnings })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CASE_STUDY: &str = "---
+slug: rust-api
+date: 2024
+technologies: [Rust, Axum]
+github_url: https://github.com/example/rust-api
+demoUrl: https://demo.example.com
+---
+# Rust API
+
+A fast REST API.
+
+## The Problem
+Slow endpoints.
+
+```bash
+## not a heading
+```
+
+## Solution
+Rewrite in Rust.
+
+## Key Features
+- Caching
+- Pagination
+
+## Lessons Learned
+Measure first.
+";
+
+    #[test]
+    fn test_front_matter_and_sections_map_onto_project() {
+        let parsed = parse_project(CASE_STUDY).unwrap();
+        let project = parsed.project;
+
+        assert_eq!(project.slug, "rust-api");
+        assert_eq!(project.title, "Rust API");
+        assert_eq!(project.date.unwrap().to_string(), "2024");
+        assert_eq!(project.technologies, vec!["Rust", "Axum"]);
+        assert_eq!(project.github_url, "https://github.com/example/rust-api");
+        assert_eq!(
+            project.demo_url.as_deref(),
+            Some("https://demo.example.com")
+        );
+        assert_eq!(project.features, vec!["Caching", "Pagination"]);
+
+        let description = project.description.unwrap();
+        assert_eq!(description.overview.as_deref(), Some("A fast REST API."));
+        assert_eq!(
+            description.problem.as_deref(),
+            Some("Slow endpoints.\n\n```bash\n## not a heading\n```")
+        );
+        assert_eq!(description.solution.as_deref(), Some("Rewrite in Rust."));
+        assert_eq!(description.impact, None);
+        assert_eq!(
+            parsed.warnings,
+            vec!["Section 'Lessons Learned' has no matching field and was ignored"]
+        );
+    }
+
+    #[test]
+    fn test_title_is_required_and_front_matter_optional() {
+        let parsed = parse_project("# Notes\n\nJust text.\n").unwrap();
+        assert_eq!(parsed.project.title, "Notes");
+        assert_eq!(parsed.project.slug, "");
+
+        assert!(parse_project("## Problem\nNo title.\n").is_err());
+        assert!(parse_project("---\ntitle: [unclosed\n---\n").is_err());
+    }
+}
@@ -0,0 +1,272 @@
+use super::MediaStore;
+use anyhow::{anyhow, bail, Result};
+use async_trait::async_trait;
+use reqwest::{
+    multipart::{Form, Part},
+    Client,
+};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+const CLOUDINARY_API: &str = "https://api.cloudinary.com/v1_1";
+
+#[derive(Debug, Deserialize)]
+struct UploadResponse {
+    secure_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DestroyResponse {
+    result: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorResponse {
+    error: ErrorMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorMessage {
+    message: String,
+}
+
+/// Media uploaded to Cloudinary with signed upload requests
+/// The API secret never leaves the backend, only its SHA-256 signature is sent
+pub struct CloudinaryMediaStore {
+    client: Client,
+    api_base: String,
+    cloud_name: String,
+    api_key: String,
+    api_secret: String,
+    folder: Option<String>,
+}
+
+impl CloudinaryMediaStore {
+    pub fn new(cloud_name: String, api_key: String, api_secret: String) -> Self {
+        Self {
+            client: Client::new(),
+            api_base: CLOUDINARY_API.to_string(),
+            cloud_name,
+            api_key,
+            api_secret,
+            folder: None,
+        }
+    }
+
+    /// Upload into this Cloudinary folder instead of the account root
+    pub fn with_folder(mut self, folder: String) -> Self {
+        self.folder = Some(folder);
+        self
+    }
+
+    /// Send a signed request to an image endpoint (`upload`, `destroy`) of the account
+    async fn signed_request(
+        &self,
+        action: &str,
+        params: Vec<(&'static str, String)>,
+        file: Option<Part>,
+    ) -> Result<reqwest::Response> {
+        let signature = sign(&params, &self.api_secret);
+        let mut form = params
+            .into_iter()
+            .fold(Form::new(), |form, (name, value)| form.text(name, value))
+            .text("api_key", self.api_key.clone())
+            .text("signature", signature)
+            .text("signature_algorithm", "sha256");
+        if let Some(file) = file {
+            form = form.part("file", file);
+        }
+
+        let url = format!("{}/{}/image/{}", self.api_base, self.cloud_name, action);
+        let response = self.client.post(&url).multipart(form).send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let message = response
+                .json::<ErrorResponse>()
+                .await
+                .map(|body| body.error.message)
+                .unwrap_or_else(|_| "no error message".to_string());
+            bail!("Cloudinary {} failed ({}): {}", action, status, message);
+        }
+        Ok(response)
+    }
+
+    #[cfg(test)]
+    fn with_api_base(mut self, api_base: String) -> Self {
+        self.api_base = api_base;
+        self
+    }
+}
+
+/// Signature of request parameters: `key=value` pairs sorted by key and joined
+/// with `&`, followed by the API secret, hashed with SHA-256
+/// `file`, `api_key`, `resource_type` and `signature_algorithm` are not signed
+fn sign(params: &[(&str, String)], api_secret: &str) -> String {
+    let mut params: Vec<_> = params
+        .iter()
+        .filter(|(_, value)| !value.is_empty())
+        .collect();
+    params.sort_by_key(|(key, _)| *key);
+
+    let payload = params
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect::<Vec<_>>()
+        .join("&");
+    hex::encode(Sha256::digest(format!("{}{}", payload, api_secret)))
+}
+
+#[async_trait]
+impl MediaStore for CloudinaryMediaStore {
+    fn backend(&self) -> &'static str {
+        "cloudinary"
+    }
+
+    async fn put(&self, key: &str, content_type: &str, bytes: Vec<u8>) -> Result<String> {
+        // Cloudinary public IDs carry no extension, the format is kept from the file
+        let public_id = key.rsplit_once('.').map_or(key, |(stem, _)| stem);
+        let mut params = vec![
+            ("public_id", public_id.to_string()),
+            ("timestamp", chrono::Utc::now().timestamp().to_string()),
+            ("overwrite", "false".to_string()),
+        ];
+        if let Some(folder) = &self.folder {
+            params.push(("folder", folder.clone()));
+        }
+        let file = Part::bytes(bytes)
+            .file_name(key.rsplit('/').next().unwrap_or(key).to_string())
+            .mime_str(content_type)?;
+        let response = self.signed_request("upload", params, Some(file)).await?;
+
+        let body: UploadResponse = response
+            .json()
+            .await
+            .map_err(|e| anyhow!("Unexpected Cloudinary response: {}", e))?;
+        Ok(body.secure_url)
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let public_id = key.rsplit_once('.').map_or(key, |(stem, _)| stem);
+        let mut public_id = public_id.to_string();
+        if let Some(folder) = &self.folder {
+            public_id = format!("{}/{}", folder, public_id);
+        }
+        let params = vec![
+            ("public_id", public_id),
+            ("timestamp", chrono::Utc::now().timestamp().to_string()),
+            ("invalidate", "true".to_string()),
+        ];
+
+        let response = self.signed_request("destroy", params, None).await?;
+        let body: DestroyResponse = response
+            .json()
+            .await
+            .map_err(|e| anyhow!("Unexpected Cloudinary response: {}", e))?;
+        match body.result.as_str() {
+            "ok" | "not found" => Ok(()),
+            other => bail!("Cloudinary destroy of '{}' returned '{}'", key, other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        extract::{Multipart, Path},
+        http::StatusCode,
+        routing::post,
+        Json, Router,
+    };
+    use serde_json::{json, Value};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_signature_covers_sorted_non_empty_params() {
+        let signature = sign(
+            &[
+                ("timestamp", "1315060510".to_string()),
+                ("public_id", "sample_image".to_string()),
+                ("eager", "w_400,h_300,c_pad|w_260,h_200,c_crop".to_string()),
+                ("folder", String::new()),
+            ],
+            "abcd",
+        );
+        let expected = Sha256::digest(
+            "eager=w_400,h_300,c_pad|w_260,h_200,c_crop&public_id=sample_image&timestamp=1315060510abcd",
+        );
+        assert_eq!(signature, hex::encode(expected));
+    }
+
+    /// Stand-in for the image API that checks the signature like Cloudinary does
+    async fn image_api(
+        Path((cloud, action)): Path<(String, String)>,
+        mut multipart: Multipart,
+    ) -> (StatusCode, Json<Value>) {
+        let mut fields = HashMap::new();
+        while let Some(field) = multipart.next_field().await.unwrap() {
+            let name = field.name().unwrap().to_string();
+            let value = match name.as_str() {
+                "file" => field.bytes().await.unwrap().len().to_string(),
+                _ => field.text().await.unwrap(),
+            };
+            fields.insert(name, value);
+        }
+
+        let signed: Vec<(&str, String)> = fields
+            .iter()
+            .filter(|(name, _)| {
+                !matches!(
+                    name.as_str(),
+                    "file" | "api_key" | "signature" | "signature_algorithm"
+                )
+            })
+            .map(|(name, value)| (name.as_str(), value.clone()))
+            .collect();
+        if fields["api_key"] != "key" || fields["signature"] != sign(&signed, "secret") {
+            return (
+                StatusCode::UNAUTHORIZED,
+                Json(json!({ "error": { "message": "Invalid Signature" } })),
+            );
+        }
+
+        if action == "destroy" {
+            return (StatusCode::OK, Json(json!({ "result": "not found" })));
+        }
+        let url = format!(
+            "https://res.cloudinary.com/{}/image/upload/{}/{}.png",
+            cloud, fields["folder"], fields["public_id"]
+        );
+        (StatusCode::OK, Json(json!({ "secure_url": url })))
+    }
+
+    #[tokio::test]
+    async fn test_signed_requests_upload_and_destroy() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let api_base = format!("http://{}", listener.local_addr().unwrap());
+        let app = Router::new().route("/{cloud}/image/{action}", post(image_api));
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let store = CloudinaryMediaStore::new("demo".into(), "key".into(), "secret".into())
+            .with_folder("portfolio".into())
+            .with_api_base(api_base.clone());
+        let url = store
+            .put("2024/05/diagram-1a2b.png", "image/png", b"png".to_vec())
+            .await
+            .unwrap();
+        assert_eq!(
+            url,
+            "https://res.cloudinary.com/demo/image/upload/portfolio/2024/05/diagram-1a2b.png"
+        );
+
+        store.delete("2024/05/diagram-1a2b.png").await.unwrap();
+
+        let wrong_secret = CloudinaryMediaStore::new("demo".into(), "key".into(), "other".into())
+            .with_api_base(api_base);
+        let error = wrong_secret
+            .put("diagram.png", "image/png", b"png".to_vec())
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("Invalid Signature"));
+    }
+}
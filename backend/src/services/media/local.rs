@@ -0,0 +1,95 @@
+use super::MediaStore;
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use std::path::{Component, Path, PathBuf};
+
+/// Media stored on the local filesystem and served by this backend under `/media`
+/// Shuttle deployments lose local files on redeploy, use Cloudinary there
+pub struct LocalMediaStore {
+    root: PathBuf,
+    base_url: String,
+}
+
+impl LocalMediaStore {
+    pub fn new(root: impl Into<PathBuf>, base_url: &str) -> Self {
+        Self {
+            root: root.into(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Directory files are written to
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// File of `key` below the root, keys reaching outside it are rejected
+    fn path(&self, key: &str) -> Result<PathBuf> {
+        let relative = Path::new(key);
+        if !relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            bail!("Invalid media key '{}'", key);
+        }
+        Ok(self.root.join(relative))
+    }
+}
+
+#[async_trait]
+impl MediaStore for LocalMediaStore {
+    fn backend(&self) -> &'static str {
+        "local"
+    }
+
+    async fn put(&self, key: &str, _content_type: &str, bytes: Vec<u8>) -> Result<String> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&path, bytes).await?;
+        Ok(format!("{}/{}", self.base_url, key))
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        match tokio::fs::remove_file(self.path(key)?).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_files_are_written_below_root() {
+        let root = std::env::temp_dir().join(format!("media-test-{}", uuid::Uuid::new_v4()));
+        let store = LocalMediaStore::new(&root, "https://api.example.com/media/");
+
+        let url = store
+            .put("2024/05/diagram.png", "image/png", b"png".to_vec())
+            .await
+            .unwrap();
+        assert_eq!(url, "https://api.example.com/media/2024/05/diagram.png");
+        assert_eq!(
+            std::fs::read(root.join("2024/05/diagram.png")).unwrap(),
+            b"png"
+        );
+
+        store.delete("2024/05/diagram.png").await.unwrap();
+        assert!(!root.join("2024/05/diagram.png").exists());
+        store.delete("2024/05/diagram.png").await.unwrap();
+
+        assert!(store
+            .put("../escape.png", "image/png", Vec::new())
+            .await
+            .is_err());
+        assert!(store
+            .put("/etc/escape.png", "image/png", Vec::new())
+            .await
+            .is_err());
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
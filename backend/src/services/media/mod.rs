@@ -0,0 +1,221 @@
+pub mod cloudinary;
+pub mod local;
+pub mod processing;
+
+pub use cloudinary::CloudinaryMediaStore;
+pub use local::LocalMediaStore;
+
+use crate::{
+    error::{ApiError, ApiResult},
+    models::media::{MediaAsset, MediaThumbnail},
+    services::slug::slugify,
+};
+use async_trait::async_trait;
+use axum::body::Bytes;
+use processing::ProcessedImage;
+use serde::Serialize;
+use std::sync::Arc;
+
+/// Largest accepted image when MEDIA_MAX_UPLOAD_BYTES is not configured
+pub const DEFAULT_MAX_UPLOAD_BYTES: usize = 10 * 1024 * 1024;
+/// Longest file name stem kept in storage keys
+const MAX_STEM_CHARS: usize = 40;
+
+/// Storage backend for uploaded media
+#[async_trait]
+pub trait MediaStore: Send + Sync {
+    /// Short backend name, reported with every upload
+    fn backend(&self) -> &'static str;
+
+    /// Store `bytes` under `key` (`2024/05/diagram-1a2b3c4d.png`), returning its public URL
+    async fn put(&self, key: &str, content_type: &str, bytes: Vec<u8>) -> anyhow::Result<String>;
+
+    /// Remove the file stored under `key`, succeeding if it is already gone
+    async fn delete(&self, key: &str) -> anyhow::Result<()>;
+}
+
+/// Stored thumbnail of an upload
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "swagger", derive(utoipa::ToSchema))]
+pub struct UploadedThumbnail {
+    pub key: String,
+    pub width: u32,
+    pub height: u32,
+    pub url: String,
+}
+
+/// Stored upload, its URL can be added to `Project.images` or set as `Certificate.image_url`
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "swagger", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct UploadedImage {
+    pub url: String,
+    pub key: String,
+    pub backend: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+    pub content_type: String,
+    pub width: u32,
+    pub height: u32,
+    pub bytes: usize,
+    pub thumbnails: Vec<UploadedThumbnail>,
+}
+
+impl UploadedImage {
+    /// Record of a fresh upload for the `media` collection, nothing references it yet
+    pub fn to_asset(&self, uploaded_by: &str) -> MediaAsset {
+        let thumbnails: Vec<MediaThumbnail> = self
+            .thumbnails
+            .iter()
+            .map(|thumbnail| MediaThumbnail {
+                key: thumbnail.key.clone(),
+                url: thumbnail.url.clone(),
+                width: thumbnail.width,
+                height: thumbnail.height,
+            })
+            .collect();
+        let urls = std::iter::once(self.url.clone())
+            .chain(thumbnails.iter().map(|thumbnail| thumbnail.url.clone()))
+            .collect();
+
+        MediaAsset {
+            id: None,
+            key: self.key.clone(),
+            url: self.url.clone(),
+            backend: self.backend.clone(),
+            file_name: self.file_name.clone(),
+            content_type: self.content_type.clone(),
+            bytes: self.bytes as i64,
+            width: self.width,
+            height: self.height,
+            thumbnails,
+            urls,
+            uploaded_by: uploaded_by.to_string(),
+            uploaded_at: chrono::Utc::now(),
+            references: Vec::new(),
+            reference_count: 0,
+        }
+    }
+}
+
+/// Upload validated and processed, not stored yet
+pub struct PreparedImage {
+    file_name: Option<String>,
+    image: ProcessedImage,
+}
+
+/// Image upload pipeline: validation and processing, then storage in a `MediaStore`
+pub struct MediaService {
+    store: Arc<dyn MediaStore>,
+    max_bytes: usize,
+}
+
+impl MediaService {
+    pub fn new(store: Arc<dyn MediaStore>, max_bytes: usize) -> Self {
+        Self { store, max_bytes }
+    }
+
+    /// Largest accepted image in bytes
+    pub fn max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+
+    /// Check type and size, strip metadata and render thumbnails, off the async runtime
+    pub async fn prepare(
+        &self,
+        file_name: Option<String>,
+        content_type: Option<String>,
+        bytes: Bytes,
+    ) -> ApiResult<PreparedImage> {
+        let max_bytes = self.max_bytes;
+        let image = tokio::task::spawn_blocking(move || {
+            processing::process(content_type.as_deref(), &bytes, max_bytes)
+        })
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Image processing failed: {}", e)))??;
+
+        Ok(PreparedImage { file_name, image })
+    }
+
+    /// Store a prepared image and its thumbnails under a fresh dated key
+    pub async fn store(&self, prepared: PreparedImage) -> ApiResult<UploadedImage> {
+        let PreparedImage { file_name, image } = prepared;
+        let base = storage_key_base(file_name.as_deref());
+        let key = format!("{}.{}", base, image.extension);
+        let bytes = image.original.bytes.len();
+
+        let url = self
+            .put(&key, image.content_type, image.original.bytes)
+            .await?;
+        let mut thumbnails = Vec::new();
+        for thumbnail in image.thumbnails {
+            let thumbnail_key = format!("{}-w{}.{}", base, thumbnail.width, image.extension);
+            thumbnails.push(UploadedThumbnail {
+                key: thumbnail_key.clone(),
+                width: thumbnail.width,
+                height: thumbnail.height,
+                url: self
+                    .put(&thumbnail_key, image.content_type, thumbnail.bytes)
+                    .await?,
+            });
+        }
+
+        Ok(UploadedImage {
+            url,
+            key,
+            backend: self.store.backend().to_string(),
+            file_name,
+            content_type: image.content_type.to_string(),
+            width: image.original.width,
+            height: image.original.height,
+            bytes,
+            thumbnails,
+        })
+    }
+
+    /// Name of the backend new uploads go to
+    pub fn backend(&self) -> &'static str {
+        self.store.backend()
+    }
+
+    /// Remove the original and thumbnail files of an asset held by this backend
+    pub async fn delete(&self, asset: &MediaAsset) -> ApiResult<()> {
+        for key in asset.keys() {
+            self.store.delete(key).await.map_err(|e| {
+                tracing::error!("Failed to delete media '{}': {}", key, e);
+                ApiError::ServiceUnavailable(format!("Media storage failed: {}", e))
+            })?;
+        }
+        Ok(())
+    }
+
+    async fn put(&self, key: &str, content_type: &str, bytes: Vec<u8>) -> ApiResult<String> {
+        self.store.put(key, content_type, bytes).await.map_err(|e| {
+            tracing::error!("Failed to store media '{}': {}", key, e);
+            ApiError::ServiceUnavailable(format!("Media storage failed: {}", e))
+        })
+    }
+}
+
+/// `2024/05/architecture-diagram-1a2b3c4d`, dated and unique, readable from the file name
+fn storage_key_base(file_name: Option<&str>) -> String {
+    let stem = file_name
+        .map(|name| name.rsplit(['/', '\\']).next().unwrap_or(name))
+        .map(|name| name.rsplit_once('.').map_or(name, |(stem, _)| stem))
+        .map(slugify)
+        .unwrap_or_default();
+    let stem: String = stem
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+        .take(MAX_STEM_CHARS)
+        .collect();
+    let stem = stem.trim_matches('-');
+
+    let id = uuid::Uuid::new_v4().simple().to_string();
+    let prefix = chrono::Utc::now().format("%Y/%m");
+    if stem.is_empty() {
+        format!("{}/{}", prefix, &id[..12])
+    } else {
+        format!("{}/{}-{}", prefix, stem, &id[..8])
+    }
+}
@@ -0,0 +1,230 @@
+use crate::error::{ApiError, ApiResult};
+use image::{
+    codecs::jpeg::JpegEncoder, metadata::Orientation, DynamicImage, ImageDecoder, ImageFormat,
+    ImageReader, Limits,
+};
+use std::io::Cursor;
+
+/// Widths of the generated thumbnails, only those smaller than the upload are made
+pub const THUMBNAIL_WIDTHS: [u32; 2] = [320, 960];
+/// Largest accepted width or height in pixels, guards against decompression bombs
+const MAX_DIMENSION: u32 = 8000;
+/// Quality of re-encoded JPEG images and thumbnails
+const JPEG_QUALITY: u8 = 85;
+
+/// Accepted image formats with their MIME type and file extension
+const ACCEPTED: [(ImageFormat, &str, &str); 3] = [
+    (ImageFormat::Jpeg, "image/jpeg", "jpg"),
+    (ImageFormat::Png, "image/png", "png"),
+    (ImageFormat::WebP, "image/webp", "webp"),
+];
+
+/// One encoded rendition of an upload
+#[derive(Debug)]
+pub struct ImageVariant {
+    pub width: u32,
+    pub height: u32,
+    pub bytes: Vec<u8>,
+}
+
+/// A validated upload, re-encoded without metadata, with its thumbnails
+#[derive(Debug)]
+pub struct ProcessedImage {
+    pub content_type: &'static str,
+    pub extension: &'static str,
+    pub original: ImageVariant,
+    pub thumbnails: Vec<ImageVariant>,
+}
+
+/// Validate an uploaded image and re-encode it
+/// The format is sniffed from the bytes and must match the declared `Content-Type`;
+/// decoding and encoding again drops EXIF and other metadata (GPS, camera serials),
+/// after the EXIF orientation has been applied to the pixels
+pub fn process(
+    declared: Option<&str>,
+    bytes: &[u8],
+    max_bytes: usize,
+) -> ApiResult<ProcessedImage> {
+    if bytes.is_empty() {
+        return Err(ApiError::BadRequest("Uploaded file is empty".to_string()));
+    }
+    if bytes.len() > max_bytes {
+        return Err(ApiError::PayloadTooLarge(format!(
+            "Image is {} bytes, the limit is {} bytes",
+            bytes.len(),
+            max_bytes
+        )));
+    }
+
+    let unsupported = || {
+        ApiError::UnsupportedMediaType("Only JPEG, PNG and WebP images are accepted".to_string())
+    };
+    let sniffed = image::guess_format(bytes).map_err(|_| unsupported())?;
+    let (format, content_type, extension) = ACCEPTED
+        .into_iter()
+        .find(|(format, _, _)| *format == sniffed)
+        .ok_or_else(unsupported)?;
+
+    if let Some(declared) = declared {
+        let declared = declared.split(';').next().unwrap_or_default().trim();
+        let declared = declared.to_ascii_lowercase();
+        let matches = declared == content_type
+            || (declared == "image/jpg" && format == ImageFormat::Jpeg)
+            || declared == "application/octet-stream";
+        if !matches {
+            return Err(ApiError::UnsupportedMediaType(format!(
+                "File is declared as '{}' but its content is {}",
+                declared, content_type
+            )));
+        }
+    }
+
+    let image = decode(bytes, format)?;
+    let original = encode(&image, format)?;
+    let thumbnails = THUMBNAIL_WIDTHS
+        .into_iter()
+        .filter(|width| *width < image.width())
+        .map(|width| encode(&image.thumbnail(width, image.height()), format))
+        .collect::<ApiResult<Vec<_>>>()?;
+
+    Ok(ProcessedImage {
+        content_type,
+        extension,
+        original,
+        thumbnails,
+    })
+}
+
+fn decode(bytes: &[u8], format: ImageFormat) -> ApiResult<DynamicImage> {
+    let invalid =
+        |e: image::ImageError| ApiError::ValidationError(format!("Cannot decode image: {}", e));
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_DIMENSION);
+    limits.max_image_height = Some(MAX_DIMENSION);
+    let mut reader = ImageReader::with_format(Cursor::new(bytes), format);
+    reader.limits(limits);
+
+    let mut decoder = reader.into_decoder().map_err(invalid)?;
+    let orientation = decoder.orientation().unwrap_or(Orientation::NoTransforms);
+    let mut image = DynamicImage::from_decoder(decoder).map_err(invalid)?;
+    image.apply_orientation(orientation);
+    Ok(image)
+}
+
+fn encode(image: &DynamicImage, format: ImageFormat) -> ApiResult<ImageVariant> {
+    let failed =
+        |e: image::ImageError| ApiError::InternalError(format!("Failed to encode image: {}", e));
+
+    let mut bytes = Vec::new();
+    match format {
+        // JPEG has no alpha channel, the WebP encoder only takes 8-bit RGB(A)
+        ImageFormat::Jpeg => DynamicImage::ImageRgb8(image.to_rgb8())
+            .write_with_encoder(JpegEncoder::new_with_quality(&mut bytes, JPEG_QUALITY))
+            .map_err(failed)?,
+        ImageFormat::WebP => DynamicImage::ImageRgba8(image.to_rgba8())
+            .write_to(&mut Cursor::new(&mut bytes), format)
+            .map_err(failed)?,
+        _ => image
+            .write_to(&mut Cursor::new(&mut bytes), format)
+            .map_err(failed)?,
+    }
+
+    Ok(ImageVariant {
+        width: image.width(),
+        height: image.height(),
+        bytes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+
+    fn jpeg(width: u32, height: u32) -> Vec<u8> {
+        let image =
+            DynamicImage::ImageRgb8(RgbImage::from_pixel(width, height, Rgb([200, 40, 40])));
+        encode(&image, ImageFormat::Jpeg).unwrap().bytes
+    }
+
+    /// Insert an EXIF APP1 segment with the given orientation right after the JPEG SOI marker
+    fn with_exif(jpeg: &[u8], orientation: u16) -> Vec<u8> {
+        let mut tiff = b"MM\0\x2a\0\0\0\x08".to_vec();
+        tiff.extend_from_slice(&1u16.to_be_bytes());
+        tiff.extend_from_slice(&[0x01, 0x12, 0x00, 0x03, 0x00, 0x00, 0x00, 0x01]);
+        tiff.extend_from_slice(&orientation.to_be_bytes());
+        tiff.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+
+        let mut segment = b"Exif\0\0".to_vec();
+        segment.extend_from_slice(&tiff);
+        let mut out = jpeg[..2].to_vec();
+        out.extend_from_slice(&[0xFF, 0xE1]);
+        out.extend_from_slice(&(segment.len() as u16 + 2).to_be_bytes());
+        out.extend_from_slice(&segment);
+        out.extend_from_slice(&jpeg[2..]);
+        out
+    }
+
+    fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+        haystack
+            .windows(needle.len())
+            .any(|window| window == needle)
+    }
+
+    #[test]
+    fn test_exif_is_stripped_after_applying_orientation() {
+        // Orientation 6 stores a portrait photo sideways
+        let upload = with_exif(&jpeg(1200, 800), 6);
+        assert!(contains(&upload, b"Exif\0\0"));
+
+        let processed = process(Some("image/jpeg"), &upload, usize::MAX).unwrap();
+        assert_eq!(processed.content_type, "image/jpeg");
+        assert_eq!(processed.extension, "jpg");
+        assert_eq!(
+            (processed.original.width, processed.original.height),
+            (800, 1200)
+        );
+        assert!(!contains(&processed.original.bytes, b"Exif\0\0"));
+
+        let sizes: Vec<_> = processed
+            .thumbnails
+            .iter()
+            .map(|thumbnail| (thumbnail.width, thumbnail.height))
+            .collect();
+        assert_eq!(sizes, vec![(320, 480)]);
+        assert!(processed
+            .thumbnails
+            .iter()
+            .all(|thumbnail| image::guess_format(&thumbnail.bytes).unwrap() == ImageFormat::Jpeg));
+    }
+
+    #[test]
+    fn test_type_and_size_are_checked() {
+        let upload = jpeg(16, 16);
+
+        assert!(matches!(
+            process(Some("image/png"), &upload, usize::MAX),
+            Err(ApiError::UnsupportedMediaType(_))
+        ));
+        assert!(matches!(
+            process(
+                Some("image/svg+xml"),
+                b"<svg xmlns='http://www.w3.org/2000/svg'/>",
+                usize::MAX
+            ),
+            Err(ApiError::UnsupportedMediaType(_))
+        ));
+        assert!(matches!(
+            process(None, &upload, upload.len() - 1),
+            Err(ApiError::PayloadTooLarge(_))
+        ));
+        assert!(matches!(
+            process(Some("image/jpeg"), &upload[..upload.len() / 2], usize::MAX),
+            Err(ApiError::ValidationError(_))
+        ));
+
+        let processed = process(Some("image/jpg"), &upload, upload.len()).unwrap();
+        assert!(processed.thumbnails.is_empty());
+    }
+}
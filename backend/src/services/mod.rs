@@ -0,0 +1,8 @@
+pub mod link_health;
+pub mod markdown;
+pub mod media;
+pub mod publisher;
+pub mod slug;
+pub mod verification;
+
+pub use slug::SlugService;
@@ -0,0 +1,129 @@
+use crate::{error::ApiResult, models::ContentKind, repositories::Repositories};
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+/// Pause between ticks when PUBLISH_INTERVAL_SECS is not configured
+pub const DEFAULT_PUBLISH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Slugs of the content published by one tick
+#[derive(Debug, Default, PartialEq)]
+pub struct Published {
+    pub projects: Vec<String>,
+    pub certificates: Vec<String>,
+}
+
+/// Flips scheduled projects and certificates to published once their `publish_at` has passed
+pub struct ScheduledPublisher {
+    repositories: Repositories,
+}
+
+impl ScheduledPublisher {
+    pub fn new(repositories: Repositories) -> Self {
+        Self { repositories }
+    }
+
+    /// Publish everything due at `now`
+    pub async fn publish_due(&self, now: DateTime<Utc>) -> ApiResult<Published> {
+        Ok(Published {
+            projects: self.repositories.projects.publish_due(now).await?,
+            certificates: self.repositories.certificates.publish_due(now).await?,
+        })
+    }
+
+    /// Publish due content now and then every `interval`
+    pub async fn run_every(&self, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            ticker.tick().await;
+            let published = match self.publish_due(Utc::now()).await {
+                Ok(published) => published,
+                Err(e) => {
+                    tracing::error!("Scheduled publishing failed: {}", e);
+                    continue;
+                }
+            };
+            for (kind, slugs) in [
+                (ContentKind::Project, &published.projects),
+                (ContentKind::Certificate, &published.certificates),
+            ] {
+                if !slugs.is_empty() {
+                    tracing::info!(
+                        "Published scheduled {}s: {}",
+                        kind.as_str(),
+                        slugs.join(", ")
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Certificate, Project, PublicationStatus};
+    use chrono::Duration as ChronoDuration;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_only_due_scheduled_content_is_published() {
+        let repositories = Repositories::in_memory();
+        let now = Utc::now();
+        for (slug, publication, publish_at) in [
+            ("due", "scheduled", Some(now - ChronoDuration::minutes(1))),
+            ("later", "scheduled", Some(now + ChronoDuration::hours(1))),
+            ("draft", "draft", None),
+        ] {
+            let project: Project = serde_json::from_value(json!({
+                "slug": slug,
+                "title": slug,
+                "technologies": ["Rust"],
+                "features": [],
+                "githubUrl": format!("https://github.com/example/{}", slug),
+                "publication": publication,
+                "publish_at": publish_at
+            }))
+            .unwrap();
+            repositories.projects.create(project).await.unwrap();
+        }
+        let certificate: Certificate = serde_json::from_value(json!({
+            "name": "Rust",
+            "issuer": "Issuer",
+            "link": "https://example.com/verify",
+            "slug": "rust",
+            "publication": "scheduled",
+            "publish_at": now
+        }))
+        .unwrap();
+        repositories.certificates.create(certificate).await.unwrap();
+
+        let publisher = ScheduledPublisher::new(repositories.clone());
+        let published = publisher.publish_due(now).await.unwrap();
+        assert_eq!(published.projects, ["due"]);
+        assert_eq!(published.certificates, ["rust"]);
+
+        let due = repositories
+            .projects
+            .get_by_slug("due")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(due.publication, PublicationStatus::Published);
+        assert_eq!(due.publish_at, None);
+        assert_eq!(due.version, 1);
+        let later = repositories
+            .projects
+            .get_by_slug("later")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(later.publication, PublicationStatus::Scheduled);
+
+        // Nothing left to publish until the next one falls due
+        assert_eq!(
+            publisher.publish_due(now).await.unwrap(),
+            Published::default()
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{
+    error::{ApiError, ApiResult},
+    models::ContentKind,
+    repositories::Repositories,
+};
+
+/// Highest numeric suffix tried before giving up on a base slug
+const MAX_SUFFIX: u32 = 100;
+
+/// Lowercase, dash-separated slug of free text ("Rust & Axum API" -> "rust-axum-api")
+pub fn slugify(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect::<String>()
+        .split('-')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Slug generation, uniqueness checks and redirects shared by projects and certificates
+pub struct SlugService {
+    repositories: Repositories,
+}
+
+impl SlugService {
+    pub fn new(repositories: Repositories) -> Self {
+        Self { repositories }
+    }
+
+    /// Trashed content keeps its slug until purged, so it counts as taken
+    async fn exists(&self, kind: ContentKind, slug: &str) -> ApiResult<bool> {
+        match kind {
+            ContentKind::Project => self.repositories.projects.slug_in_use(slug).await,
+            ContentKind::Certificate => self.repositories.certificates.slug_in_use(slug).await,
+        }
+    }
+
+    /// Unused slug for new content, suffixing `-2`, `-3`, ... on collisions
+    pub async fn generate(&self, kind: ContentKind, text: &str) -> ApiResult<String> {
+        let base = slugify(text);
+        if base.is_empty() {
+            return Err(ApiError::BadRequest(format!(
+                "Cannot derive a slug from '{}'",
+                text
+            )));
+        }
+
+        for n in 1..=MAX_SUFFIX {
+            let candidate = match n {
+                1 => base.clone(),
+                n => format!("{}-{}", base, n),
+            };
+            if !self.exists(kind, &candidate).await? {
+                return Ok(candidate);
+            }
+        }
+
+        Err(ApiError::Conflict(format!(
+            "No free slug left for '{}'",
+            base
+        )))
+    }
+
+    /// Normalized slug for an explicit rename, conflicts are rejected rather than suffixed
+    /// An empty request keeps the current slug
+    pub async fn rename(
+        &self,
+        kind: ContentKind,
+        current: &str,
+        requested: &str,
+    ) -> ApiResult<String> {
+        let requested = slugify(requested);
+        if requested.is_empty() || requested == current {
+            return Ok(current.to_string());
+        }
+
+        if self.exists(kind, &requested).await? {
+            return Err(ApiError::Conflict(format!(
+                "Slug '{}' is already in use",
+                requested
+            )));
+        }
+
+        Ok(requested)
+    }
+
+    /// Keep the old slug resolving after a rename
+    pub async fn record_rename(&self, kind: ContentKind, from: &str, to: &str) -> ApiResult<()> {
+        if from == to {
+            return Ok(());
+        }
+        self.repositories.redirects.record(kind, from, to).await
+    }
+
+    /// Current slug of renamed content
+    pub async fn resolve(&self, kind: ContentKind, slug: &str) -> ApiResult<Option<String>> {
+        self.repositories.redirects.resolve(kind, slug).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Certificate;
+
+    fn certificate(slug: &str) -> Certificate {
+        Certificate {
+            id: None,
+            name: "Rust".to_string(),
+            issuer: "Coursera".to_string(),
+            link: "https://coursera.org/verify/abc".to_string(),
+            slug: slug.to_string(),
+            issue_date: None,
+            embedding: None,
+            embedding_model: None,
+            embedding_dimensions: None,
+            expires_at: None,
+            credential_id: None,
+            image_url: None,
+            verified: false,
+            verified_at: None,
+            publication: Default::default(),
+            publish_at: None,
+            deleted_at: None,
+            deleted_by: None,
+            version: 0,
+        }
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("  Rust & Axum: REST API!  "), "rust-axum-rest-api");
+        assert_eq!(slugify("---"), "");
+    }
+
+    #[tokio::test]
+    async fn test_generate_suffixes_and_rename_conflicts() {
+        let repositories = Repositories::in_memory();
+        let slugs = SlugService::new(repositories.clone());
+        let kind = ContentKind::Certificate;
+
+        for slug in ["rust", "rust-2"] {
+            repositories
+                .certificates
+                .create(certificate(slug))
+                .await
+                .unwrap();
+        }
+        assert_eq!(slugs.generate(kind, "Rust").await.unwrap(), "rust-3");
+        assert_eq!(
+            slugs.generate(ContentKind::Project, "Rust").await.unwrap(),
+            "rust"
+        );
+        assert!(matches!(
+            slugs.generate(kind, "!!").await,
+            Err(ApiError::BadRequest(_))
+        ));
+
+        assert_eq!(slugs.rename(kind, "rust", "").await.unwrap(), "rust");
+        assert_eq!(
+            slugs.rename(kind, "rust", "Rust Lang").await.unwrap(),
+            "rust-lang"
+        );
+        assert!(matches!(
+            slugs.rename(kind, "rust", "rust-2").await,
+            Err(ApiError::Conflict(_))
+        ));
+    }
+}
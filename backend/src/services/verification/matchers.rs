@@ -0,0 +1,169 @@
+use scraper::{Html, Selector};
+
+/// Text of a verification page, normalized for matching
+/// Script and style contents are left out, they often name other users or courses
+#[derive(Debug, Default)]
+pub struct IssuerPage {
+    pub title: String,
+    pub og_title: String,
+    pub og_description: String,
+    /// `h1` and `h2` headings
+    pub headings: String,
+    /// Visible text of the whole document
+    pub text: String,
+}
+
+impl IssuerPage {
+    pub fn parse(html: &str) -> Self {
+        let document = Html::parse_document(html);
+        let select = |selector: &str| -> Vec<String> {
+            let selector = Selector::parse(selector).expect("valid selector");
+            document
+                .select(&selector)
+                .map(|element| match element.value().attr("content") {
+                    Some(content) => content.to_string(),
+                    None => element.text().collect::<Vec<_>>().join(" "),
+                })
+                .collect()
+        };
+
+        let text: Vec<&str> = document
+            .root_element()
+            .descendants()
+            .filter_map(|node| {
+                let text = node.value().as_text()?;
+                let parent = node.parent()?.value().as_element()?;
+                (!matches!(parent.name(), "script" | "style" | "noscript" | "template"))
+                    .then_some(&**text)
+            })
+            .collect();
+
+        Self {
+            title: normalize(&select("title").join(" ")),
+            og_title: normalize(&select(r#"meta[property="og:title"]"#).join(" ")),
+            og_description: normalize(&select(r#"meta[property="og:description"]"#).join(" ")),
+            headings: normalize(&select("h1, h2").join(" ")),
+            text: normalize(&text.join(" ")),
+        }
+    }
+}
+
+/// Lowercase words separated by single spaces, punctuation dropped
+fn normalize(text: &str) -> String {
+    text.chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Whether `needle` appears in any of the normalized `sources` as whole words
+fn found(needle: &str, sources: &[&str]) -> bool {
+    let needle = normalize(needle);
+    !needle.is_empty()
+        && sources
+            .iter()
+            .any(|source| format!(" {} ", source).contains(&format!(" {} ", needle)))
+}
+
+fn require(needle: &str, sources: &[&str], what: &str) -> Result<(), String> {
+    if found(needle, sources) {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} \"{}\" not found on the issuer page",
+            what, needle
+        ))
+    }
+}
+
+/// Knows where one issuer's verification pages name the credential and its holder
+pub trait IssuerMatcher: Send + Sync {
+    /// Short name reported with the outcome
+    fn name(&self) -> &'static str;
+
+    /// Whether this matcher understands pages served from `host`
+    fn handles(&self, host: &str) -> bool;
+
+    /// Ok when the page names the credential and its holder, otherwise what is missing
+    fn check(&self, page: &IssuerPage, credential: &str, holder: &str) -> Result<(), String>;
+}
+
+fn on_domain(host: &str, domains: &[&str]) -> bool {
+    domains
+        .iter()
+        .any(|domain| host == *domain || host.ends_with(&format!(".{}", domain)))
+}
+
+/// Coursera verify pages: course name in the title or heading, learner in the page text
+pub struct CourseraMatcher;
+
+impl IssuerMatcher for CourseraMatcher {
+    fn name(&self) -> &'static str {
+        "coursera"
+    }
+
+    fn handles(&self, host: &str) -> bool {
+        on_domain(host, &["coursera.org"])
+    }
+
+    fn check(&self, page: &IssuerPage, credential: &str, holder: &str) -> Result<(), String> {
+        require(
+            credential,
+            &[&page.og_title, &page.title, &page.headings],
+            "Course",
+        )?;
+        require(holder, &[&page.text, &page.og_description], "Learner")
+    }
+}
+
+/// Credly badges: rendered client-side, so both names are read from the page metadata
+pub struct CredlyMatcher;
+
+impl IssuerMatcher for CredlyMatcher {
+    fn name(&self) -> &'static str {
+        "credly"
+    }
+
+    fn handles(&self, host: &str) -> bool {
+        on_domain(host, &["credly.com", "youracclaim.com"])
+    }
+
+    fn check(&self, page: &IssuerPage, credential: &str, holder: &str) -> Result<(), String> {
+        let metadata = [
+            page.og_title.as_str(),
+            page.og_description.as_str(),
+            page.title.as_str(),
+        ];
+        require(credential, &metadata, "Badge")?;
+        require(
+            holder,
+            &[&page.og_title, &page.og_description, &page.text],
+            "Earner",
+        )
+    }
+}
+
+/// Any other issuer: credential in the page title, holder anywhere in the visible text
+pub struct GenericMatcher;
+
+impl IssuerMatcher for GenericMatcher {
+    fn name(&self) -> &'static str {
+        "generic"
+    }
+
+    fn handles(&self, _host: &str) -> bool {
+        true
+    }
+
+    fn check(&self, page: &IssuerPage, credential: &str, holder: &str) -> Result<(), String> {
+        require(
+            credential,
+            &[&page.title, &page.og_title, &page.headings],
+            "Certificate",
+        )?;
+        require(holder, &[&page.text], "Holder")
+    }
+}
@@ -0,0 +1,184 @@
+//! Checks that a certificate's `link` leads to an issuer page naming the credential and its holder
+
+mod matchers;
+
+pub use matchers::{CourseraMatcher, CredlyMatcher, GenericMatcher, IssuerMatcher, IssuerPage};
+
+use crate::models::Certificate;
+use reqwest::{Client, Url};
+use std::time::Duration;
+
+/// Page load timeout when CERTIFICATE_VERIFY_TIMEOUT_SECS is not configured
+pub const DEFAULT_VERIFY_TIMEOUT: Duration = Duration::from_secs(15);
+/// Pages are read up to this size, verification text sits near the top
+const MAX_PAGE_BYTES: usize = 2 * 1024 * 1024;
+
+/// What the issuer page said about a certificate
+#[derive(Debug, Clone, PartialEq)]
+pub struct Verdict {
+    /// Matcher that read the page
+    pub matcher: &'static str,
+    /// What was missing from the page, None when the certificate is verified
+    pub mismatch: Option<String>,
+}
+
+/// Fetches verification pages and hands them to the matcher of their issuer
+pub struct CertificateVerifier {
+    client: Client,
+    holder: String,
+    /// Tried in order, the last one accepts any site
+    matchers: Vec<Box<dyn IssuerMatcher>>,
+}
+
+impl CertificateVerifier {
+    /// `holder` is the name certificates are expected to be issued to
+    pub fn new(holder: String, timeout: Duration) -> Self {
+        let client = Client::builder()
+            .timeout(timeout)
+            .user_agent(concat!(
+                "portfolio-certificate-verifier/",
+                env!("CARGO_PKG_VERSION")
+            ))
+            .build()
+            .expect("certificate verifier HTTP client");
+        Self {
+            client,
+            holder,
+            matchers: vec![
+                Box::new(CourseraMatcher),
+                Box::new(CredlyMatcher),
+                Box::new(GenericMatcher),
+            ],
+        }
+    }
+
+    /// Load the certificate's link and check it, Err when the page could not be loaded
+    pub async fn verify(&self, certificate: &Certificate) -> Result<Verdict, String> {
+        let url = Url::parse(&certificate.link).map_err(|e| format!("Invalid link: {}", e))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err("Not an http(s) URL".to_string());
+        }
+
+        let mut response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| e.without_url().to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("Issuer page answered {}", response.status()));
+        }
+        // Redirects may land on another host, e.g. a short link to the issuer
+        let url = response.url().clone();
+
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+            body.extend_from_slice(&chunk);
+            if body.len() >= MAX_PAGE_BYTES {
+                body.truncate(MAX_PAGE_BYTES);
+                break;
+            }
+        }
+
+        Ok(self.check_page(&url, &String::from_utf8_lossy(&body), certificate))
+    }
+
+    /// Check a loaded page with the matcher for its host
+    pub fn check_page(&self, url: &Url, html: &str, certificate: &Certificate) -> Verdict {
+        let host = url.host_str().unwrap_or_default().to_lowercase();
+        let matcher = self
+            .matchers
+            .iter()
+            .find(|matcher| matcher.handles(&host))
+            .expect("the generic matcher handles every host");
+        let page = IssuerPage::parse(html);
+
+        Verdict {
+            matcher: matcher.name(),
+            mismatch: matcher.check(&page, &certificate.name, &self.holder).err(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verifier() -> CertificateVerifier {
+        CertificateVerifier::new("Jane Doe".to_string(), Duration::from_secs(1))
+    }
+
+    fn certificate(name: &str) -> Certificate {
+        serde_json::from_value(serde_json::json!({
+            "name": name,
+            "issuer": "Issuer",
+            "link": "https://example.com/verify",
+            "slug": "certificate"
+        }))
+        .unwrap()
+    }
+
+    fn check(url: &str, fixture: &str, name: &str) -> Verdict {
+        verifier().check_page(&Url::parse(url).unwrap(), fixture, &certificate(name))
+    }
+
+    #[test]
+    fn test_issuer_fixtures_are_matched_by_their_matcher() {
+        let coursera = include_str!("fixtures/coursera.html");
+        let credly = include_str!("fixtures/credly.html");
+        let generic = include_str!("fixtures/generic.html");
+
+        let verdict = check(
+            "https://www.coursera.org/account/accomplishments/specialization/ABC123",
+            coursera,
+            "Machine Learning Specialization",
+        );
+        assert_eq!(verdict.matcher, "coursera");
+        assert_eq!(verdict.mismatch, None);
+
+        let verdict = check(
+            "https://www.credly.com/badges/0b1c2d3e",
+            credly,
+            "AWS Certified Solutions Architect - Associate",
+        );
+        assert_eq!(verdict.matcher, "credly");
+        assert_eq!(verdict.mismatch, None);
+
+        let verdict = check(
+            "https://academy.example.com/c/42",
+            generic,
+            "Rust Fundamentals",
+        );
+        assert_eq!(verdict.matcher, "generic");
+        assert_eq!(verdict.mismatch, None);
+
+        // Names only present in scripts or styles do not count
+        let verdict = check(
+            "https://coursera.org/verify/ABC123",
+            coursera,
+            "Deep Learning",
+        );
+        assert_eq!(
+            verdict.mismatch.as_deref(),
+            Some("Course \"Deep Learning\" not found on the issuer page")
+        );
+        let other_holder =
+            CertificateVerifier::new("John Smith".to_string(), Duration::from_secs(1)).check_page(
+                &Url::parse("https://coursera.org/verify/ABC123").unwrap(),
+                coursera,
+                &certificate("Machine Learning Specialization"),
+            );
+        assert_eq!(
+            other_holder.mismatch.as_deref(),
+            Some("Learner \"John Smith\" not found on the issuer page")
+        );
+
+        // Every word of the name has to appear, in order
+        let verdict = check(
+            "https://academy.example.com/c/42",
+            generic,
+            "Rust Fundamentals II",
+        );
+        assert!(verdict.mismatch.is_some());
+    }
+}
@@ -4,7 +4,7 @@ const BACKEND_URL = process.env.BACKEND_URL;
 
 export async function GET() {
   try {
-    const response = await fetch(`${BACKEND_URL}/api/v1/certificates`, {
+    const response = await fetch(`${BACKEND_URL}/api/v1/certificates?format=array`, {
       method: "GET",
       headers: {
         "Content-Type": "application/json",
@@ -24,7 +24,11 @@ export async function GET(
     }
 
     const data = await response.json();
-    return NextResponse.json(data);
+    // Forward the version tag so edits can be sent back with If-Match
+    const etag = response.headers.get("ETag");
+    return NextResponse.json(data, {
+      headers: etag ? { ETag: etag } : undefined,
+    });
   } catch (error) {
     console.error("Error proxying project request:", error);
     return NextResponse.json(
@@ -47,6 +51,8 @@ export async function PUT(
         { status: 401 },
       );
     }
+    // Stale edits are rejected by the backend with 412
+    const ifMatch = request.headers.get("If-Match");
 
     const { slug } = await params;
     const formData = await request.formData();
@@ -55,6 +61,7 @@ export async function PUT(
       method: "PUT",
       headers: {
         Authorization: authHeader,
+        ...(ifMatch ? { "If-Match": ifMatch } : {}),
       },
       body: formData,
     });
@@ -71,7 +78,10 @@ export async function PUT(
     }
 
     const data = await response.json();
-    return NextResponse.json(data);
+    const etag = response.headers.get("ETag");
+    return NextResponse.json(data, {
+      headers: etag ? { ETag: etag } : undefined,
+    });
   } catch (error) {
     console.error("Error proxying project update:", error);
     return NextResponse.json(
@@ -94,12 +104,15 @@ export async function DELETE(
         { status: 401 },
       );
     }
+    // Stale edits are rejected by the backend with 412
+    const ifMatch = request.headers.get("If-Match");
 
     const { slug } = await params;
     const response = await fetch(`${BACKEND_URL}/api/v1/projects/${slug}`, {
       method: "DELETE",
       headers: {
         Authorization: authHeader,
+        ...(ifMatch ? { "If-Match": ifMatch } : {}),
       },
     });
 
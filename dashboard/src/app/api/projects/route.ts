@@ -4,7 +4,7 @@ const BACKEND_URL = process.env.BACKEND_URL;
 
 export async function GET() {
   try {
-    const response = await fetch(`${BACKEND_URL}/api/v1/projects`, {
+    const response = await fetch(`${BACKEND_URL}/api/v1/projects?format=array`, {
       method: "GET",
       headers: {
         "Content-Type": "application/json",
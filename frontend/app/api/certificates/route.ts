@@ -24,13 +24,13 @@ export async function GET(request: NextRequest) {
   try {
     console.log(
       "🔍 [Certificates API] Fetching from:",
-      `${BACKEND_URL}/api/v1/certificates`,
+      `${BACKEND_URL}/api/v1/certificates?format=array`,
     );
 
     const controller = new AbortController();
     const timeoutId = setTimeout(() => controller.abort(), 10000);
 
-    const response = await fetch(`${BACKEND_URL}/api/v1/certificates`, {
+    const response = await fetch(`${BACKEND_URL}/api/v1/certificates?format=array`, {
       method: "GET",
       headers: {
         "Content-Type": "application/json",
@@ -22,13 +22,13 @@ export async function GET(request: NextRequest) {
   try {
     console.log(
       "🔍 [Projects API] Fetching from:",
-      `${BACKEND_URL}/api/v1/projects`,
+      `${BACKEND_URL}/api/v1/projects?format=array`,
     );
 
     const controller = new AbortController();
     const timeoutId = setTimeout(() => controller.abort(), 10000);
 
-    const response = await fetch(`${BACKEND_URL}/api/v1/projects`, {
+    const response = await fetch(`${BACKEND_URL}/api/v1/projects?format=array`, {
       method: "GET",
       headers: {
         "Content-Type": "application/json",
@@ -13,7 +13,7 @@ async function fetchProjects(): Promise<Project[]> {
       process.env.BACKEND_URL || process.env.NEXT_PUBLIC_BACKEND_URL;
     if (!backendUrl) return [];
 
-    const res = await fetch(`${backendUrl}/api/v1/projects`, {
+    const res = await fetch(`${backendUrl}/api/v1/projects?format=array`, {
       cache: "no-store",
     });
 